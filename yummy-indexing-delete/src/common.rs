@@ -1,17 +1,29 @@
 pub use std::{
+    collections::{BTreeMap, HashMap, HashSet},
     env,
-    fs::File,
-    io::{BufReader, Write},
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Read, Write},
+    os::unix::fs::OpenOptionsExt,
     ops::Deref,
-    sync::Arc,
+    path::{Path, PathBuf},
+    str::FromStr,
+    process::Command,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, RwLock,
+    },
 };
 
 pub use derive_new::new;
 
 
 pub use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    signal::unix::{signal, Signal, SignalKind},
     sync::{OwnedSemaphorePermit, Semaphore},
-    time::Duration,
+    task::JoinHandle,
+    time::{Duration, Instant},
 };
 
 pub use dotenv::dotenv;
@@ -22,7 +34,7 @@ pub use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 pub use anyhow::anyhow;
 
-pub use serde_json::{from_reader, Value};
+pub use serde_json::{from_reader, json, Value};
 
 pub use async_trait::async_trait;
 
@@ -42,19 +54,56 @@ pub use once_cell::sync::{
 // elasticsearch::indices::IndicesDeleteParts;
 
 pub use elasticsearch::{
-    cat::CatIndicesParts,
+    auth::{ClientCertificate, Credentials},
+    cert::{Certificate, CertificateValidation},
+    cat::{CatAllocationParts, CatIndicesParts},
+    cluster::ClusterStateParts,
+    params::{Bytes, ExpandWildcards, Level},
+    http::headers::{HeaderValue, AUTHORIZATION},
+    http::request::JsonBody,
     http::response::Response,
     http::transport::Transport as EsTransport,
     http::transport::{SingleNodeConnectionPool, TransportBuilder},
-    http::Url,
-    indices::IndicesDeleteParts, Elasticsearch,
+    http::{StatusCode, Url},
+    indices::{
+        IndicesAddBlockParts, IndicesCloseParts, IndicesCreateParts, IndicesDeleteParts,
+        IndicesForcemergeParts, IndicesGetAliasParts, IndicesGetDataStreamParts,
+        IndicesGetMappingParts, IndicesGetParts, IndicesGetSettingsParts, IndicesPutMappingParts,
+        IndicesPutSettingsParts, IndicesRefreshParts, IndicesStatsParts,
+    },
+    snapshot::{SnapshotCreateParts, SnapshotGetParts, SnapshotRestoreParts},
+    BulkParts, CountParts, Elasticsearch, IndexParts, OpenPointInTimeParts, SearchParts,
 };
 
-pub use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
+pub use rand::{rngs::StdRng, Rng, SeedableRng};
 
-pub use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+pub use chrono::{DateTime, Months, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc, Weekday};
 
-pub use chrono_tz::Asia::Seoul;
+pub use chrono_tz::{Asia::Seoul, Tz};
 
+pub use cron::Schedule;
 
-pub use regex::Regex;
+
+pub use regex::{Match, Regex};
+
+pub use base64::{prelude::BASE64_STANDARD, Engine};
+
+pub use clap::{Args, Parser, Subcommand};
+
+pub use flate2::{read::GzDecoder, write::GzEncoder};
+
+pub use zstd::stream::{read::Decoder as ZstdDecoder, write::Encoder as ZstdEncoder};
+
+pub use sha2::{Digest, Sha256};
+
+pub use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+
+pub use pbkdf2::pbkdf2_hmac;
+
+pub use prometheus::{
+    Encoder, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry,
+    TextEncoder,
+};
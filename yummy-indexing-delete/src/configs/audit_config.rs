@@ -0,0 +1,54 @@
+use crate::common::*;
+
+static AUDIT_CONFIG: OnceCell<AuditConfig> = OnceCell::new();
+
+/* 감사 로그 파일 기본 경로 - logs/ 는 로테이션으로 지워지므로 별도 디렉토리에 남긴다. */
+const DEFAULT_AUDIT_LOG_PATH: &str = "./audit/deletion_audit.jsonl";
+
+#[derive(Debug, Deserialize, Serialize, Getters)]
+#[getset(get = "pub")]
+pub struct AuditConfig {
+    /* 감사 로그를 append-only JSON lines 로 남길 파일 경로 */
+    pub audit_log_path: String,
+    /* 감사 로그를 함께 색인할 Elasticsearch 인덱스 (None 인 경우 파일에만 남긴다.) */
+    pub audit_es_index: Option<String>,
+}
+
+#[doc = "AuditConfig 정보를 전역적으로 초기화해주는 함수"]
+/// # Arguments
+/// * `log_path_override` - 명령행에서 지정한 감사 로그 파일 경로 (None 인 경우 env 의 AUDIT_LOG_PATH 사용)
+/// * `es_index_override` - 명령행에서 지정한 감사 인덱스 (None 인 경우 env 의 AUDIT_ES_INDEX 사용)
+pub fn init_audit_config(log_path_override: Option<&str>, es_index_override: Option<&str>) {
+    let config: AuditConfig = AuditConfig::new(log_path_override, es_index_override);
+
+    AUDIT_CONFIG
+        .set(config)
+        .expect("[Error][init_audit_config] AUDIT_CONFIG is already initialized");
+}
+
+#[doc = "전역화된 AuditConfig 정보를 안전하게 사용가능하게 하는 함수"]
+pub fn get_audit_config() -> &'static AuditConfig {
+    AUDIT_CONFIG
+        .get()
+        .expect("[Error][get_audit_config] AUDIT_CONFIG not initialized")
+}
+
+impl AuditConfig {
+    pub fn new(log_path_override: Option<&str>, es_index_override: Option<&str>) -> Self {
+        let audit_log_path: String = match log_path_override {
+            Some(log_path) => log_path.to_string(),
+            None => env::var("AUDIT_LOG_PATH").unwrap_or(String::from(DEFAULT_AUDIT_LOG_PATH)),
+        };
+
+        let audit_es_index: Option<String> = match es_index_override {
+            Some(es_index) => Some(es_index.to_string()),
+            None => env::var("AUDIT_ES_INDEX").ok(),
+        }
+        .filter(|es_index| !es_index.trim().is_empty());
+
+        Self {
+            audit_log_path,
+            audit_es_index,
+        }
+    }
+}
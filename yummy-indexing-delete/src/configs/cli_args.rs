@@ -0,0 +1,153 @@
+use crate::common::*;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "yummy-indexing-delete",
+    version,
+    about = "Yummy-project 에서 사용되는 Elasticsearch 로그 인덱스를 보존기한 규칙에 따라 정리해주는 프로그램"
+)]
+pub struct CliArgs {
+    #[command(flatten)]
+    pub global: GlobalArgs,
+
+    /* 서브커맨드가 없는 경우 기존 배치와 동일하게 `run` 으로 동작한다. */
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+#[derive(Debug, Args, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct GlobalArgs {
+    /// 인덱스 보존 규칙 파일 경로 (기본값: env 의 INDEX_LIST_PATH)
+    #[arg(long, short = 'c', global = true, env = "INDEX_LIST_PATH")]
+    pub config: Option<String>,
+
+    /// Elasticsearch 클러스터 주소, 콤마로 구분 (기본값: env 의 ES_DB_URL)
+    /// 스킴이 없는 주소는 TLS 설정(ES_CA_CERT, ES_CLIENT_CERT, ES_SKIP_VERIFY)이 있으면 https, 없으면 http 로 접속한다.
+    #[arg(long, global = true)]
+    pub es_url: Option<String>,
+
+    /// Elasticsearch 에 동시에 보낼 수 있는 요청 수 (기본값: env 의 ES_POOL_CNT)
+    #[arg(long, global = true)]
+    pub concurrency: Option<i32>,
+
+    /// 삭제 감사 로그(JSON lines) 파일 경로 (기본값: env 의 AUDIT_LOG_PATH 또는 ./audit/deletion_audit.jsonl)
+    #[arg(long, global = true)]
+    pub audit_log: Option<String>,
+
+    /// 삭제 감사 로그를 함께 남길 Elasticsearch 인덱스 (기본값: env 의 AUDIT_ES_INDEX)
+    #[arg(long, global = true)]
+    pub audit_index: Option<String>,
+
+    /// 리갈 홀드 파일 경로 (기본값: env 의 LEGAL_HOLDS_PATH, 없으면 인덱스 _meta 의 홀드만 사용)
+    #[arg(long, global = true)]
+    pub holds_file: Option<String>,
+
+    /// 규칙 파일의 [safety] 안전 상한을 넘더라도 삭제한다. (의도한 대량 삭제인 경우에만 사용)
+    #[arg(long, global = true)]
+    pub override_safety_caps: bool,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum Commands {
+    /// 보존기한 규칙에 따라 인덱스를 삭제한다.
+    Run,
+    /// 실제 삭제 없이 삭제 계획만 출력한다. (dry-run)
+    Plan {
+        /// 계획을 Json 파일로 저장할 경로
+        #[arg(long, short = 'o')]
+        out: Option<String>,
+    },
+    /// 검토가 끝난 계획 파일에 포함된 인덱스만 삭제한다.
+    Apply {
+        /// `plan --out` 으로 저장한 계획 파일 경로
+        plan_file: String,
+    },
+    /// 인덱스 보존 규칙 파일을 검증한다. (클러스터에 접속하지 않음)
+    Validate,
+    /// 규칙에 해당하는 인덱스와 인덱스의 나이를 보여준다.
+    List,
+    /// 특정 인덱스가 어떤 규칙에 해당하고 어떻게 처리되는지 설명한다.
+    Explain {
+        /// 설명할 인덱스 명
+        index: String,
+    },
+    /// 디스크 사용률이 상한을 넘은 경우, 보존기한과 상관없이 오래된 인덱스부터 삭제한다.
+    Emergency {
+        /// 실제 삭제 없이 긴급 정리 계획만 출력한다.
+        #[arg(long)]
+        dry_run: bool,
+        /// 긴급 정리를 시작하는 디스크 사용률(%) (기본값: 규칙 파일의 [emergency])
+        #[arg(long)]
+        high_disk_percent: Option<f64>,
+        /// 이 디스크 사용률(%) 아래로 내려갈 때까지 삭제한다. (기본값: 규칙 파일의 [emergency])
+        #[arg(long)]
+        target_disk_percent: Option<f64>,
+    },
+    /// 삭제된 인덱스를 스냅샷에서 다른 이름으로 복원한다.
+    Restore {
+        /// 복원할 (삭제된) 인덱스 명
+        index: String,
+        /// 스냅샷 저장소 (기본값: 규칙 파일의 snapshot_repository 전체)
+        #[arg(long)]
+        repository: Option<String>,
+        /// 스냅샷 명 (기본값: 인덱스가 들어있는 가장 최근 스냅샷)
+        #[arg(long)]
+        snapshot: Option<String>,
+        /// 복원될 인덱스 명 앞에 붙일 접두어
+        #[arg(long, default_value = "restored-")]
+        prefix: String,
+        /// 복원 후 보존할 일수 (미지정 시 보존기한 규칙이 삭제하지 않는다.)
+        #[arg(long)]
+        ttl_days: Option<u32>,
+    },
+    /// `archive_before_delete` 로 내보낸 아카이브를 `<prefix><인덱스 명>` 인덱스로 다시 가져온다.
+    Import {
+        /// 인덱스 하나의 아카이브 디렉토리 (`<archive_dir>/<인덱스 명>`)
+        archive: String,
+        /// 가져올 인덱스 명 앞에 붙일 접두어
+        #[arg(long, default_value = "restored-")]
+        prefix: String,
+        /// 가져온 후 보존할 일수 (미지정 시 보존기한 규칙이 삭제하지 않는다.)
+        #[arg(long)]
+        ttl_days: Option<u32>,
+        /// 한 번의 _bulk 요청에 넣을 문서 수
+        #[arg(long, default_value_t = 1000)]
+        bulk_size: usize,
+        /// 동시에 보낼 _bulk 요청 수
+        #[arg(long, default_value_t = 4)]
+        bulk_concurrency: usize,
+    },
+    /// 표준입력의 Elasticsearch 인증 정보(`ES_ID=...` 형식의 줄)를 env 의 ES_SECRETS_KEY 로 암호화해서 저장한다.
+    EncryptSecrets {
+        /// 저장할 비밀 파일 경로 (env 의 ES_SECRETS_FILE 로 지정해서 사용한다.)
+        out: String,
+    },
+    /// 종료될 때까지 cron 스케줄에 따라 보존기한 규칙을 반복 실행한다.
+    Daemon {
+        /// 규칙에 schedule 이 없는 경우 사용할 cron 표현식 (기본값: 규칙 파일의 [schedule] cron)
+        #[arg(long)]
+        cron: Option<String>,
+        /// cron 표현식을 해석할 타임존 (기본값: 규칙 파일의 [schedule] timezone)
+        #[arg(long)]
+        timezone: Option<String>,
+        /// 디스크 사용률을 확인해서 긴급 정리를 실행할 cron 표현식 (기본값: 규칙 파일의 [emergency] schedule)
+        #[arg(long)]
+        emergency_cron: Option<String>,
+        /// Prometheus `/metrics` 엔드포인트를 열 주소 (예: 0.0.0.0:9464, `/nodes` 에서 노드별 상태를 볼 수 있다.)
+        #[arg(long, env = "METRICS_ADDR")]
+        metrics_addr: Option<String>,
+    },
+}
+
+impl Commands {
+    #[doc = "Elasticsearch 클러스터 접속이 필요한 서브커맨드인지 확인해주는 함수"]
+    pub fn requires_cluster(&self) -> bool {
+        !matches!(self, Commands::Validate | Commands::EncryptSecrets { .. })
+    }
+
+    #[doc = "인덱스 보존 규칙 파일이 필요한 서브커맨드인지 확인해주는 함수"]
+    pub fn requires_rules(&self) -> bool {
+        !matches!(self, Commands::EncryptSecrets { .. })
+    }
+}
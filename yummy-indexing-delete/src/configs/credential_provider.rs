@@ -0,0 +1,262 @@
+use crate::common::*;
+
+use crate::configs::elastic_server_config::*;
+
+use crate::utils_module::secret_utils::*;
+
+/* 인증 정보로 사용하는 키 - 어떤 공급자든 같은 키를 사용한다. */
+pub const CREDENTIAL_KEYS: [&str; 4] = ["ES_ID", "ES_PW", "ES_API_KEY", "ES_BEARER_TOKEN"];
+
+/* 암호화된 비밀 파일의 패스프레이즈를 담는 환경변수 */
+pub const SECRETS_KEY_ENV: &str = "ES_SECRETS_KEY";
+
+#[doc = "Elasticsearch 인증 정보를 가져올 곳"]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum CredentialProvider {
+    /* 환경변수 (ES_ID/ES_PW, ES_API_KEY, ES_BEARER_TOKEN) */
+    Env,
+    /* 파일 또는 디렉토리 (ES_CREDENTIALS_FILE) - Docker/Kubernetes 의 secret 마운트 */
+    File(String),
+    /* 표준출력이 인증 정보인 외부 명령 (ES_CREDENTIALS_COMMAND) */
+    Command(String),
+    /* `encrypt-secrets` 로 암호화한 비밀 파일 (ES_SECRETS_FILE, 패스프레이즈는 ES_SECRETS_KEY) */
+    SecretFile(String),
+}
+
+impl CredentialProvider {
+    #[doc = "환경변수에서 인증 정보 공급자를 결정해주는 함수 - 두 가지 이상을 지정하면 panic"]
+    /// ES_CREDENTIALS_FILE, ES_CREDENTIALS_COMMAND, ES_SECRETS_FILE 이 모두 없으면 환경변수를 사용한다.
+    pub fn from_env() -> Self {
+        let mut providers: Vec<CredentialProvider> = [
+            non_empty_env("ES_CREDENTIALS_FILE").map(CredentialProvider::File),
+            non_empty_env("ES_CREDENTIALS_COMMAND").map(CredentialProvider::Command),
+            non_empty_env("ES_SECRETS_FILE").map(CredentialProvider::SecretFile),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        if providers.len() > 1 {
+            panic!("[ENV file read Error][initialize_db_clients()] Only one of 'ES_CREDENTIALS_FILE', 'ES_CREDENTIALS_COMMAND' and 'ES_SECRETS_FILE' can be set");
+        }
+
+        providers.pop().unwrap_or(CredentialProvider::Env)
+    }
+
+    #[doc = "로그에 남길 수 있는 공급자 설명"]
+    pub fn describe(&self) -> String {
+        match self {
+            CredentialProvider::Env => String::from("env"),
+            CredentialProvider::File(path) => format!("file '{}'", path),
+            CredentialProvider::Command(_) => String::from("command"),
+            CredentialProvider::SecretFile(path) => format!("secret file '{}'", path),
+        }
+    }
+
+    #[doc = "공급자에서 인증 정보를 읽어주는 함수 - 교체(rotation)를 반영할 수 있도록 호출할 때마다 새로 읽는다."]
+    /// # Returns
+    /// * Result<ElasticAuth, anyhow::Error>
+    ///
+    /// 공급자가 주지 않은 키는 환경변수에서 가져온다. (예: ES_ID 는 env, ES_PW 는 secret 파일)
+    pub fn load(&self) -> Result<ElasticAuth, anyhow::Error> {
+        let values: HashMap<String, String> = match self {
+            CredentialProvider::Env => HashMap::new(),
+            CredentialProvider::File(path) => Self::read_credential_path(path)?,
+            CredentialProvider::Command(command) => {
+                parse_credentials(&Self::run_credential_command(command)?)
+            }
+            CredentialProvider::SecretFile(path) => {
+                let passphrase: String = non_empty_env(SECRETS_KEY_ENV).ok_or_else(|| {
+                    anyhow!("[Error][CredentialProvider->load] '{}' must be set to read '{}'", SECRETS_KEY_ENV, path)
+                })?;
+                let content: String = std::fs::read_to_string(path).map_err(|e| {
+                    anyhow!("[Error][CredentialProvider->load] Failed to read secrets file '{}': {}", path, e)
+                })?;
+
+                parse_credentials(&decrypt_secrets(&content, &passphrase)?)
+            }
+        };
+
+        ElasticAuth::from_values(|key: &str| {
+            values.get(key).cloned().or_else(|| non_empty_env(key))
+        })
+    }
+
+    #[doc = "인증 정보 파일/디렉토리를 읽어주는 함수"]
+    /// 디렉토리인 경우 키 이름의 파일(ES_ID, ES_PW ...)을 하나씩 읽는다. (Kubernetes secret 볼륨)
+    fn read_credential_path(path: &str) -> Result<HashMap<String, String>, anyhow::Error> {
+        let credential_path: &Path = Path::new(path);
+
+        if !credential_path.is_dir() {
+            let content: String = std::fs::read_to_string(credential_path).map_err(|e| {
+                anyhow!("[Error][CredentialProvider->read_credential_path] Failed to read credentials file '{}': {}", path, e)
+            })?;
+
+            return Ok(parse_credentials(&content));
+        }
+
+        let mut values: HashMap<String, String> = HashMap::new();
+
+        for key in CREDENTIAL_KEYS {
+            let key_path: PathBuf = credential_path.join(key);
+
+            if !key_path.is_file() {
+                continue;
+            }
+
+            let value: String = std::fs::read_to_string(&key_path)?.trim().to_string();
+
+            if !value.is_empty() {
+                values.insert(key.to_string(), value);
+            }
+        }
+
+        Ok(values)
+    }
+
+    #[doc = "외부 명령을 실행해서 표준출력을 가져와주는 함수"]
+    fn run_credential_command(command: &str) -> Result<String, anyhow::Error> {
+        let output: std::process::Output = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .output()
+            .map_err(|e| {
+                anyhow!("[Error][CredentialProvider->run_credential_command] Failed to run credentials command: {}", e)
+            })?;
+
+        if !output.status.success() {
+            return Err(anyhow!(
+                "[Error][CredentialProvider->run_credential_command] Credentials command failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
+
+#[doc = "인증 정보 내용을 키/값으로 나눠주는 함수"]
+/// # Arguments
+/// * `content` - `KEY=VALUE` 형식의 줄들, 또는 비밀값 하나
+///
+/// # Returns
+/// * HashMap<String, String> - `KEY=VALUE` 줄이 하나도 없으면 내용 전체를 ES_PW 로 본다. (Docker secret 파일)
+pub fn parse_credentials(content: &str) -> HashMap<String, String> {
+    let mut values: HashMap<String, String> = HashMap::new();
+
+    for line in content.lines().map(str::trim) {
+        if let Some((key, value)) = line.split_once('=') {
+            let key: &str = key.trim();
+
+            if CREDENTIAL_KEYS.contains(&key) && !value.trim().is_empty() {
+                values.insert(key.to_string(), value.trim().to_string());
+            }
+        }
+    }
+
+    let secret: &str = content.trim();
+
+    if values.is_empty() && !secret.is_empty() {
+        values.insert(String::from("ES_PW"), secret.to_string());
+    }
+
+    values
+}
+
+#[doc = "표준입력의 인증 정보를 암호화해서 비밀 파일로 저장해주는 함수 (`encrypt-secrets` 서브커맨드)"]
+/// # Arguments
+/// * `out_path` - 저장할 비밀 파일 경로
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub fn encrypt_secrets_from_stdin(out_path: &str) -> Result<(), anyhow::Error> {
+    let passphrase: String = non_empty_env(SECRETS_KEY_ENV).ok_or_else(|| {
+        anyhow!("[Error][encrypt_secrets_from_stdin] '{}' must be set", SECRETS_KEY_ENV)
+    })?;
+
+    let mut plaintext: String = String::new();
+    std::io::stdin().lock().read_to_string(&mut plaintext)?;
+
+    /* 저장하기 전에 인증 정보로 해석되는지 확인한다. */
+    let values: HashMap<String, String> = parse_credentials(&plaintext);
+
+    if values.is_empty() {
+        return Err(anyhow!("[Error][encrypt_secrets_from_stdin] No credentials were given on stdin"));
+    }
+
+    let elastic_auth: ElasticAuth =
+        ElasticAuth::from_values(|key: &str| values.get(key).cloned())?;
+
+    let mut out_file: File = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(out_path)?;
+    writeln!(out_file, "{}", encrypt_secrets(&plaintext, &passphrase)?)?;
+
+    let mut keys: Vec<&String> = values.keys().collect();
+    keys.sort();
+
+    println!(
+        "Encrypted {} credentials ({}) to '{}'.",
+        elastic_auth.kind(),
+        keys.iter().map(|key| key.as_str()).collect::<Vec<&str>>().join(", "),
+        out_path
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_value_lines_are_parsed() {
+        let values: HashMap<String, String> =
+            parse_credentials("ES_ID=elastic\n  ES_PW = changeme  \n\n");
+
+        assert_eq!(values.len(), 2);
+        assert_eq!(values["ES_ID"], "elastic");
+        assert_eq!(values["ES_PW"], "changeme");
+    }
+
+    #[test]
+    fn bare_docker_secret_is_password() {
+        let values: HashMap<String, String> = parse_credentials("changeme\n");
+
+        assert_eq!(values.len(), 1);
+        assert_eq!(values["ES_PW"], "changeme");
+    }
+
+    #[test]
+    fn unknown_and_empty_keys_are_ignored() {
+        let values: HashMap<String, String> =
+            parse_credentials("ES_PW=changeme\nOTHER=value\nES_ID=\n# ES_API_KEY=a:b\n");
+
+        assert_eq!(values.len(), 1);
+        assert_eq!(values["ES_PW"], "changeme");
+    }
+
+    #[test]
+    fn value_may_contain_equal_sign() {
+        let values: HashMap<String, String> = parse_credentials("ES_API_KEY=a2V5LWlkOnNlY3JldA==");
+
+        assert_eq!(values["ES_API_KEY"], "a2V5LWlkOnNlY3JldA==");
+    }
+
+    #[test]
+    fn empty_content_has_no_credentials() {
+        assert!(parse_credentials(" \n").is_empty());
+    }
+
+    #[test]
+    fn parsed_credentials_decide_auth() {
+        let values: HashMap<String, String> = parse_credentials("ES_ID=elastic\n");
+
+        /* ES_ID 만 있고 ES_PW 가 없으면 인증 방식을 정할 수 없다. */
+        assert!(ElasticAuth::from_values(|key: &str| values.get(key).cloned()).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use crate::common::*;
 
+use crate::configs::credential_provider::*;
+
 
 static ELASTIC_CONFIG: OnceCell<ElasticServerConfig> = OnceCell::new();
 
@@ -7,14 +9,48 @@ static ELASTIC_CONFIG: OnceCell<ElasticServerConfig> = OnceCell::new();
 #[getset(get = "pub")]
 pub struct ElasticServerConfig {
     pub elastic_host: Vec<String>,
-    pub elastic_id: Option<String>,
-    pub elastic_pw: Option<String>,
+    pub credential_provider: CredentialProvider,
+    /* 공급자에서 마지막으로 읽은 인증 정보 - 데몬 모드에서 교체(rotation)될 수 있다. */
+    #[getset(skip)]
+    elastic_auth: RwLock<ElasticAuth>,
+    pub elastic_tls: ElasticTlsConfig,
     pub elastic_pool_cnt: i32,
 }
 
+#[doc = "Elasticsearch 인증 방식 - 인증 정보는 URL 이 아닌 Transport 의 credentials 로 전달된다."]
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+pub enum ElasticAuth {
+    /* 보안 기능이 꺼진 클러스터 */
+    None,
+    /* ES_ID / ES_PW */
+    Basic { id: String, pw: String },
+    /* ES_API_KEY 를 "id:api_key" 형식으로 지정한 경우 */
+    ApiKey { id: String, key: String },
+    /* ES_API_KEY 를 Kibana 에서 발급한 인코딩된 값(base64)으로 지정한 경우 */
+    EncodedApiKey(String),
+    /* ES_BEARER_TOKEN */
+    Bearer(String),
+}
+
+#[doc = "Elasticsearch HTTPS 연결 설정"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, Default)]
+#[getset(get = "pub")]
+pub struct ElasticTlsConfig {
+    /* 서버 인증서를 검증할 CA 번들 (PEM) */
+    pub ca_cert_path: Option<String>,
+    /* 클라이언트 인증서 (PKCS#12) */
+    pub client_cert_path: Option<String>,
+    pub client_cert_pw: Option<String>,
+    /* 서버 인증서를 검증하지 않는다. (테스트 클러스터 전용) */
+    pub skip_verify: bool,
+}
+
 #[doc = "ElasticServerConfig 정보를 전역적으로 초기화해주는 함수"]
-pub fn init_elastic_config() {
-    let config: ElasticServerConfig = ElasticServerConfig::new();
+/// # Arguments
+/// * `es_url_override` - 명령행에서 지정한 클러스터 주소 (None 인 경우 env 의 ES_DB_URL 사용)
+/// * `pool_cnt_override` - 명령행에서 지정한 동시 연결 수 (None 인 경우 env 의 ES_POOL_CNT 사용)
+pub fn init_elastic_config(es_url_override: Option<&str>, pool_cnt_override: Option<i32>) {
+    let config: ElasticServerConfig = ElasticServerConfig::new(es_url_override, pool_cnt_override);
 
     ELASTIC_CONFIG
         .set(config)
@@ -26,33 +62,267 @@ pub fn get_elastic_config() -> &'static ElasticServerConfig {
     ELASTIC_CONFIG.get().expect("[Error][get_config] CONFIG not initialized")
 }
 
+#[doc = "비어있지 않은 환경변수 값을 가져와주는 함수"]
+pub fn non_empty_env(key: &str) -> Option<String> {
+    env::var(key)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
 
 impl ElasticServerConfig {
-    pub fn new() -> Self {
-        let elastic_host: Vec<String> = env::var("ES_DB_URL")
-            .expect("[ENV file read Error][initialize_db_clients()] 'ES_DB_URL' must be set")
-            .split(",")
-            .map(|s| s.to_string())
-            .collect();
+    pub fn new(es_url_override: Option<&str>, pool_cnt_override: Option<i32>) -> Self {
+        let elastic_host: Vec<String> = match es_url_override {
+            Some(es_url) => es_url.to_string(),
+            None => env::var("ES_DB_URL")
+                .expect("[ENV file read Error][initialize_db_clients()] 'ES_DB_URL' must be set"),
+        }
+        .split(",")
+        .map(|s| s.trim().to_string())
+        .collect();
+
+        let elastic_pool_cnt: i32 = match pool_cnt_override {
+            Some(pool_cnt) => pool_cnt,
+            None => env::var("ES_POOL_CNT")
+                .expect("[ENV file read Error][initialize_db_clients()] 'ES_POOL_CNT' must be set")
+                .parse::<i32>()
+                .expect("[Error][ElasticServerConfig->new] "),
+        };
 
-        let elastic_id: String = env::var("ES_ID")
-            .expect("[ENV file read Error][initialize_db_clients()] 'ES_ID' must be set");
-        let elastic_pw: String = env::var("ES_PW")
-            .expect("[ENV file read Error][initialize_db_clients()] 'ES_PW' must be set");
-        let elastic_pool_cnt: i32 = env::var("ES_POOL_CNT")
-            .expect("[ENV file read Error][initialize_db_clients()] 'ES_POOL_CNT' must be set")
-            .parse::<i32>()
-            .expect("[Error][ElasticServerConfig->new] ");
+        if elastic_pool_cnt < 1 {
+            panic!("[Error][ElasticServerConfig->new] The number of elastic search connection pools must be at least 1.");
+        }
 
         if elastic_pool_cnt > 10 {
             panic!("[Error][ElasticServerConfig->new] The number of elastic search connection pools cannot exceed 10.");
         }
 
+        let credential_provider: CredentialProvider = CredentialProvider::from_env();
+        let elastic_auth: ElasticAuth = credential_provider.load().unwrap_or_else(|e| {
+            panic!(
+                "[Error][ElasticServerConfig->new] Failed to load Elasticsearch credentials from {}: {}",
+                credential_provider.describe(),
+                e
+            )
+        });
+        let elastic_tls: ElasticTlsConfig = ElasticTlsConfig::from_env();
+
         Self {
             elastic_host,
-            elastic_id: Some(elastic_id),
-            elastic_pw: Some(elastic_pw),
+            credential_provider,
+            elastic_auth: RwLock::new(elastic_auth),
+            elastic_tls,
             elastic_pool_cnt,
         }
     }
+
+    #[doc = "현재 사용중인 인증 정보를 가져와주는 함수"]
+    pub fn current_auth(&self) -> ElasticAuth {
+        self.elastic_auth
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    #[doc = "교체된 인증 정보로 바꿔주는 함수"]
+    pub fn set_auth(&self, elastic_auth: ElasticAuth) {
+        *self
+            .elastic_auth
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = elastic_auth;
+    }
+
+    #[doc = "주소에 스킴이 없는 호스트에 사용할 스킴 - TLS 설정이 하나라도 있으면 https"]
+    pub fn default_scheme(&self) -> &'static str {
+        if self.elastic_tls.is_enabled() {
+            "https"
+        } else {
+            "http"
+        }
+    }
+}
+
+impl ElasticAuth {
+    #[doc = "인증 정보 키/값에서 인증 방식을 결정해주는 함수"]
+    /// # Arguments
+    /// * `lookup` - 키(ES_ID, ES_PW, ES_API_KEY, ES_BEARER_TOKEN)의 값을 가져오는 함수
+    ///
+    /// # Returns
+    /// * Result<ElasticAuth, anyhow::Error> - 두 가지 이상의 인증 방식이 있으면 에러, 하나도 없으면 인증 없이 접속한다.
+    pub fn from_values<F>(lookup: F) -> Result<Self, anyhow::Error>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let basic: Option<ElasticAuth> = match (lookup("ES_ID"), lookup("ES_PW")) {
+            (Some(id), Some(pw)) => Some(ElasticAuth::Basic { id, pw }),
+            (None, None) => None,
+            _ => {
+                return Err(anyhow!("[Error][ElasticAuth->from_values] 'ES_ID' and 'ES_PW' must be set together"))
+            }
+        };
+
+        let api_key: Option<ElasticAuth> =
+            lookup("ES_API_KEY").map(|api_key| match api_key.split_once(':') {
+                Some((id, key)) => ElasticAuth::ApiKey {
+                    id: id.to_string(),
+                    key: key.to_string(),
+                },
+                None => ElasticAuth::EncodedApiKey(api_key),
+            });
+
+        let bearer: Option<ElasticAuth> = lookup("ES_BEARER_TOKEN").map(ElasticAuth::Bearer);
+
+        let mut auths: Vec<ElasticAuth> = [basic, api_key, bearer].into_iter().flatten().collect();
+
+        if auths.len() > 1 {
+            return Err(anyhow!("[Error][ElasticAuth->from_values] Only one of 'ES_ID'/'ES_PW', 'ES_API_KEY' and 'ES_BEARER_TOKEN' can be set"));
+        }
+
+        Ok(auths.pop().unwrap_or(ElasticAuth::None))
+    }
+
+    #[doc = "로그에 남길 수 있는 인증 방식 이름"]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ElasticAuth::None => "none",
+            ElasticAuth::Basic { .. } => "basic",
+            ElasticAuth::ApiKey { .. } | ElasticAuth::EncodedApiKey(_) => "api_key",
+            ElasticAuth::Bearer(_) => "bearer",
+        }
+    }
+}
+
+/* 인증 정보가 로그에 남지 않도록 Debug 에서는 비밀값을 가린다. */
+impl std::fmt::Debug for ElasticAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ElasticAuth::Basic { id, .. } => write!(f, "Basic {{ id: {:?}, pw: \"***\" }}", id),
+            ElasticAuth::ApiKey { id, .. } => write!(f, "ApiKey {{ id: {:?}, key: \"***\" }}", id),
+            _ => write!(f, "{}", self.kind()),
+        }
+    }
+}
+
+impl ElasticTlsConfig {
+    #[doc = "환경변수에서 HTTPS 연결 설정을 읽어주는 함수"]
+    /// ES_CA_CERT, ES_CLIENT_CERT, ES_CLIENT_CERT_PW, ES_SKIP_VERIFY
+    pub fn from_env() -> Self {
+        let skip_verify: bool = match non_empty_env("ES_SKIP_VERIFY") {
+            Some(skip_verify) => skip_verify
+                .parse::<bool>()
+                .expect("[Error][ElasticTlsConfig->from_env] 'ES_SKIP_VERIFY' must be true or false"),
+            None => false,
+        };
+
+        let tls_config: ElasticTlsConfig = ElasticTlsConfig {
+            ca_cert_path: non_empty_env("ES_CA_CERT"),
+            client_cert_path: non_empty_env("ES_CLIENT_CERT"),
+            client_cert_pw: non_empty_env("ES_CLIENT_CERT_PW"),
+            skip_verify,
+        };
+
+        if tls_config.skip_verify && tls_config.ca_cert_path.is_some() {
+            panic!("[Error][ElasticTlsConfig->from_env] 'ES_CA_CERT' and 'ES_SKIP_VERIFY' cannot be used together.");
+        }
+
+        if tls_config.client_cert_pw.is_some() && tls_config.client_cert_path.is_none() {
+            panic!("[Error][ElasticTlsConfig->from_env] 'ES_CLIENT_CERT_PW' requires 'ES_CLIENT_CERT'.");
+        }
+
+        for cert_path in [&tls_config.ca_cert_path, &tls_config.client_cert_path]
+            .into_iter()
+            .flatten()
+        {
+            if !Path::new(cert_path).is_file() {
+                panic!("[Error][ElasticTlsConfig->from_env] Certificate file '{}' does not exist.", cert_path);
+            }
+        }
+
+        tls_config
+    }
+
+    #[doc = "TLS 관련 설정이 하나라도 지정되었는지 확인해주는 함수"]
+    pub fn is_enabled(&self) -> bool {
+        self.ca_cert_path.is_some() || self.client_cert_path.is_some() || self.skip_verify
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth_of(pairs: &[(&str, &str)]) -> Result<ElasticAuth, anyhow::Error> {
+        let values: HashMap<String, String> = pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        ElasticAuth::from_values(|key: &str| values.get(key).cloned())
+    }
+
+    #[test]
+    fn no_credentials_connects_without_auth() {
+        assert_eq!(auth_of(&[]).unwrap(), ElasticAuth::None);
+    }
+
+    #[test]
+    fn id_and_pw_are_basic_auth() {
+        let elastic_auth: ElasticAuth =
+            auth_of(&[("ES_ID", "elastic"), ("ES_PW", "changeme")]).unwrap();
+
+        assert_eq!(
+            elastic_auth,
+            ElasticAuth::Basic {
+                id: String::from("elastic"),
+                pw: String::from("changeme"),
+            }
+        );
+    }
+
+    #[test]
+    fn id_without_pw_is_rejected() {
+        assert!(auth_of(&[("ES_ID", "elastic")]).is_err());
+        assert!(auth_of(&[("ES_PW", "changeme")]).is_err());
+    }
+
+    #[test]
+    fn more_than_one_auth_kind_is_rejected() {
+        assert!(
+            auth_of(&[("ES_ID", "elastic"), ("ES_PW", "changeme"), ("ES_API_KEY", "a:b")]).is_err()
+        );
+        assert!(auth_of(&[("ES_API_KEY", "a:b"), ("ES_BEARER_TOKEN", "token")]).is_err());
+    }
+
+    #[test]
+    fn api_key_with_id_is_split() {
+        assert_eq!(
+            auth_of(&[("ES_API_KEY", "key-id:secret")]).unwrap(),
+            ElasticAuth::ApiKey {
+                id: String::from("key-id"),
+                key: String::from("secret"),
+            }
+        );
+    }
+
+    #[test]
+    fn encoded_api_key_is_used_as_is() {
+        /* base64 로 인코딩된 값에는 ':' 가 없다. */
+        assert_eq!(
+            auth_of(&[("ES_API_KEY", "a2V5LWlkOnNlY3JldA==")]).unwrap(),
+            ElasticAuth::EncodedApiKey(String::from("a2V5LWlkOnNlY3JldA=="))
+        );
+        assert_eq!(
+            auth_of(&[("ES_BEARER_TOKEN", "token")]).unwrap(),
+            ElasticAuth::Bearer(String::from("token"))
+        );
+    }
+
+    #[test]
+    fn debug_output_hides_secrets() {
+        let elastic_auth: ElasticAuth =
+            auth_of(&[("ES_ID", "elastic"), ("ES_PW", "changeme")]).unwrap();
+
+        assert!(!format!("{:?}", elastic_auth).contains("changeme"));
+    }
 }
@@ -0,0 +1,40 @@
+use crate::common::*;
+
+static HOLD_CONFIG: OnceCell<HoldConfig> = OnceCell::new();
+
+#[derive(Debug, Deserialize, Serialize, Getters)]
+#[getset(get = "pub")]
+pub struct HoldConfig {
+    /* 리갈 홀드 파일 경로 (None 인 경우 인덱스 _meta 의 홀드만 사용한다.) */
+    pub holds_file_path: Option<String>,
+}
+
+#[doc = "HoldConfig 정보를 전역적으로 초기화해주는 함수"]
+/// # Arguments
+/// * `holds_file_override` - 명령행에서 지정한 리갈 홀드 파일 경로 (None 인 경우 env 의 LEGAL_HOLDS_PATH 사용)
+pub fn init_hold_config(holds_file_override: Option<&str>) {
+    let config: HoldConfig = HoldConfig::new(holds_file_override);
+
+    HOLD_CONFIG
+        .set(config)
+        .expect("[Error][init_hold_config] HOLD_CONFIG is already initialized");
+}
+
+#[doc = "전역화된 HoldConfig 정보를 안전하게 사용가능하게 하는 함수"]
+pub fn get_hold_config() -> &'static HoldConfig {
+    HOLD_CONFIG
+        .get()
+        .expect("[Error][get_hold_config] HOLD_CONFIG not initialized")
+}
+
+impl HoldConfig {
+    pub fn new(holds_file_override: Option<&str>) -> Self {
+        let holds_file_path: Option<String> = match holds_file_override {
+            Some(holds_file) => Some(holds_file.to_string()),
+            None => env::var("LEGAL_HOLDS_PATH").ok(),
+        }
+        .filter(|holds_file| !holds_file.trim().is_empty());
+
+        Self { holds_file_path }
+    }
+}
@@ -1 +1,5 @@
+pub mod audit_config;
+pub mod cli_args;
+pub mod credential_provider;
+pub mod hold_config;
 pub mod elastic_server_config;
@@ -2,28 +2,102 @@ use crate::common::*;
 
 use crate::service::index_clear_service::*;
 
+use crate::repository::es_repository::*;
+use crate::repository::hold_repository::*;
+
+use crate::metrics::metrics_server::*;
+
+use crate::model::archive_manifest::*;
+use crate::model::audit_entry::*;
+use crate::model::deletion_plan::*;
+use crate::model::emergency_config::*;
+use crate::model::index_decision::*;
+use crate::model::legal_hold::*;
+use crate::model::safety_config::*;
+use crate::model::schedule_config::*;
 use crate::model::target_index::*;
 
+use crate::utils_module::archive_utils::*;
 use crate::utils_module::io_utils::*;
+use crate::utils_module::signal_utils::*;
+use crate::utils_module::size_utils::*;
+use crate::utils_module::time_utils::*;
+
+/* 데몬 모드에서 진행중인 작업 목록에 긴급 정리를 올릴 때 사용하는 이름 (규칙의 인덱스 패턴과 겹치지 않는다.) */
+const EMERGENCY_TASK_NAME: &str = "[emergency]";
 
 pub struct MainController<I: IndexClearService + Sync + Send + 'static> {
     index_clear_service: Arc<I>,
+    index_list_path: String,
 }
 
 impl<I: IndexClearService + Sync + Send + 'static> MainController<I> {
-    pub fn new(index_clear_service: Arc<I>) -> Self {
+    pub fn new(index_clear_service: Arc<I>, index_list_path: String) -> Self {
         Self {
             index_clear_service,
+            index_list_path,
         }
     }
 
+    #[doc = "정리해줄 인덱스의 규칙 리스트를 읽고 검증해주는 함수"]
+    fn read_target_indexes(&self) -> Result<TargetIndexList, anyhow::Error> {
+        let target_indexes: TargetIndexList =
+            read_toml_from_file::<TargetIndexList>(&self.index_list_path).map_err(|e| {
+                anyhow!(
+                    "[Error][MainController->read_target_indexes] There was a problem reading '{}': {:?}",
+                    self.index_list_path,
+                    e
+                )
+            })?;
+
+        target_indexes.validate()?;
+
+        Ok(target_indexes)
+    }
+
+    #[doc = "규칙 파일의 [safety] 안전 상한을 반환해주는 함수 (--override-safety-caps 인 경우 None)"]
+    /// # Arguments
+    /// * `target_indexes` - 인덱스 보존 규칙 리스트
+    /// * `override_safety_caps` - 안전 상한을 무시할지 여부
+    ///
+    /// # Returns
+    /// * Option<SafetyConfig>
+    fn resolve_safety_config(
+        &self,
+        target_indexes: &TargetIndexList,
+        override_safety_caps: bool,
+    ) -> Option<SafetyConfig> {
+        if !target_indexes.safety().is_enabled() {
+            return None;
+        }
+
+        if override_safety_caps {
+            info!("[MainController->resolve_safety_config] Safety caps are overridden by --override-safety-caps.");
+            return None;
+        }
+
+        Some(target_indexes.safety().clone())
+    }
+
     #[doc = "메인 배치 함수"]
-    pub async fn main_task(&self) -> Result<(), anyhow::Error> {
+    /// # Arguments
+    /// * `override_safety_caps` - true 인 경우 [safety] 안전 상한을 넘더라도 삭제한다.
+    pub async fn main_task(&self, override_safety_caps: bool) -> Result<(), anyhow::Error> {
         /* 정리해줄 인덱스의 리스트를 뽑아준다. */
-        let target_indexes: TargetIndexList = read_toml_file_from_env::<TargetIndexList>(
-            "INDEX_LIST_PATH",
-        )
-        .expect("[Error][MainController->main_task] There was a problem reading 'target_indexes'.");
+        let target_indexes: TargetIndexList = self.read_target_indexes()?;
+
+        let run_id: String = generate_run_id();
+        info!("[MainController->main_task] Run id: {}", run_id);
+
+        let safety_config: Option<SafetyConfig> =
+            self.resolve_safety_config(&target_indexes, override_safety_caps);
+
+        /* 모든 규칙의 계획을 한 번만 세운 뒤, 안전 상한을 확인한 바로 그 계획대로 삭제한다. */
+        let index_plan: Vec<IndexDecision> = self.collect_index_plan(&target_indexes).await?;
+
+        if let Some(safety_config) = &safety_config {
+            safety_config.check(&index_plan)?;
+        }
 
         /* 직렬처리 코드 */
         // for target_index in target_indexes.index {
@@ -31,26 +105,42 @@ impl<I: IndexClearService + Sync + Send + 'static> MainController<I> {
         // }
 
         /* 아래는 병렬처리 코드인데 크게 의미없는 듯.. 직렬처리해도 상관 없어보임 */
-        let mut handles: Vec<tokio::task::JoinHandle<Result<(), anyhow::Error>>> = vec![];
+        let mut handles: Vec<(String, tokio::task::JoinHandle<Result<(), anyhow::Error>>)> = vec![];
 
         for target_index in target_indexes.index {
+            let rule_name: String = target_index.index_name().clone();
+            let rule_plan: Vec<IndexDecision> = index_plan
+                .iter()
+                .filter(|decision| decision.rule() == target_index.index_name())
+                .cloned()
+                .collect();
+
             let service: Arc<I> = Arc::clone(&self.index_clear_service);
+            let run_id: String = run_id.clone();
 
             let handle: tokio::task::JoinHandle<Result<(), anyhow::Error>> =
-                tokio::spawn(async move { service.delete_index_from_rule(&target_index).await });
+                tokio::spawn(async move {
+                    service
+                        .run_index_plan(&target_index, &rule_plan, &run_id)
+                        .await
+                });
             // let handle = tokio::spawn(async move {
             //     tokio::time::timeout(Duration::from_secs(15), service.delete_index_from_rule(&target_index)).await
             //         .map_err(|_| anyhow!("Timed out while processing index: {}", target_index.index_name()))?
             // });
 
-            handles.push(handle);
+            handles.push((rule_name, handle));
         }
 
-        for handle in handles {
+        let rule_cnt: usize = handles.len();
+        let mut failed_rules: Vec<String> = Vec::new();
+
+        for (rule_name, handle) in handles {
             match handle.await {
                 Ok(inner_result) => {
                     if let Err(e) = inner_result {
                         error!("[Error][MainController->run_parallel] Stream task failed with error: {:?}", e);
+                        failed_rules.push(rule_name);
                     }
                 }
                 Err(e) => {
@@ -58,10 +148,1087 @@ impl<I: IndexClearService + Sync + Send + 'static> MainController<I> {
                         "[Error][MainController->run_parallel] Tokio task join error: {:?}",
                         e
                     );
+                    failed_rules.push(rule_name);
+                }
+            }
+        }
+
+        /* 규칙 하나라도 계획대로 처리되지 않았다면 실패로 종료한다. */
+        if !failed_rules.is_empty() {
+            return Err(anyhow!(
+                "[Error][MainController->main_task] {} of {} rules failed: [{}]",
+                failed_rules.len(),
+                rule_cnt,
+                failed_rules.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[doc = "데몬 모드에서 디스크 사용률을 확인하고, 상한을 넘은 경우 긴급 정리를 실행해주는 함수"]
+    /// # Arguments
+    /// * `index_clear_service` - 인덱스 정리 서비스
+    /// * `target_indexes` - 인덱스 보존 규칙 리스트
+    /// * `emergency_config` - 긴급 정리 설정
+    /// * `safety_config` - [safety] 안전 상한 (None 인 경우 확인하지 않는다.)
+    /// * `run_id` - 실행 아이디
+    ///
+    /// `emergency` 서브커맨드와 같은 긴급 정리 계획을 만들고, 안전 상한을 넘으면 아무것도 삭제하지 않는다.
+    async fn run_emergency_cleanup(
+        index_clear_service: Arc<I>,
+        target_indexes: Vec<TargetIndex>,
+        emergency_config: EmergencyConfig,
+        safety_config: Option<SafetyConfig>,
+        run_id: String,
+    ) {
+        let result: Result<(), anyhow::Error> = async {
+            let emergency_plan: EmergencyPlan = index_clear_service
+                .build_emergency_plan(&target_indexes, &emergency_config)
+                .await?;
+
+            if emergency_plan.used_percent() <= emergency_config.high_disk_percent() {
+                return Ok(());
+            }
+
+            if let Some(safety_config) = &safety_config {
+                safety_config.check(&emergency_plan.safety_plan())?;
+            }
+
+            info!(
+                "[MainController->run_emergency_cleanup] Disk usage {:.1}% is over the high watermark ({}%). Deleting {} indices (run id: {}, expected disk usage after cleanup: {:.1}%)",
+                emergency_plan.used_percent(),
+                emergency_config.high_disk_percent(),
+                emergency_plan.decisions().len(),
+                run_id,
+                emergency_plan.projected_percent()
+            );
+
+            index_clear_service
+                .delete_planned_indexes(emergency_plan.decisions(), &run_id)
+                .await
+        }
+        .await;
+
+        if let Err(e) = result {
+            error!(
+                "[Error][MainController->run_emergency_cleanup] Disk watermark cleanup failed with error: {:?}",
+                e
+            );
+        }
+    }
+
+    #[doc = "데몬 모드에서 같은 시각에 실행되는 규칙들의 계획을 세우고, 합친 계획으로 안전 상한을 확인한 뒤 규칙별로 실행해주는 함수"]
+    /// # Arguments
+    /// * `index_clear_service` - 인덱스 정리 서비스
+    /// * `target_indexes` - 이번에 실행할 규칙 리스트
+    /// * `safety_config` - [safety] 안전 상한 (None 인 경우 확인하지 않는다.)
+    /// * `run_id` - 실행 아이디
+    ///
+    /// `run` 과 같이 이번 실행의 모든 규칙을 합쳐서 안전 상한을 확인하고, 넘으면 아무것도 삭제하지 않는다.
+    async fn run_scheduled_rules(
+        index_clear_service: Arc<I>,
+        target_indexes: Vec<TargetIndex>,
+        safety_config: Option<SafetyConfig>,
+        run_id: String,
+    ) {
+        /* 규칙별로 병렬로 계획을 세운다. - 계획을 세우지 못한 규칙은 이번 실행에서 빠진다. */
+        let mut plan_handles: Vec<JoinHandle<Result<Vec<IndexDecision>, anyhow::Error>>> =
+            Vec::new();
+
+        for target_index in target_indexes.clone() {
+            let service: Arc<I> = Arc::clone(&index_clear_service);
+
+            let handle: JoinHandle<Result<Vec<IndexDecision>, anyhow::Error>> =
+                tokio::spawn(async move { service.build_index_plan(&target_index).await });
+
+            plan_handles.push(handle);
+        }
+
+        let mut rule_plans: Vec<(TargetIndex, Vec<IndexDecision>)> = Vec::new();
+
+        for (target_index, handle) in target_indexes.into_iter().zip(plan_handles) {
+            match handle.await {
+                Ok(Ok(index_plan)) => rule_plans.push((target_index, index_plan)),
+                Ok(Err(e)) => {
+                    error!(
+                        "[Error][MainController->run_scheduled_rules] '{}' failed with error: {:?}",
+                        target_index.index_name(),
+                        e
+                    );
+                }
+                Err(e) => {
+                    error!(
+                        "[Error][MainController->run_scheduled_rules] Tokio task join error ('{}'): {:?}",
+                        target_index.index_name(),
+                        e
+                    );
+                }
+            }
+        }
+
+        /* 안전 상한을 넘는 실행은 어떤 규칙도 지우지 않고 중단한다. */
+        if let Some(safety_config) = &safety_config {
+            let run_plan: Vec<IndexDecision> = rule_plans
+                .iter()
+                .flat_map(|(_, index_plan)| index_plan.iter().cloned())
+                .collect();
+
+            if let Err(e) = safety_config.check(&run_plan) {
+                error!(
+                    "[Error][MainController->run_scheduled_rules] Run {} was aborted: {:?}",
+                    run_id, e
+                );
+                return;
+            }
+        }
+
+        let mut handles: Vec<(String, JoinHandle<Result<(), anyhow::Error>>)> = Vec::new();
+
+        for (target_index, index_plan) in rule_plans {
+            let service: Arc<I> = Arc::clone(&index_clear_service);
+            let run_id: String = run_id.clone();
+            let rule_name: String = target_index.index_name().clone();
+
+            let handle: JoinHandle<Result<(), anyhow::Error>> = tokio::spawn(async move {
+                service
+                    .run_index_plan(&target_index, &index_plan, &run_id)
+                    .await
+            });
+
+            handles.push((rule_name, handle));
+        }
+
+        for (rule_name, handle) in handles {
+            match handle.await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    error!(
+                        "[Error][MainController->run_scheduled_rules] '{}' failed with error: {:?}",
+                        rule_name, e
+                    );
+                }
+                Err(e) => {
+                    error!(
+                        "[Error][MainController->run_scheduled_rules] Tokio task join error ('{}'): {:?}",
+                        rule_name, e
+                    );
+                }
+            }
+        }
+    }
+
+    #[doc = "진행중인 작업 중에 주어진 이름(규칙 또는 긴급 정리)을 맡은 작업이 있는지 확인해주는 함수"]
+    /// # Arguments
+    /// * `running_tasks` - 진행중인 작업 목록 (작업이 맡은 이름들, 작업)
+    /// * `task_name` - 확인할 이름
+    ///
+    /// # Returns
+    /// * bool
+    fn is_task_running(running_tasks: &[(Vec<String>, JoinHandle<()>)], task_name: &str) -> bool {
+        running_tasks.iter().any(|(task_names, handle)| {
+            !handle.is_finished() && task_names.iter().any(|name| name == task_name)
+        })
+    }
+
+    #[doc = "진행중인 작업 중에 규칙을 실행하는 작업이 있는지 확인해주는 함수 (긴급 정리 제외)"]
+    /// # Arguments
+    /// * `running_tasks` - 진행중인 작업 목록 (작업이 맡은 이름들, 작업)
+    ///
+    /// # Returns
+    /// * bool
+    fn is_rule_task_running(running_tasks: &[(Vec<String>, JoinHandle<()>)]) -> bool {
+        running_tasks.iter().any(|(task_names, handle)| {
+            !handle.is_finished() && task_names.iter().all(|name| name != EMERGENCY_TASK_NAME)
+        })
+    }
+
+    #[doc = "종료 신호(SIGTERM/SIGINT)가 들어올 때까지 규칙별 cron 스케줄에 따라 인덱스를 정리해주는 함수"]
+    /// # Arguments
+    /// * `cron_override` - 규칙 파일의 [schedule] cron 을 덮어쓸 cron 표현식
+    /// * `timezone_override` - 규칙 파일의 [schedule] timezone 을 덮어쓸 타임존
+    /// * `emergency_cron_override` - 규칙 파일의 [emergency] schedule 을 덮어쓸 cron 표현식
+    /// * `metrics_addr` - Prometheus `/metrics` 엔드포인트를 열 주소 (None 인 경우 열지 않는다.)
+    /// * `override_safety_caps` - true 인 경우 [safety] 안전 상한을 넘더라도 삭제한다.
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    ///
+    /// 종료 신호가 들어오면 새로운 실행은 시작하지 않고, 진행중인 삭제 작업이 끝날 때까지 기다린 뒤 종료한다.
+    /// [emergency] schedule 이 있으면 그 주기로 디스크 사용률을 확인해서 `emergency` 와 같은 긴급 정리를 실행한다. (안전 상한 적용)
+    /// 긴급 정리와 규칙 실행은 동시에 하지 않는다. 한쪽이 진행중이면 다른 쪽의 이번 실행은 건너뛴다.
+    pub async fn daemon_task(
+        &self,
+        cron_override: Option<&str>,
+        timezone_override: Option<&str>,
+        emergency_cron_override: Option<&str>,
+        metrics_addr: Option<&str>,
+        override_safety_caps: bool,
+    ) -> Result<(), anyhow::Error> {
+        let target_indexes: TargetIndexList = self.read_target_indexes()?;
+        let safety_config: Option<SafetyConfig> =
+            self.resolve_safety_config(&target_indexes, override_safety_caps);
+
+        let timezone: Tz =
+            parse_timezone(timezone_override.unwrap_or(target_indexes.schedule().timezone()))?;
+        let default_cron: Option<&str> =
+            cron_override.or(target_indexes.schedule().cron().as_deref());
+
+        /* 규칙별 스케줄 - 규칙에 schedule 이 없으면 기본 cron 을 사용한다. */
+        let mut rule_schedules: Vec<(TargetIndex, Schedule)> = Vec::new();
+
+        for target_index in target_indexes.index() {
+            let cron_expr: &str = target_index
+                .schedule()
+                .as_deref()
+                .or(default_cron)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "[Error][MainController->daemon_task] '{}' has no schedule. (set [schedule] cron, the rule's schedule or --cron)",
+                        target_index.index_name()
+                    )
+                })?;
+
+            rule_schedules.push((target_index.clone(), parse_cron_schedule(cron_expr)?));
+        }
+
+        /* 디스크 사용률 확인 스케줄 - 지정하지 않으면 데몬 모드에서는 긴급 정리를 하지 않는다. */
+        let emergency_schedule: Option<(EmergencyConfig, Schedule)> = match (
+            target_indexes.emergency(),
+            emergency_cron_override,
+        ) {
+            (Some(emergency_config), cron_expr) => {
+                match cron_expr.or(emergency_config.schedule().as_deref()) {
+                    Some(cron_expr) => {
+                        Some((emergency_config.clone(), parse_cron_schedule(cron_expr)?))
+                    }
+                    None => None,
+                }
+            }
+            (None, Some(_)) => {
+                return Err(anyhow!(
+                    "[Error][MainController->daemon_task] --emergency-cron requires [emergency] in '{}'.",
+                    self.index_list_path
+                ))
+            }
+            (None, None) => None,
+        };
+
+        let mut next_runs: Vec<Option<DateTime<Tz>>> = rule_schedules
+            .iter()
+            .map(|(_, schedule)| schedule.upcoming(timezone).next())
+            .collect();
+        let mut next_emergency_run: Option<DateTime<Tz>> = emergency_schedule
+            .as_ref()
+            .and_then(|(_, schedule)| schedule.upcoming(timezone).next());
+
+        for ((target_index, _), next_run) in rule_schedules.iter().zip(&next_runs) {
+            info!(
+                "[MainController->daemon_task] '{}' next run: {:?}",
+                target_index.index_name(),
+                next_run
+            );
+        }
+
+        if let Some((emergency_config, _)) = &emergency_schedule {
+            info!(
+                "[MainController->daemon_task] Disk watermark check (high: {}%, target: {}%) next run: {:?}",
+                emergency_config.high_disk_percent(),
+                emergency_config.target_disk_percent(),
+                next_emergency_run
+            );
+        }
+
+        let metrics_server: Option<JoinHandle<()>> = match metrics_addr {
+            Some(metrics_addr) => Some(start_metrics_server(metrics_addr).await?),
+            None => None,
+        };
+
+        /* 진행중인 작업 (작업이 맡은 규칙들, 작업) - 이전 실행이 끝나지 않은 규칙은 이번 실행을 건너뛴다. */
+        let mut running_tasks: Vec<(Vec<String>, JoinHandle<()>)> = Vec::new();
+
+        let shutdown_signal = wait_shutdown_signal();
+        tokio::pin!(shutdown_signal);
+
+        loop {
+            let next_run: DateTime<Tz> = match next_runs
+                .iter()
+                .chain(std::iter::once(&next_emergency_run))
+                .flatten()
+                .min()
+            {
+                Some(next_run) => *next_run,
+                None => {
+                    info!("[MainController->daemon_task] No more scheduled runs.");
+                    break;
+                }
+            };
+
+            let wait_duration: Duration = (next_run.with_timezone(&Utc) - Utc::now())
+                .to_std()
+                .unwrap_or(Duration::ZERO);
+
+            tokio::select! {
+                result = &mut shutdown_signal => {
+                    result?;
+                    info!("[MainController->daemon_task] Shutdown requested. Waiting for running tasks to finish.");
+                    break;
+                }
+                _ = tokio::time::sleep(wait_duration) => {}
+            }
+
+            let cur_time: DateTime<Tz> = Utc::now().with_timezone(&timezone);
+            let run_id: String = generate_run_id();
+
+            /* 재시작 없이 교체(rotation)된 인증 정보를 반영한다. - 읽지 못하면 기존 인증 정보로 계속 실행한다. */
+            if let Err(e) = refresh_elastic_credentials() {
+                error!(
+                    "[Error][MainController->daemon_task] Failed to refresh Elasticsearch credentials. Keeping the current ones: {:?}",
+                    e
+                );
+            }
+
+            /* 끝난 작업은 목록에서 빼준다. */
+            running_tasks.retain(|(_, handle)| !handle.is_finished());
+
+            /* 긴급 정리와 규칙 실행은 같은 인덱스를 동시에 지울 수 있으므로, 한쪽이 진행중이면 다른 쪽은 이번 실행을 건너뛴다. */
+            if let Some((emergency_config, schedule)) = &emergency_schedule {
+                if next_emergency_run.is_some_and(|next_run| next_run <= cur_time) {
+                    next_emergency_run = schedule.after(&cur_time).next();
+
+                    if Self::is_task_running(&running_tasks, EMERGENCY_TASK_NAME) {
+                        error!("[Error][MainController->daemon_task] The previous disk watermark check is still running. Skipping this run.");
+                    } else if Self::is_rule_task_running(&running_tasks) {
+                        error!("[Error][MainController->daemon_task] Rule runs are still running. Skipping this disk watermark check.");
+                    } else {
+                        let handle: JoinHandle<()> = tokio::spawn(Self::run_emergency_cleanup(
+                            Arc::clone(&self.index_clear_service),
+                            target_indexes.index().clone(),
+                            emergency_config.clone(),
+                            safety_config.clone(),
+                            run_id.clone(),
+                        ));
+
+                        running_tasks.push((vec![String::from(EMERGENCY_TASK_NAME)], handle));
+                    }
+                }
+            }
+
+            /* 이번에 실행할 규칙 */
+            let mut due_rules: Vec<TargetIndex> = Vec::new();
+
+            for ((target_index, schedule), next_run) in rule_schedules.iter().zip(next_runs.iter_mut()) {
+                if !next_run.is_some_and(|next_run| next_run <= cur_time) {
+                    continue;
+                }
+
+                *next_run = schedule.after(&cur_time).next();
+
+                let rule_name: &String = target_index.index_name();
+
+                if Self::is_task_running(&running_tasks, rule_name) {
+                    error!(
+                        "[Error][MainController->daemon_task] The previous run of '{}' is still running. Skipping this run.",
+                        rule_name
+                    );
+                    continue;
                 }
+
+                if Self::is_task_running(&running_tasks, EMERGENCY_TASK_NAME) {
+                    error!(
+                        "[Error][MainController->daemon_task] Disk watermark cleanup is still running. Skipping this run of '{}'.",
+                        rule_name
+                    );
+                    continue;
+                }
+
+                info!(
+                    "[MainController->daemon_task] Running '{}' (run id: {}, next run: {:?})",
+                    rule_name, run_id, next_run
+                );
+
+                due_rules.push(target_index.clone());
+            }
+
+            if !due_rules.is_empty() {
+                let task_names: Vec<String> = due_rules
+                    .iter()
+                    .map(|target_index| target_index.index_name().clone())
+                    .collect();
+
+                let handle: JoinHandle<()> = tokio::spawn(Self::run_scheduled_rules(
+                    Arc::clone(&self.index_clear_service),
+                    due_rules,
+                    safety_config.clone(),
+                    run_id.clone(),
+                ));
+
+                running_tasks.push((task_names, handle));
             }
+
         }
 
+        /* 진행중인 삭제 작업이 끝날 때까지 기다려준다. */
+        for (task_names, handle) in running_tasks {
+            if let Err(e) = handle.await {
+                error!(
+                    "[Error][MainController->daemon_task] Tokio task join error ('{}'): {:?}",
+                    task_names.join(", "),
+                    e
+                );
+            }
+        }
+
+        if let Some(metrics_server) = metrics_server {
+            metrics_server.abort();
+        }
+
+        for node_stats in get_es_node_stats() {
+            info!("[MainController->daemon_task] Elasticsearch node: {}", node_stats);
+        }
+
+        info!("[MainController->daemon_task] Daemon stopped.");
+
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[doc = "실제 삭제는 하지 않고, 규칙별로 삭제될 인덱스 계획을 만들어서 출력해주는 함수 (dry-run)"]
+    /// # Arguments
+    /// * `plan_output_path` - 계획을 Json 파일로 저장할 경로 (None 인 경우 출력만 해준다.)
+    ///
+    /// # Returns
+    /// * Result<DeletionPlan, anyhow::Error>
+    pub async fn plan_task(
+        &self,
+        plan_output_path: Option<&str>,
+    ) -> Result<DeletionPlan, anyhow::Error> {
+        let target_indexes: TargetIndexList = self.read_target_indexes()?;
+        let index_plan: Vec<IndexDecision> = self.collect_index_plan(&target_indexes).await?;
+
+        self.print_index_plan(&index_plan)?;
+
+        let deletion_plan: DeletionPlan = DeletionPlan::new(
+            get_currnet_utc_naivedatetime(),
+            target_indexes.index,
+            index_plan,
+        );
+
+        if let Some(plan_output_path) = plan_output_path {
+            write_json_to_file(plan_output_path, &deletion_plan)?;
+            println!("Saved the plan to '{}'.", plan_output_path);
+            info!(
+                "[MainController->plan_task] Saved the plan to '{}'.",
+                plan_output_path
+            );
+        }
+
+        Ok(deletion_plan)
+    }
+
+    #[doc = "검토가 끝난 계획 파일을 읽어서, 계획에 포함된 인덱스만 삭제해주는 함수"]
+    /// # Arguments
+    /// * `plan_path` - `plan_task` 로 저장한 계획 파일 경로
+    /// * `override_safety_caps` - true 인 경우 [safety] 안전 상한을 넘더라도 삭제한다.
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn apply_task(
+        &self,
+        plan_path: &str,
+        override_safety_caps: bool,
+    ) -> Result<(), anyhow::Error> {
+        let deletion_plan: DeletionPlan = read_json_from_file::<DeletionPlan>(plan_path)
+            .map_err(|e| {
+                anyhow!(
+                    "[Error][MainController->apply_task] Failed to read plan file '{}': {:?}",
+                    plan_path,
+                    e
+                )
+            })?;
+
+        /* 안전 상한은 계획을 만들 때가 아닌 현재 규칙 파일의 [safety] 기준으로 확인한다. */
+        let target_indexes: TargetIndexList = self.read_target_indexes()?;
+
+        let safety_config: Option<SafetyConfig> =
+            self.resolve_safety_config(&target_indexes, override_safety_caps);
+
+        if let Some(safety_config) = &safety_config {
+            safety_config.check(deletion_plan.decisions())?;
+        }
+
+        let run_id: String = generate_run_id();
+
+        info!(
+            "[MainController->apply_task] Applying plan created at {} ({} indices to delete, run id: {})",
+            deletion_plan.created_at(),
+            deletion_plan.delete_decisions().len(),
+            run_id
+        );
+
+        let mut handles: Vec<(String, tokio::task::JoinHandle<Result<(), anyhow::Error>>)> = vec![];
+
+        for target_index in deletion_plan.rules() {
+            /* 계획 파일에 기록된 규칙 기준으로 삭제 대상을 묶어준다. */
+            let planned_decisions: Vec<IndexDecision> = deletion_plan
+                .delete_decisions()
+                .into_iter()
+                .filter(|decision| decision.rule() == target_index.index_name())
+                .cloned()
+                .collect();
+
+            if planned_decisions.is_empty() {
+                continue;
+            }
+
+            let rule_name: String = target_index.index_name().clone();
+            let service: Arc<I> = Arc::clone(&self.index_clear_service);
+            let target_index: TargetIndex = target_index.clone();
+            let run_id: String = run_id.clone();
+
+            let handle: tokio::task::JoinHandle<Result<(), anyhow::Error>> =
+                tokio::spawn(async move {
+                    service
+                        .apply_index_plan(&target_index, &planned_decisions, &run_id)
+                        .await
+                });
+
+            handles.push((rule_name, handle));
+        }
+
+        let mut failed_rules: Vec<String> = Vec::new();
+
+        for (rule_name, handle) in handles {
+            match handle.await {
+                Ok(inner_result) => {
+                    if let Err(e) = inner_result {
+                        error!(
+                            "[Error][MainController->apply_task] Apply task failed with error: {:?}",
+                            e
+                        );
+                        failed_rules.push(rule_name);
+                    }
+                }
+                Err(e) => {
+                    error!(
+                        "[Error][MainController->apply_task] Tokio task join error: {:?}",
+                        e
+                    );
+                    failed_rules.push(rule_name);
+                }
+            }
+        }
+
+        /* 계획에 있던 인덱스를 모두 처리하지 못했다면 실패로 종료한다. */
+        if !failed_rules.is_empty() {
+            return Err(anyhow!(
+                "[Error][MainController->apply_task] Plan '{}' was not fully applied. Failed rules: [{}]",
+                plan_path,
+                failed_rules.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[doc = "디스크 사용률이 상한을 넘은 경우, 목표 사용률 아래로 내려갈 때까지 인덱스를 삭제해주는 함수"]
+    /// # Arguments
+    /// * `dry_run` - true 인 경우 계획만 출력하고 삭제하지 않는다.
+    /// * `high_disk_percent` - 규칙 파일의 high_disk_percent 를 덮어쓸 값
+    /// * `target_disk_percent` - 규칙 파일의 target_disk_percent 를 덮어쓸 값
+    /// * `override_safety_caps` - true 인 경우 [safety] 안전 상한을 넘더라도 삭제한다.
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn emergency_task(
+        &self,
+        dry_run: bool,
+        high_disk_percent: Option<f64>,
+        target_disk_percent: Option<f64>,
+        override_safety_caps: bool,
+    ) -> Result<(), anyhow::Error> {
+        let target_indexes: TargetIndexList = self.read_target_indexes()?;
+
+        let emergency_config: EmergencyConfig = match (
+            target_indexes.emergency(),
+            high_disk_percent,
+            target_disk_percent,
+        ) {
+            (_, Some(high), Some(target)) => EmergencyConfig::new(high, target),
+            (Some(config), high, target) => EmergencyConfig::new(
+                high.unwrap_or(*config.high_disk_percent()),
+                target.unwrap_or(*config.target_disk_percent()),
+            ),
+            (None, _, _) => {
+                return Err(anyhow!(
+                    "[Error][MainController->emergency_task] [emergency] is not configured in '{}'. (or pass --high-disk-percent and --target-disk-percent)",
+                    self.index_list_path
+                ))
+            }
+        };
+
+        emergency_config.validate()?;
+
+        let emergency_plan: EmergencyPlan = self
+            .index_clear_service
+            .build_emergency_plan(target_indexes.index(), &emergency_config)
+            .await?;
+
+        println!(
+            "Disk usage: {:.1}% (high: {}%, target: {}%)",
+            emergency_plan.used_percent(),
+            emergency_config.high_disk_percent(),
+            emergency_config.target_disk_percent()
+        );
+
+        if emergency_plan.used_percent() <= emergency_config.high_disk_percent() {
+            println!("Disk usage is below the high watermark. Nothing to do.");
+            return Ok(());
+        }
+
+        self.print_index_plan(emergency_plan.decisions())?;
+        println!(
+            "Expected disk usage after cleanup: {:.1}%",
+            emergency_plan.projected_percent()
+        );
+
+        if dry_run {
+            return Ok(());
+        }
+
+        /* 긴급 정리도 일반 실행과 같은 [safety] 안전 상한을 지킨다. */
+        if let Some(safety_config) =
+            self.resolve_safety_config(&target_indexes, override_safety_caps)
+        {
+            safety_config.check(&emergency_plan.safety_plan())?;
+        }
+
+        let run_id: String = generate_run_id();
+        info!("[MainController->emergency_task] Run id: {}", run_id);
+
+        self.index_clear_service
+            .delete_planned_indexes(emergency_plan.decisions(), &run_id)
+            .await
+    }
+
+    #[doc = "삭제된 인덱스를 스냅샷에서 `<prefix><인덱스 명>` 으로 복원해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 복원할 (삭제된) 인덱스 명
+    /// * `repository` - 스냅샷 저장소 (None 인 경우 규칙 파일의 snapshot_repository 전체에서 찾는다.)
+    /// * `snapshot_name` - 스냅샷 명 (None 인 경우 인덱스가 들어있는 가장 최근 스냅샷)
+    /// * `prefix` - 복원될 인덱스 명 앞에 붙일 접두어
+    /// * `ttl_days` - 복원 후 보존할 일수 (None 인 경우 보존기한 규칙이 삭제하지 않는다.)
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn restore_task(
+        &self,
+        index_name: &str,
+        repository: Option<&str>,
+        snapshot_name: Option<&str>,
+        prefix: &str,
+        ttl_days: Option<u32>,
+    ) -> Result<(), anyhow::Error> {
+        let repositories: Vec<String> = match repository {
+            Some(repository) => vec![repository.to_string()],
+            None => {
+                let target_indexes: TargetIndexList = self.read_target_indexes()?;
+                let mut repositories: Vec<String> = target_indexes
+                    .index()
+                    .iter()
+                    .filter_map(|target_index| target_index.snapshot_repository().clone())
+                    .collect();
+
+                repositories.sort();
+                repositories.dedup();
+                repositories
+            }
+        };
+
+        if repositories.is_empty() {
+            return Err(anyhow!(
+                "[Error][MainController->restore_task] No snapshot repository to search. (set --repository or snapshot_repository in '{}')",
+                self.index_list_path
+            ));
+        }
+
+        let restored_index_name: String = format!("{}{}", prefix, index_name);
+
+        let source: String = self
+            .index_clear_service
+            .restore_index(
+                index_name,
+                &repositories,
+                snapshot_name,
+                &restored_index_name,
+                ttl_days,
+            )
+            .await?;
+
+        let ttl_desc: String = match ttl_days {
+            Some(ttl_days) => format!("deleted after {} days", ttl_days),
+            None => String::from("ignored by retention rules"),
+        };
+
+        println!(
+            "Restored '{}' from '{}' as '{}' ({}).",
+            index_name, source, restored_index_name, ttl_desc
+        );
+        info!(
+            "[MainController->restore_task] Restored '{}' from '{}' as '{}' ({}).",
+            index_name, source, restored_index_name, ttl_desc
+        );
+
+        Ok(())
+    }
+
+    #[doc = "아카이브(NDJSON)로 내보낸 인덱스를 `<prefix><인덱스 명>` 으로 다시 가져와주는 함수"]
+    /// # Arguments
+    /// * `archive_dir` - 인덱스 하나의 아카이브 디렉토리
+    /// * `prefix` - 가져올 인덱스 명 앞에 붙일 접두어
+    /// * `ttl_days` - 가져온 후 보존할 일수 (None 인 경우 보존기한 규칙이 삭제하지 않는다.)
+    /// * `bulk_size` - 한 번의 _bulk 요청에 넣을 문서 수
+    /// * `bulk_concurrency` - 동시에 보낼 _bulk 요청 수
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn import_task(
+        &self,
+        archive_dir: &str,
+        prefix: &str,
+        ttl_days: Option<u32>,
+        bulk_size: usize,
+        bulk_concurrency: usize,
+    ) -> Result<(), anyhow::Error> {
+        if bulk_size == 0 || bulk_concurrency == 0 {
+            return Err(anyhow!(
+                "[Error][MainController->import_task] --bulk-size and --bulk-concurrency must be greater than 0"
+            ));
+        }
+
+        let archive_dir: &Path = Path::new(archive_dir);
+        let manifest: ArchiveManifest = read_archive_manifest(archive_dir)?;
+        let imported_index_name: String = format!("{}{}", prefix, manifest.index_name());
+
+        let imported_count: u64 = self
+            .index_clear_service
+            .import_archive(
+                archive_dir,
+                &manifest,
+                &imported_index_name,
+                ttl_days,
+                bulk_size,
+                bulk_concurrency,
+            )
+            .await?;
+
+        let ttl_desc: String = match ttl_days {
+            Some(ttl_days) => format!("deleted after {} days", ttl_days),
+            None => String::from("ignored by retention rules"),
+        };
+
+        println!(
+            "Imported {} documents of '{}' from '{}' as '{}' ({}).",
+            imported_count,
+            manifest.index_name(),
+            archive_dir.display(),
+            imported_index_name,
+            ttl_desc
+        );
+        info!(
+            "[MainController->import_task] Imported {} documents of '{}' from '{}' as '{}' ({}).",
+            imported_count,
+            manifest.index_name(),
+            archive_dir.display(),
+            imported_index_name,
+            ttl_desc
+        );
+
+        Ok(())
+    }
+
+    #[doc = "인덱스 보존 규칙 파일을 검증해주는 함수 (클러스터에 접속하지 않음)"]
+    pub fn validate_task(&self) -> Result<(), anyhow::Error> {
+        let target_indexes: TargetIndexList = self.read_target_indexes()?;
+
+        for target_index in target_indexes.index() {
+            let rule_name: String = if *target_index.data_stream() {
+                format!("{} (data stream)", target_index.index_name())
+            } else {
+                target_index.index_name().to_string()
+            };
+
+            println!(
+                "{:<30} duration_days = {:<5} date granularity = {:<8} age_source = {:?}",
+                rule_name,
+                target_index.duration_days(),
+                format!("{:?}", target_index.index_date_format()?.granularity()),
+                target_index.age_source()
+            );
+
+            for phase in target_index.phases() {
+                println!(
+                    "{:<30} after {} days: replicas = {:?}, forcemerge_segments = {:?}, require_attributes = {:?}",
+                    "",
+                    phase.after_days(),
+                    phase.replicas(),
+                    phase.forcemerge_segments(),
+                    phase.require_attributes()
+                );
+            }
+        }
+
+        println!(
+            "\n'{}' is valid. ({} rules)",
+            self.index_list_path,
+            target_indexes.index().len()
+        );
+
+        /* 리갈 홀드 파일이 설정된 경우 함께 검증하고 홀드별 유효 여부를 보여준다. */
+        let legal_holds: LegalHoldList = read_legal_holds()?;
+        let today: NaiveDate = get_currnet_utc_naivedatetime().date();
+
+        if !legal_holds.hold().is_empty() {
+            println!();
+        }
+
+        for entry in legal_holds.hold() {
+            let status: &str = if entry.hold().is_active(today) {
+                "active"
+            } else {
+                "expired"
+            };
+
+            println!("{:<30} [{:<7}] {}", entry.index(), status, entry.hold());
+        }
+
+        Ok(())
+    }
+
+    #[doc = "규칙에 해당하는 인덱스들과 각 인덱스의 나이를 출력해주는 함수"]
+    pub async fn list_task(&self) -> Result<(), anyhow::Error> {
+        let target_indexes: TargetIndexList = self.read_target_indexes()?;
+        let index_plan: Vec<IndexDecision> = self.collect_index_plan(&target_indexes).await?;
+        let cur_utc_date: NaiveDate = get_current_utc_naivedate();
+
+        println!(
+            "{:<45} {:<12} {:>9} {:<25} ACTION",
+            "INDEX", "DATE", "AGE(DAYS)", "RULE"
+        );
+
+        for decision in &index_plan {
+            let (parsed_date, age_days) = match decision.parsed_date() {
+                Some(parsed_date) => (
+                    get_str_from_naivedate(*parsed_date, "%Y-%m-%d")?,
+                    (cur_utc_date - *parsed_date).num_days().to_string(),
+                ),
+                None => (String::from("-"), String::from("-")),
+            };
+
+            println!(
+                "{:<45} {:<12} {:>9} {:<25} {}",
+                decision.index_name(),
+                parsed_date,
+                age_days,
+                decision.rule(),
+                decision.action()
+            );
+        }
+
+        println!("\n{} indices matched.", index_plan.len());
+
+        Ok(())
+    }
+
+    #[doc = "특정 인덱스가 어떤 규칙에 해당하고, 어떻게 처리되는지 설명해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 설명할 인덱스 명
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub async fn explain_task(&self, index_name: &str) -> Result<(), anyhow::Error> {
+        let target_indexes: TargetIndexList = self.read_target_indexes()?;
+
+        let matched_rules: Vec<&TargetIndex> = target_indexes
+            .index()
+            .iter()
+            .filter(|target_index| target_index.is_match_index(index_name))
+            .collect();
+
+        if matched_rules.is_empty() {
+            println!(
+                "{} does not match any rule in '{}'. It will never be deleted.",
+                index_name, self.index_list_path
+            );
+            return Ok(());
+        }
+
+        for target_index in matched_rules {
+            println!("rule          : {}", target_index.index_name());
+
+            if *target_index.data_stream() {
+                println!("data_stream   : backing indices of '{}' (write index is always kept)", target_index.index_name());
+            }
+
+            println!("duration_days : {}", target_index.duration_days());
+
+            if let Some(soft_delete_days) = target_index.soft_delete_days() {
+                println!(
+                    "soft_delete   : {} days ({:?})",
+                    soft_delete_days,
+                    target_index.soft_delete_mode()
+                );
+            }
+
+            /* 같은 규칙의 다른 인덱스에 따라 결과가 달라질 수 있으므로 규칙 전체 계획을 세운 뒤 찾는다. */
+            let rule_plan: Vec<IndexDecision> =
+                self.index_clear_service.build_index_plan(target_index).await?;
+
+            match rule_plan
+                .iter()
+                .find(|decision| decision.index_name() == index_name)
+            {
+                Some(decision) => {
+                    let parsed_date: String = match decision.parsed_date() {
+                        Some(parsed_date) => get_str_from_naivedate(*parsed_date, "%Y-%m-%d")?,
+                        None => String::from("-"),
+                    };
+
+                    println!("index date    : {}", parsed_date);
+                    println!(
+                        "cutoff date   : {}",
+                        get_str_from_naivedate(*decision.cutoff_date(), "%Y-%m-%d")?
+                    );
+                    println!("action        : {}", decision.action());
+                    println!("reason        : {}", decision.reason());
+
+                    for protection in decision.protections() {
+                        println!("protected     : {}", protection);
+                    }
+                }
+                None => {
+                    println!("action        : -");
+                    println!("reason        : index does not exist in the cluster");
+                }
+            }
+
+            println!();
+        }
+
+        Ok(())
+    }
+
+    #[doc = "규칙별로 병렬로 삭제 계획을 세운 뒤 하나로 모아주는 함수"]
+    /// # Arguments
+    /// * `target_indexes` - 인덱스 보존 규칙 리스트
+    ///
+    /// # Returns
+    /// * Result<Vec<IndexDecision>, anyhow::Error>
+    async fn collect_index_plan(
+        &self,
+        target_indexes: &TargetIndexList,
+    ) -> Result<Vec<IndexDecision>, anyhow::Error> {
+        let mut handles: Vec<tokio::task::JoinHandle<Result<Vec<IndexDecision>, anyhow::Error>>> =
+            vec![];
+
+        for target_index in target_indexes.index.clone() {
+            let service: Arc<I> = Arc::clone(&self.index_clear_service);
+
+            let handle: tokio::task::JoinHandle<Result<Vec<IndexDecision>, anyhow::Error>> =
+                tokio::spawn(async move { service.build_index_plan(&target_index).await });
+
+            handles.push(handle);
+        }
+
+        let mut index_plan: Vec<IndexDecision> = Vec::new();
+
+        /* 계획 단계에서는 규칙 하나라도 실패하면 불완전한 계획이 되므로 에러로 처리한다. */
+        for handle in handles {
+            let rule_plan: Vec<IndexDecision> = handle.await.map_err(|e| {
+                anyhow!(
+                    "[Error][MainController->collect_index_plan] Tokio task join error: {:?}",
+                    e
+                )
+            })??;
+
+            index_plan.extend(rule_plan);
+        }
+
+        Ok(index_plan)
+    }
+
+    #[doc = "삭제 계획을 표 형태로 출력해주는 함수"]
+    /// # Arguments
+    /// * `index_plan` - 출력할 삭제 계획
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    fn print_index_plan(&self, index_plan: &[IndexDecision]) -> Result<(), anyhow::Error> {
+        println!(
+            "{:<11} {:<45} {:<12} {:<12} {:>9} {:<25} REASON",
+            "ACTION", "INDEX", "DATE", "CUTOFF", "SIZE", "RULE"
+        );
+
+        for decision in index_plan {
+            let parsed_date: String = match decision.parsed_date() {
+                Some(parsed_date) => get_str_from_naivedate(*parsed_date, "%Y-%m-%d")?,
+                None => String::from("-"),
+            };
+
+            println!(
+                "{:<11} {:<45} {:<12} {:<12} {:>9} {:<25} {}",
+                decision.action(),
+                decision.index_name(),
+                parsed_date,
+                get_str_from_naivedate(*decision.cutoff_date(), "%Y-%m-%d")?,
+                decision
+                    .store_size()
+                    .map(format_byte_size)
+                    .unwrap_or(String::from("-")),
+                decision.rule(),
+                decision.reason()
+            );
+        }
+
+        let delete_decisions: Vec<&IndexDecision> = index_plan
+            .iter()
+            .filter(|decision| decision.action == IndexAction::Delete)
+            .collect();
+
+        let delete_size: u64 = delete_decisions
+            .iter()
+            .filter_map(|decision| decision.store_size)
+            .sum();
+
+        let soft_delete_cnt: usize = index_plan
+            .iter()
+            .filter(|decision| decision.action == IndexAction::SoftDelete)
+            .count();
+
+        /* 보존기한이 지났지만 보호 대상이라 삭제하지 않는 인덱스 수 */
+        let protected_cnt: usize = index_plan
+            .iter()
+            .filter(|decision| {
+                decision.action == IndexAction::Skip && !decision.protections.is_empty()
+            })
+            .count();
+
+        println!(
+            "\nPlan: {} to delete ({}), {} to soft-delete, {} protected, {} total indices checked.",
+            delete_decisions.len(),
+            format_byte_size(delete_size),
+            soft_delete_cnt,
+            protected_cnt,
+            index_plan.len()
+        );
+        info!(
+            "[MainController->print_index_plan] Plan: {} to delete ({}), {} to soft-delete, {} protected, {} total indices checked.",
+            delete_decisions.len(),
+            format_byte_size(delete_size),
+            soft_delete_cnt,
+            protected_cnt,
+            index_plan.len()
+        );
+
+        Ok(())
+    }
+}
@@ -21,31 +21,140 @@ use controller::main_controller::*;
 
 mod model;
 
+mod metrics;
+
 mod configs;
+use configs::audit_config::*;
+use configs::cli_args::*;
+use configs::credential_provider::*;
+use configs::hold_config::*;
 use configs::elastic_server_config::*;
 
 #[tokio::main]
 async fn main() {
     dotenv().ok();
 
+    let cli_args: CliArgs = CliArgs::parse();
+
     /* 전역 로거설정 */
     set_global_logger();
 
-    init_elastic_config();
+    let command: Commands = cli_args.command.clone().unwrap_or(Commands::Run);
+
+    if command.requires_cluster() {
+        init_elastic_config(
+            cli_args.global.es_url().as_deref(),
+            *cli_args.global.concurrency(),
+        );
+    }
+
+    init_audit_config(
+        cli_args.global.audit_log().as_deref(),
+        cli_args.global.audit_index().as_deref(),
+    );
+
+    init_hold_config(cli_args.global.holds_file().as_deref());
 
-    info!("Program Start");
+    info!("Program Start : {:?}", command);
+
+    let index_list_path: String = match cli_args.global.config() {
+        Some(index_list_path) => index_list_path.clone(),
+        None if !command.requires_rules() => String::new(),
+        None => {
+            eprintln!("[Error] index rule config path must be set. (--config or 'INDEX_LIST_PATH')");
+            std::process::exit(2);
+        }
+    };
 
     let index_clear_service: IndexClearServicePub = IndexClearServicePub::new();
     let main_controller: MainController<IndexClearServicePub> =
-        MainController::new(Arc::new(index_clear_service));
+        MainController::new(Arc::new(index_clear_service), index_list_path);
 
-    match main_controller.main_task().await {
-        Ok(_) => (),
-        Err(e) => {
-            error!("{:?}", e);
+    let result: Result<(), anyhow::Error> = match command {
+        Commands::Run => {
+            main_controller
+                .main_task(*cli_args.global.override_safety_caps())
+                .await
         }
+        Commands::Plan { out } => main_controller
+            .plan_task(out.as_deref())
+            .await
+            .map(|_| ()),
+        Commands::Apply { plan_file } => {
+            main_controller
+                .apply_task(&plan_file, *cli_args.global.override_safety_caps())
+                .await
+        }
+        Commands::Validate => main_controller.validate_task(),
+        Commands::EncryptSecrets { out } => encrypt_secrets_from_stdin(&out),
+        Commands::List => main_controller.list_task().await,
+        Commands::Explain { index } => main_controller.explain_task(&index).await,
+        Commands::Emergency {
+            dry_run,
+            high_disk_percent,
+            target_disk_percent,
+        } => {
+            main_controller
+                .emergency_task(
+                    dry_run,
+                    high_disk_percent,
+                    target_disk_percent,
+                    *cli_args.global.override_safety_caps(),
+                )
+                .await
+        }
+        Commands::Restore {
+            index,
+            repository,
+            snapshot,
+            prefix,
+            ttl_days,
+        } => {
+            main_controller
+                .restore_task(
+                    &index,
+                    repository.as_deref(),
+                    snapshot.as_deref(),
+                    &prefix,
+                    ttl_days,
+                )
+                .await
+        }
+        Commands::Import {
+            archive,
+            prefix,
+            ttl_days,
+            bulk_size,
+            bulk_concurrency,
+        } => {
+            main_controller
+                .import_task(&archive, &prefix, ttl_days, bulk_size, bulk_concurrency)
+                .await
+        }
+        Commands::Daemon {
+            cron,
+            timezone,
+            emergency_cron,
+            metrics_addr,
+        } => {
+            main_controller
+                .daemon_task(
+                    cron.as_deref(),
+                    timezone.as_deref(),
+                    emergency_cron.as_deref(),
+                    metrics_addr.as_deref(),
+                    *cli_args.global.override_safety_caps(),
+                )
+                .await
+        }
+    };
+
+    if let Err(e) = result {
+        error!("{:?}", e);
+        eprintln!("{:#}", e);
+        std::process::exit(1);
     }
-    
+
     //let duration = start.elapsed(); // 경과 시간 측정
     // println!("⏱ 실행 시간: {:.3?}", duration);
 }
@@ -0,0 +1,97 @@
+use crate::common::*;
+
+use crate::metrics::retention_metrics::*;
+
+use crate::repository::es_repository::*;
+
+/* 요청 헤더를 읽을 최대 크기 */
+const MAX_REQUEST_HEADER_BYTES: usize = 8192;
+
+#[doc = "Prometheus 가 수집할 수 있도록 `/metrics` HTTP 엔드포인트를 열어주는 함수 (`/nodes` 는 노드별 상태 Json)"]
+/// # Arguments
+/// * `listen_addr` - 바인딩할 주소 (예: "0.0.0.0:9464")
+///
+/// # Returns
+/// * Result<JoinHandle<()>, anyhow::Error> - 서버 task
+pub async fn start_metrics_server(listen_addr: &str) -> Result<JoinHandle<()>, anyhow::Error> {
+    let listener: TcpListener = TcpListener::bind(listen_addr).await.map_err(|e| {
+        anyhow!(
+            "[Error][start_metrics_server] Failed to bind '{}': {:?}",
+            listen_addr,
+            e
+        )
+    })?;
+
+    info!("[start_metrics_server] Serving metrics on http://{}/metrics", listen_addr);
+
+    let handle: JoinHandle<()> = tokio::spawn(async move {
+        loop {
+            let (stream, _) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    error!("[Error][start_metrics_server] Failed to accept connection: {:?}", e);
+                    continue;
+                }
+            };
+
+            tokio::spawn(async move {
+                if let Err(e) = handle_metrics_request(stream).await {
+                    error!("[Error][start_metrics_server] {:?}", e);
+                }
+            });
+        }
+    });
+
+    Ok(handle)
+}
+
+#[doc = "HTTP 요청 하나를 처리해주는 함수"]
+async fn handle_metrics_request(mut stream: TcpStream) -> Result<(), anyhow::Error> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut chunk: [u8; 1024] = [0; 1024];
+
+    /* 요청 헤더의 끝(빈 줄)까지만 읽어준다. */
+    while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read_size: usize = stream.read(&mut chunk).await?;
+
+        if read_size == 0 || buffer.len() > MAX_REQUEST_HEADER_BYTES {
+            break;
+        }
+
+        buffer.extend_from_slice(&chunk[..read_size]);
+    }
+
+    let request: String = String::from_utf8_lossy(&buffer).to_string();
+    let mut request_line = request.lines().next().unwrap_or("").split_whitespace();
+    let method: &str = request_line.next().unwrap_or("");
+    let path: &str = request_line.next().unwrap_or("");
+
+    let (status, content_type, body) = if method == "GET" && path == "/metrics" {
+        (
+            "200 OK",
+            "text/plain; version=0.0.4",
+            get_retention_metrics().gather_text()?,
+        )
+    } else if method == "GET" && path == "/nodes" {
+        (
+            "200 OK",
+            "application/json",
+            serde_json::to_string_pretty(&get_es_node_stats())?,
+        )
+    } else {
+        ("404 Not Found", "text/plain", String::from("not found\n"))
+    };
+
+    let response: String = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    Ok(())
+}
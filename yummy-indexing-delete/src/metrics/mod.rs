@@ -0,0 +1,2 @@
+pub mod metrics_server;
+pub mod retention_metrics;
@@ -0,0 +1,170 @@
+use crate::common::*;
+
+static RETENTION_METRICS: once_lazy<RetentionMetrics> = once_lazy::new(|| {
+    RetentionMetrics::new()
+        .expect("[Error][RETENTION_METRICS] Failed to register prometheus metrics")
+});
+
+#[doc = "인덱스 보존기한 작업의 Prometheus 메트릭"]
+pub struct RetentionMetrics {
+    registry: Registry,
+    /* 규칙별 메트릭 */
+    pub indices_matched: IntCounterVec,
+    pub indices_deleted: IntCounterVec,
+    pub indices_soft_deleted: IntCounterVec,
+    pub phase_actions: IntCounterVec,
+    pub indices_skipped: IntCounterVec,
+    pub indices_delete_failed: IntCounterVec,
+    pub bytes_freed: IntCounterVec,
+    pub run_duration: HistogramVec,
+    pub last_success_timestamp: GaugeVec,
+    pub last_delete_timestamp: GaugeVec,
+    /* Elasticsearch 메트릭 */
+    pub es_request_duration: HistogramVec,
+    pub es_conn_wait_duration: Histogram,
+    pub es_node_up: GaugeVec,
+    pub es_node_failures: IntCounterVec,
+}
+
+impl RetentionMetrics {
+    fn new() -> Result<Self, anyhow::Error> {
+        let registry: Registry = Registry::new();
+
+        let indices_matched: IntCounterVec = IntCounterVec::new(
+            Opts::new("retention_indices_matched_total", "Indices matched by the rule"),
+            &["rule"],
+        )?;
+        let indices_deleted: IntCounterVec = IntCounterVec::new(
+            Opts::new("retention_indices_deleted_total", "Indices deleted by the rule"),
+            &["rule"],
+        )?;
+        let indices_soft_deleted: IntCounterVec = IntCounterVec::new(
+            Opts::new(
+                "retention_indices_soft_deleted_total",
+                "Indices closed or write-blocked by the rule before being deleted",
+            ),
+            &["rule"],
+        )?;
+        let phase_actions: IntCounterVec = IntCounterVec::new(
+            Opts::new(
+                "retention_phase_actions_total",
+                "Phase actions (settings, forcemerge) applied to indices by the rule",
+            ),
+            &["rule", "action"],
+        )?;
+        let indices_skipped: IntCounterVec = IntCounterVec::new(
+            Opts::new(
+                "retention_indices_skipped_total",
+                "Indices skipped because their age could not be decided",
+            ),
+            &["rule"],
+        )?;
+        let indices_delete_failed: IntCounterVec = IntCounterVec::new(
+            Opts::new(
+                "retention_indices_delete_failed_total",
+                "Index deletions that failed",
+            ),
+            &["rule"],
+        )?;
+        let bytes_freed: IntCounterVec = IntCounterVec::new(
+            Opts::new(
+                "retention_bytes_freed_total",
+                "Store size of the deleted indices in bytes",
+            ),
+            &["rule"],
+        )?;
+        let run_duration: HistogramVec = HistogramVec::new(
+            HistogramOpts::new("retention_run_duration_seconds", "Duration of a rule run")
+                .buckets(vec![0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0, 900.0]),
+            &["rule"],
+        )?;
+        let last_success_timestamp: GaugeVec = GaugeVec::new(
+            Opts::new(
+                "retention_last_success_timestamp_seconds",
+                "Unix time of the last rule run that finished without errors",
+            ),
+            &["rule"],
+        )?;
+        let last_delete_timestamp: GaugeVec = GaugeVec::new(
+            Opts::new(
+                "retention_last_delete_timestamp_seconds",
+                "Unix time of the last successful index deletion",
+            ),
+            &["rule"],
+        )?;
+        let es_request_duration: HistogramVec = HistogramVec::new(
+            HistogramOpts::new(
+                "elasticsearch_request_duration_seconds",
+                "Elasticsearch request latency per node",
+            ),
+            &["node", "outcome"],
+        )?;
+        let es_conn_wait_duration: Histogram = Histogram::with_opts(
+            HistogramOpts::new(
+                "elasticsearch_connection_wait_seconds",
+                "Time spent waiting for an Elasticsearch connection permit",
+            )
+            .buckets(vec![0.001, 0.01, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0]),
+        )?;
+        let es_node_up: GaugeVec = GaugeVec::new(
+            Opts::new(
+                "elasticsearch_node_up",
+                "Whether the connection pool considers the node alive (1) or dead (0)",
+            ),
+            &["node"],
+        )?;
+        let es_node_failures: IntCounterVec = IntCounterVec::new(
+            Opts::new(
+                "elasticsearch_node_failures_total",
+                "Requests that failed on the node and marked it dead",
+            ),
+            &["node"],
+        )?;
+
+        registry.register(Box::new(indices_matched.clone()))?;
+        registry.register(Box::new(indices_deleted.clone()))?;
+        registry.register(Box::new(indices_soft_deleted.clone()))?;
+        registry.register(Box::new(phase_actions.clone()))?;
+        registry.register(Box::new(indices_skipped.clone()))?;
+        registry.register(Box::new(indices_delete_failed.clone()))?;
+        registry.register(Box::new(bytes_freed.clone()))?;
+        registry.register(Box::new(run_duration.clone()))?;
+        registry.register(Box::new(last_success_timestamp.clone()))?;
+        registry.register(Box::new(last_delete_timestamp.clone()))?;
+        registry.register(Box::new(es_request_duration.clone()))?;
+        registry.register(Box::new(es_conn_wait_duration.clone()))?;
+        registry.register(Box::new(es_node_up.clone()))?;
+        registry.register(Box::new(es_node_failures.clone()))?;
+
+        Ok(Self {
+            registry,
+            indices_matched,
+            indices_deleted,
+            indices_soft_deleted,
+            phase_actions,
+            indices_skipped,
+            indices_delete_failed,
+            bytes_freed,
+            run_duration,
+            last_success_timestamp,
+            last_delete_timestamp,
+            es_request_duration,
+            es_conn_wait_duration,
+            es_node_up,
+            es_node_failures,
+        })
+    }
+
+    #[doc = "수집된 메트릭을 Prometheus text format 으로 만들어주는 함수"]
+    pub fn gather_text(&self) -> Result<String, anyhow::Error> {
+        let mut buffer: Vec<u8> = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+#[doc = "전역 메트릭을 반환해주는 함수"]
+pub fn get_retention_metrics() -> &'static RetentionMetrics {
+    &RETENTION_METRICS
+}
@@ -0,0 +1,57 @@
+use crate::common::*;
+
+/* 아카이브 디렉토리 안의 매니페스트 파일 명 - 매니페스트가 없는 아카이브는 완성되지 않은 것으로 본다. */
+pub const ARCHIVE_MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveCompression {
+    #[default]
+    Gzip,
+    Zstd,
+}
+
+impl ArchiveCompression {
+    #[doc = "압축 방식에 해당하는 파일 확장자"]
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveCompression::Gzip => "gz",
+            ArchiveCompression::Zstd => "zst",
+        }
+    }
+}
+
+#[doc = "삭제 전에 인덱스를 아카이브할 위치와 압축 방식"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct ArchiveTarget {
+    pub dir: String,
+    #[serde(default)]
+    pub compression: ArchiveCompression,
+}
+
+#[doc = "인덱스 아카이브의 매니페스트 - 문서 수와 데이터 파일 체크섬을 기록한다."]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct ArchiveManifest {
+    pub index_name: String,
+    pub rule: String,
+    pub run_id: String,
+    pub created_at: DateTime<Utc>,
+    pub parsed_date: Option<NaiveDate>,
+    pub compression: ArchiveCompression,
+    /* 문서가 NDJSON ({"_id": .., "_source": ..}) 으로 들어있는 데이터 파일 명 */
+    pub data_file: String,
+    /* 매핑과 세팅이 들어있는 파일 명 */
+    pub definition_file: String,
+    pub doc_count: u64,
+    /* 데이터 파일의 SHA-256 */
+    pub sha256: String,
+}
+
+impl ArchiveTarget {
+    #[doc = "인덱스 하나의 아카이브 디렉토리 경로"]
+    pub fn index_dir(&self, index_name: &str) -> PathBuf {
+        Path::new(&self.dir).join(index_name)
+    }
+}
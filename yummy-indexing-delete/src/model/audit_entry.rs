@@ -0,0 +1,89 @@
+use crate::common::*;
+
+use crate::model::index_decision::*;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /* 삭제 완료 */
+    Deleted,
+    /* soft delete 완료 (닫거나 쓰기를 막아둠) */
+    SoftDeleted,
+    /* 삭제 시도했으나 실패 */
+    Failed,
+    /* 나이를 판단할 수 없어서 건너뜀 */
+    Skipped,
+}
+
+#[doc = "인덱스 삭제 판단 한 건에 대한 감사 로그"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct AuditEntry {
+    pub run_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub rule: String,
+    pub index_name: String,
+    pub parsed_date: Option<NaiveDate>,
+    /* 삭제 시점의 인덱스 저장 용량 (byte) */
+    pub store_size: Option<u64>,
+    /* 삭제 시점의 인덱스 문서 수 */
+    pub docs_count: Option<u64>,
+    pub outcome: AuditOutcome,
+    pub reason: String,
+    pub error: Option<String>,
+    /* 삭제 전에 남긴 스냅샷 ("<저장소>/<스냅샷>") */
+    #[serde(default)]
+    pub snapshot: Option<String>,
+    /* 삭제 전에 남긴 아카이브 디렉토리 */
+    #[serde(default)]
+    pub archive: Option<String>,
+}
+
+impl AuditEntry {
+    #[doc = "인덱스 결정으로 감사 로그 한 건을 만들어주는 함수"]
+    /// # Arguments
+    /// * `run_id` - 실행 ID
+    /// * `decision` - 인덱스 결정
+    /// * `outcome` - 처리 결과
+    /// * `error` - 실패한 경우 에러 메시지
+    ///
+    /// # Returns
+    /// * AuditEntry
+    pub fn from_decision(
+        run_id: &str,
+        decision: &IndexDecision,
+        outcome: AuditOutcome,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            run_id: run_id.to_string(),
+            timestamp: Utc::now(),
+            rule: decision.rule().clone(),
+            index_name: decision.index_name().clone(),
+            parsed_date: *decision.parsed_date(),
+            store_size: *decision.store_size(),
+            docs_count: *decision.docs_count(),
+            outcome,
+            reason: decision.reason().clone(),
+            error,
+            snapshot: None,
+            archive: None,
+        }
+    }
+
+    #[doc = "삭제 전에 남긴 스냅샷과 아카이브를 설정해주는 함수"]
+    pub fn with_backup(mut self, snapshot: Option<String>, archive: Option<String>) -> Self {
+        self.snapshot = snapshot;
+        self.archive = archive;
+        self
+    }
+}
+
+#[doc = "실행 한 번을 구분하기 위한 실행 ID 를 만들어주는 함수 (예: 20250514T030000Z-1a2b3c)"]
+pub fn generate_run_id() -> String {
+    format!(
+        "{}-{:06x}",
+        Utc::now().format("%Y%m%dT%H%M%SZ"),
+        rand::thread_rng().gen_range(0..0x1000000)
+    )
+}
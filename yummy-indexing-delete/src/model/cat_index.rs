@@ -0,0 +1,43 @@
+use crate::common::*;
+
+#[doc = "_cat/indices 응답의 인덱스 한 건 (닫힌 인덱스는 크기/문서수가 비어있을 수 있음)"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct CatIndex {
+    pub index: String,
+    #[serde(default)]
+    pub health: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default, rename = "creation.date")]
+    pub creation_date: Option<String>,
+    #[serde(default, rename = "store.size")]
+    pub store_size: Option<String>,
+    #[serde(default, rename = "docs.count")]
+    pub docs_count: Option<String>,
+}
+
+impl CatIndex {
+    #[doc = "인덱스 생성 시각(UTC)을 반환해주는 함수"]
+    pub fn creation_datetime(&self) -> Option<NaiveDateTime> {
+        self.creation_date
+            .as_deref()
+            .and_then(|millis| millis.parse::<i64>().ok())
+            .and_then(DateTime::from_timestamp_millis)
+            .map(|datetime| datetime.naive_utc())
+    }
+
+    #[doc = "인덱스 저장 용량(byte)을 반환해주는 함수"]
+    pub fn store_size_bytes(&self) -> Option<u64> {
+        self.store_size
+            .as_deref()
+            .and_then(|size| size.parse::<u64>().ok())
+    }
+
+    #[doc = "인덱스 문서 수를 반환해주는 함수"]
+    pub fn docs_count_num(&self) -> Option<u64> {
+        self.docs_count
+            .as_deref()
+            .and_then(|count| count.parse::<u64>().ok())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::common::*;
+
+#[doc = "_data_stream 응답 전체"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct DataStreamList {
+    #[serde(default)]
+    pub data_streams: Vec<DataStream>,
+}
+
+#[doc = "_data_stream 응답의 데이터 스트림 한 건"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct DataStream {
+    pub name: String,
+    /* 현재 세대 번호 (롤오버될 때마다 1씩 증가) */
+    #[serde(default)]
+    pub generation: u64,
+    /* backing 인덱스 목록 - 생성된 순서대로이며 마지막 인덱스가 write 인덱스이다. */
+    #[serde(default)]
+    pub indices: Vec<DataStreamIndex>,
+}
+
+#[doc = "데이터 스트림의 backing 인덱스 한 건"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct DataStreamIndex {
+    pub index_name: String,
+}
+
+impl DataStream {
+    #[doc = "데이터 스트림의 현재 write 인덱스 명을 반환해주는 함수"]
+    pub fn write_index(&self) -> Option<&str> {
+        self.indices.last().map(|index| index.index_name.as_str())
+    }
+}
@@ -0,0 +1,22 @@
+use crate::common::*;
+
+use crate::model::index_decision::*;
+use crate::model::target_index::*;
+
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct DeletionPlan {
+    pub created_at: NaiveDateTime,
+    pub rules: Vec<TargetIndex>,
+    pub decisions: Vec<IndexDecision>,
+}
+
+impl DeletionPlan {
+    #[doc = "삭제(soft delete 포함) 예정인 인덱스 결정만 뽑아주는 함수"]
+    pub fn delete_decisions(&self) -> Vec<&IndexDecision> {
+        self.decisions
+            .iter()
+            .filter(|decision| decision.action.is_removal())
+            .collect()
+    }
+}
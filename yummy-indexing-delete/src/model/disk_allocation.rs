@@ -0,0 +1,132 @@
+use crate::common::*;
+
+#[doc = "_cat/allocation 응답의 노드 한 건 (UNASSIGNED 행은 디스크 정보가 비어있음)"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct CatAllocation {
+    #[serde(default)]
+    pub node: Option<String>,
+    #[serde(default, rename = "disk.used")]
+    pub disk_used: Option<String>,
+    #[serde(default, rename = "disk.total")]
+    pub disk_total: Option<String>,
+}
+
+#[doc = "데이터 노드 하나의 디스크 사용량"]
+#[derive(Debug, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct NodeDiskUsage {
+    pub node: String,
+    pub used: u64,
+    pub total: u64,
+}
+
+#[doc = "클러스터 디스크 사용량 - 노드별 워터마크가 적용되므로 가장 많이 사용중인 노드를 기준으로 판단한다."]
+#[derive(Debug, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct ClusterDiskUsage {
+    nodes: Vec<NodeDiskUsage>,
+}
+
+impl ClusterDiskUsage {
+    #[doc = "_cat/allocation 응답으로 클러스터 디스크 사용량을 만들어주는 함수"]
+    pub fn new(cat_allocations: Vec<CatAllocation>) -> Self {
+        let nodes: Vec<NodeDiskUsage> = cat_allocations
+            .into_iter()
+            .filter_map(|allocation| {
+                let used: u64 = allocation.disk_used.as_deref()?.parse::<u64>().ok()?;
+                let total: u64 = allocation.disk_total.as_deref()?.parse::<u64>().ok()?;
+
+                if total == 0 {
+                    return None;
+                }
+
+                Some(NodeDiskUsage::new(allocation.node?, used, total))
+            })
+            .collect();
+
+        Self { nodes }
+    }
+
+    #[doc = "가장 많이 사용중인 노드의 디스크 사용률(%)"]
+    pub fn max_used_percent(&self) -> f64 {
+        self.projected_max_used_percent(0)
+    }
+
+    #[doc = "freed_bytes 만큼 삭제했을 때 예상되는 가장 많이 사용중인 노드의 디스크 사용률(%) - 샤드가 노드 사용량에 비례해서 분포한다고 가정한다."]
+    pub fn projected_max_used_percent(&self, freed_bytes: u64) -> f64 {
+        let cluster_used: u64 = self.nodes.iter().map(|node| node.used).sum();
+
+        self.nodes
+            .iter()
+            .map(|node| {
+                let node_freed: f64 = if cluster_used == 0 {
+                    0.0
+                } else {
+                    freed_bytes as f64 * node.used as f64 / cluster_used as f64
+                };
+
+                ((node.used as f64 - node_freed).max(0.0) / node.total as f64) * 100.0
+            })
+            .fold(0.0, f64::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allocation(
+        node: Option<&str>,
+        disk_used: Option<&str>,
+        disk_total: Option<&str>,
+    ) -> CatAllocation {
+        CatAllocation {
+            node: node.map(str::to_string),
+            disk_used: disk_used.map(str::to_string),
+            disk_total: disk_total.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn unassigned_and_empty_rows_are_ignored() {
+        let disk_usage: ClusterDiskUsage = ClusterDiskUsage::new(vec![
+            allocation(Some("node-1"), Some("50"), Some("100")),
+            allocation(None, None, None),
+            allocation(Some("node-2"), Some("0"), Some("0")),
+        ]);
+
+        assert_eq!(disk_usage.nodes().len(), 1);
+        assert_eq!(disk_usage.max_used_percent(), 50.0);
+    }
+
+    #[test]
+    fn busiest_node_decides_usage() {
+        let disk_usage: ClusterDiskUsage = ClusterDiskUsage::new(vec![
+            allocation(Some("node-1"), Some("80"), Some("100")),
+            allocation(Some("node-2"), Some("20"), Some("100")),
+        ]);
+
+        assert_eq!(disk_usage.max_used_percent(), 80.0);
+    }
+
+    #[test]
+    fn freed_bytes_are_spread_by_node_usage() {
+        let disk_usage: ClusterDiskUsage = ClusterDiskUsage::new(vec![
+            allocation(Some("node-1"), Some("80"), Some("100")),
+            allocation(Some("node-2"), Some("20"), Some("100")),
+        ]);
+
+        /* node-1 : 80 - 40, node-2 : 20 - 10 */
+        assert_eq!(disk_usage.projected_max_used_percent(50), 40.0);
+        /* 사용량보다 많이 지워도 0% 아래로 내려가지 않는다. */
+        assert_eq!(disk_usage.projected_max_used_percent(1000), 0.0);
+    }
+
+    #[test]
+    fn cluster_without_nodes_is_empty() {
+        let disk_usage: ClusterDiskUsage = ClusterDiskUsage::new(vec![]);
+
+        assert_eq!(disk_usage.projected_max_used_percent(100), 0.0);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::common::*;
+
+use crate::model::index_decision::*;
+use crate::model::schedule_config::*;
+
+#[doc = "디스크 워터마크 기반 긴급 정리 설정 (index_list.toml 의 [emergency])"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct EmergencyConfig {
+    /* 이 사용률(%)을 넘으면 긴급 정리를 시작한다. */
+    pub high_disk_percent: f64,
+    /* 이 사용률(%) 아래로 내려갈 때까지 삭제한다. */
+    pub target_disk_percent: f64,
+    /* 데몬 모드에서 디스크 사용률을 확인할 cron 표현식 - 미지정 시 데몬 모드에서는 확인하지 않는다. */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[new(default)]
+    pub schedule: Option<String>,
+}
+
+impl EmergencyConfig {
+    #[doc = "긴급 정리 설정을 검증해주는 함수"]
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if !(0.0..=100.0).contains(&self.high_disk_percent)
+            || !(0.0..=100.0).contains(&self.target_disk_percent)
+        {
+            return Err(anyhow!("[Error][EmergencyConfig->validate] disk percent must be between 0 and 100."));
+        }
+
+        if self.target_disk_percent >= self.high_disk_percent {
+            return Err(anyhow!("[Error][EmergencyConfig->validate] target_disk_percent ({}) must be lower than high_disk_percent ({}).", self.target_disk_percent, self.high_disk_percent));
+        }
+
+        if let Some(schedule) = &self.schedule {
+            parse_cron_schedule(schedule)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[doc = "긴급 정리 계획 - 삭제 대상과 삭제 전후의 예상 디스크 사용률"]
+#[derive(Debug, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct EmergencyPlan {
+    pub used_percent: f64,
+    pub projected_percent: f64,
+    pub decisions: Vec<IndexDecision>,
+    /* 긴급 정리 대상 규칙에서 삭제하지 않는 인덱스 (안전 상한의 규칙별 비율 계산용) */
+    pub kept_decisions: Vec<IndexDecision>,
+}
+
+impl EmergencyPlan {
+    #[doc = "안전 상한 확인에 사용할 계획 - 삭제 대상과 남겨지는 인덱스를 모두 포함한다."]
+    pub fn safety_plan(&self) -> Vec<IndexDecision> {
+        self.decisions
+            .iter()
+            .chain(&self.kept_decisions)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emergency(emergency_toml: &str) -> EmergencyConfig {
+        toml::from_str(emergency_toml).unwrap()
+    }
+
+    #[test]
+    fn schedule_is_optional_and_validated() {
+        assert!(emergency("high_disk_percent = 85\ntarget_disk_percent = 75")
+            .validate()
+            .is_ok());
+        assert!(emergency(
+            "high_disk_percent = 85\ntarget_disk_percent = 75\nschedule = \"*/5 * * * *\""
+        )
+        .validate()
+        .is_ok());
+        assert!(emergency(
+            "high_disk_percent = 85\ntarget_disk_percent = 75\nschedule = \"every 5 minutes\""
+        )
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn target_must_be_below_high_watermark() {
+        assert!(EmergencyConfig::new(75.0, 75.0).validate().is_err());
+        assert!(EmergencyConfig::new(101.0, 75.0).validate().is_err());
+        assert!(EmergencyConfig::new(85.0, 75.0).validate().is_ok());
+    }
+
+    #[test]
+    fn safety_plan_includes_kept_indices() {
+        let decision = |index_name: &str| {
+            IndexDecision::new(
+                index_name,
+                "logs-*",
+                NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                None,
+                None,
+            )
+        };
+        let emergency_plan: EmergencyPlan = EmergencyPlan::new(
+            90.0,
+            70.0,
+            vec![decision("logs-1")],
+            vec![decision("logs-2"), decision("logs-3")],
+        );
+
+        let index_names: Vec<String> = emergency_plan
+            .safety_plan()
+            .into_iter()
+            .map(|decision| decision.index_name)
+            .collect();
+
+        assert_eq!(index_names, vec!["logs-1", "logs-2", "logs-3"]);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::common::*;
+
+#[doc = "Elasticsearch 노드 하나의 상태와 요청 통계"]
+#[derive(Debug, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct EsNodeStats {
+    pub host: String,
+    pub alive: bool,
+    /* 연속으로 실패한 횟수 - 성공하면 0 으로 돌아간다. */
+    pub consecutive_failures: u32,
+    pub requests: u64,
+    pub failures: u64,
+    /* 죽은 노드를 다시 시도하기까지 남은 시간(초) */
+    pub retry_in_secs: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+impl std::fmt::Display for EsNodeStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state: String = match (self.alive, self.retry_in_secs) {
+            (true, _) => String::from("alive"),
+            (false, Some(retry_in_secs)) => format!("dead, retry in {}s", retry_in_secs),
+            (false, None) => String::from("dead, retrying"),
+        };
+
+        write!(
+            f,
+            "{} [{}] requests: {}, failures: {}",
+            self.host, state, self.requests, self.failures
+        )?;
+
+        if let Some(last_error) = &self.last_error {
+            write!(f, " (last error: {})", last_error)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,351 @@
+use crate::common::*;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DateGranularity {
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    /* 인덱스 이름이 아닌 생성일자/롤오버 일자처럼 정확한 시각을 기준으로 하는 경우 */
+    Timestamp,
+}
+
+#[doc = "인덱스 이름에서 뽑아낸 날짜가 의미하는 기간"]
+#[derive(Debug, Getters, Clone, Copy, new)]
+#[getset(get = "pub")]
+pub struct IndexPeriod {
+    pub granularity: DateGranularity,
+    pub start: NaiveDateTime,
+    /* 기간의 마지막 일자 (월 단위 인덱스라면 그 달의 말일) */
+    pub last_date: NaiveDate,
+}
+
+impl IndexPeriod {
+    #[doc = "기간의 끝을 사람이 읽기 쉬운 문자열로 바꿔주는 함수"]
+    pub fn period_end_label(&self) -> String {
+        match self.granularity {
+            DateGranularity::Hourly => self.start.format("%Y-%m-%d %H:59").to_string(),
+            DateGranularity::Timestamp => self.start.format("%Y-%m-%d %H:%M:%S").to_string(),
+            _ => self.last_date.format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+#[doc = "규칙별 인덱스 이름 날짜 양식 - 프리셋, strftime 양식, named capture 정규식 모두 정규식 하나로 변환해서 사용한다."]
+#[derive(Debug, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct IndexDateFormat {
+    regex: Regex,
+    granularity: DateGranularity,
+}
+
+/*
+    프리셋과 strftime 양식은 날짜 앞뒤에 숫자가 아닌 문자(또는 처음/끝)를 요구해서 더 긴 숫자(롤오버 세대 등)의 일부를 날짜로 읽지 않는다.
+    (regex 크레이트는 lookaround 를 지원하지 않으므로 non-capturing 그룹으로 앞뒤 문자까지 매칭한다.)
+*/
+const DATE_PREFIX_GUARD: &str = r"(?:^|\D)";
+const DATE_SUFFIX_GUARD: &str = r"(?:\D|$)";
+
+/* 날짜 양식을 지정하지 않은 경우 사용되는 기본 정규식 (기존 yyyy-mm-dd, yyyy_mm_dd, yyyymmdd 에 yyyy.mm.dd 추가) */
+const DAILY_DATE_REGEX: &str =
+    r"(?:^|\D)(?P<year>\d{4})[-_.]?(?P<month>\d{2})[-_.]?(?P<day>\d{2})(?:\D|$)";
+const MONTHLY_DATE_REGEX: &str = r"(?:^|\D)(?P<year>\d{4})[-_.](?P<month>\d{2})(?:\D|$)";
+const WEEKLY_DATE_REGEX: &str = r"(?:^|\D)(?P<year>\d{4})[-_.]?[wW]?(?P<week>\d{2})(?:\D|$)";
+const HOURLY_DATE_REGEX: &str = r"(?:^|\D)(?P<year>\d{4})[-_.]?(?P<month>\d{2})[-_.]?(?P<day>\d{2})[-_.Tt]?(?P<hour>\d{2})(?:\D|$)";
+/* 데이터 스트림 backing 인덱스(.ds-<스트림명>-yyyy.mm.dd-<세대>)의 세대 생성일자 - 스트림 명에 날짜가 있어도 끝부분만 본다. */
+pub const DATA_STREAM_DATE_REGEX: &str =
+    r"-(?P<year>\d{4})\.(?P<month>\d{2})\.(?P<day>\d{2})-\d+$";
+
+/* 인덱스 이름의 날짜로 인정하는 연도 - 범위를 벗어나면 날짜가 아닌 숫자(세대 번호 등)로 본다. */
+const MIN_INDEX_YEAR: i32 = 1970;
+const MAX_INDEX_YEAR: i32 = 2200;
+
+impl IndexDateFormat {
+    #[doc = "규칙에 설정된 날짜 양식으로 IndexDateFormat 을 만들어주는 함수"]
+    /// # Arguments
+    /// * `date_format` - 프리셋(daily, monthly, weekly, hourly) 또는 strftime 양식 (예: %Y.%m.%d)
+    /// * `date_regex` - year, month, day, week, hour 이름의 그룹을 가진 정규식
+    ///
+    /// # Returns
+    /// * Result<Self, anyhow::Error>
+    pub fn new(date_format: Option<&str>, date_regex: Option<&str>) -> Result<Self, anyhow::Error> {
+        let regex_str: String = match (date_format, date_regex) {
+            (Some(_), Some(_)) => {
+                return Err(anyhow!("[Error][IndexDateFormat->new] 'date_format' and 'date_regex' cannot be used together."));
+            }
+            (None, Some(date_regex)) => date_regex.to_string(),
+            (Some("daily"), None) | (None, None) => DAILY_DATE_REGEX.to_string(),
+            (Some("monthly"), None) => MONTHLY_DATE_REGEX.to_string(),
+            (Some("weekly"), None) => WEEKLY_DATE_REGEX.to_string(),
+            (Some("hourly"), None) => HOURLY_DATE_REGEX.to_string(),
+            (Some(strftime), None) => Self::convert_strftime_to_regex(strftime)?,
+        };
+
+        let regex: Regex = Regex::new(&regex_str).map_err(|e| {
+            anyhow!(
+                "[Error][IndexDateFormat->new] Invalid date regex '{}': {}",
+                regex_str,
+                e
+            )
+        })?;
+
+        let group_names: HashSet<&str> = regex.capture_names().flatten().collect();
+
+        let granularity: DateGranularity = if group_names.contains("year")
+            && group_names.contains("month")
+            && group_names.contains("day")
+            && group_names.contains("hour")
+        {
+            DateGranularity::Hourly
+        } else if group_names.contains("year")
+            && group_names.contains("month")
+            && group_names.contains("day")
+        {
+            DateGranularity::Daily
+        } else if group_names.contains("year") && group_names.contains("week") {
+            DateGranularity::Weekly
+        } else if group_names.contains("year") && group_names.contains("month") {
+            DateGranularity::Monthly
+        } else {
+            return Err(anyhow!("[Error][IndexDateFormat->new] Date regex '{}' must contain named groups year+month[+day[+hour]] or year+week.", regex_str));
+        };
+
+        Ok(Self { regex, granularity })
+    }
+
+    #[doc = "strftime 양식을 named capture 정규식으로 바꿔주는 함수"]
+    fn convert_strftime_to_regex(strftime: &str) -> Result<String, anyhow::Error> {
+        let mut regex_str: String = String::from(DATE_PREFIX_GUARD);
+        let mut chars = strftime.chars();
+
+        while let Some(ch) = chars.next() {
+            if ch != '%' {
+                regex_str.push_str(&regex::escape(&ch.to_string()));
+                continue;
+            }
+
+            let group: &str = match chars.next() {
+                Some('Y') | Some('G') => r"(?P<year>\d{4})",
+                Some('m') => r"(?P<month>\d{2})",
+                Some('d') => r"(?P<day>\d{2})",
+                Some('V') => r"(?P<week>\d{2})",
+                Some('H') => r"(?P<hour>\d{2})",
+                Some('%') => "%",
+                other => {
+                    return Err(anyhow!("[Error][IndexDateFormat->convert_strftime_to_regex] Unsupported specifier '%{}' in date_format '{}'. (supported: %Y %G %m %d %V %H)", other.map(String::from).unwrap_or_default(), strftime));
+                }
+            };
+
+            regex_str.push_str(group);
+        }
+
+        regex_str.push_str(DATE_SUFFIX_GUARD);
+
+        Ok(regex_str)
+    }
+
+    #[doc = "인덱스 이름에서 날짜를 찾아 해당 인덱스가 의미하는 기간으로 바꿔주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 대상이 되는 인덱스 명
+    ///
+    /// # Returns
+    /// * Result<IndexPeriod, anyhow::Error>
+    pub fn parse_index_period(&self, index_name: &str) -> Result<IndexPeriod, anyhow::Error> {
+        /* 인덱스 이름 안에 숫자가 여러 번 나올 수 있으므로 실제 날짜로 해석되는 첫번째 부분을 사용한다. */
+        let mut search_start: usize = 0;
+
+        while let Some(captures) = self.regex.captures_at(index_name, search_start) {
+            let matched: Match = match captures.get(0) {
+                Some(matched) => matched,
+                None => break,
+            };
+
+            /* 뒤쪽 구분 문자가 다음 날짜의 앞쪽 구분 문자일 수 있으므로, 매칭된 부분의 첫 글자 다음부터 다시 찾는다. */
+            search_start =
+                matched.start() + matched.as_str().chars().next().map_or(1, char::len_utf8);
+
+            let capture_num = |name: &str| -> Option<u32> {
+                captures.name(name).and_then(|mat| mat.as_str().parse::<u32>().ok())
+            };
+
+            let year: Option<i32> = capture_num("year")
+                .map(|year| year as i32)
+                .filter(|year| (MIN_INDEX_YEAR..=MAX_INDEX_YEAR).contains(year));
+
+            let period: Option<IndexPeriod> = match (self.granularity, year) {
+                (DateGranularity::Hourly, Some(year)) => {
+                    NaiveDate::from_ymd_opt(year, capture_num("month").unwrap_or(0), capture_num("day").unwrap_or(0))
+                        .and_then(|date| date.and_hms_opt(capture_num("hour").unwrap_or(99), 0, 0))
+                        .map(|start| IndexPeriod::new(self.granularity, start, start.date()))
+                }
+                (DateGranularity::Daily, Some(year)) => {
+                    NaiveDate::from_ymd_opt(year, capture_num("month").unwrap_or(0), capture_num("day").unwrap_or(0))
+                        .map(|date| IndexPeriod::new(self.granularity, date.and_time(NaiveTime::MIN), date))
+                }
+                (DateGranularity::Weekly, Some(year)) => {
+                    let week: u32 = capture_num("week").unwrap_or(0);
+
+                    NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+                        .zip(NaiveDate::from_isoywd_opt(year, week, Weekday::Sun))
+                        .map(|(monday, sunday)| {
+                            IndexPeriod::new(self.granularity, monday.and_time(NaiveTime::MIN), sunday)
+                        })
+                }
+                (DateGranularity::Monthly, Some(year)) => {
+                    let month: u32 = capture_num("month").unwrap_or(0);
+
+                    NaiveDate::from_ymd_opt(year, month, 1).and_then(|first_day| {
+                        let next_month: NaiveDate = first_day.checked_add_months(Months::new(1))?;
+                        Some(IndexPeriod::new(
+                            self.granularity,
+                            first_day.and_time(NaiveTime::MIN),
+                            next_month.pred_opt()?,
+                        ))
+                    })
+                }
+                (DateGranularity::Timestamp, _) | (_, None) => None,
+            };
+
+            if let Some(period) = period {
+                return Ok(period);
+            }
+        }
+
+        Err(anyhow!("[Error][IndexDateFormat->parse_index_period] No {:?} date matching '{}' found in index name : {}", self.granularity, self.regex.as_str(), index_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(ymd: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(ymd, "%Y-%m-%d").unwrap()
+    }
+
+    fn period(date_format: &str, index_name: &str) -> IndexPeriod {
+        IndexDateFormat::new(Some(date_format), None)
+            .unwrap()
+            .parse_index_period(index_name)
+            .unwrap()
+    }
+
+    #[test]
+    fn iso_week_spans_year_boundary() {
+        /* 2020 년은 53 주까지 있고, 53 주는 2021-01-03 에 끝난다. */
+        let week_53: IndexPeriod = period("%G.w%V", "logs-2020.w53");
+        assert_eq!(week_53.start().date(), date("2020-12-28"));
+        assert_eq!(*week_53.last_date(), date("2021-01-03"));
+
+        /* 2019 년 1 주는 2018-12-31 (월요일) 에 시작한다. */
+        let week_01: IndexPeriod = period("%G.w%V", "logs-2019.w01");
+        assert_eq!(week_01.start().date(), date("2018-12-31"));
+        assert_eq!(*week_01.last_date(), date("2019-01-06"));
+
+        assert_eq!(
+            *period("weekly", "logs-2021-w01").start(),
+            date("2021-01-04").and_time(NaiveTime::MIN)
+        );
+    }
+
+    #[test]
+    fn iso_week_that_does_not_exist_is_an_error() {
+        let date_format: IndexDateFormat = IndexDateFormat::new(Some("%G.w%V"), None).unwrap();
+
+        /* 2021 년은 52 주까지만 있다. */
+        assert!(date_format.parse_index_period("logs-2021.w53").is_err());
+        assert!(date_format.parse_index_period("logs-2021.w00").is_err());
+    }
+
+    #[test]
+    fn hourly_period() {
+        let hourly: IndexPeriod = period("hourly", "hourly-logs-2025.01.10.23");
+        assert_eq!(*hourly.granularity(), DateGranularity::Hourly);
+        assert_eq!(*hourly.start(), date("2025-01-10").and_hms_opt(23, 0, 0).unwrap());
+        assert_eq!(*hourly.last_date(), date("2025-01-10"));
+        assert_eq!(hourly.period_end_label(), "2025-01-10 23:59");
+
+        assert_eq!(
+            *period("%Y%m%d%H", "logs-2025011000").start(),
+            date("2025-01-10").and_time(NaiveTime::MIN)
+        );
+
+        let date_format: IndexDateFormat = IndexDateFormat::new(Some("hourly"), None).unwrap();
+        assert!(date_format.parse_index_period("hourly-logs-2025.01.10.24").is_err());
+    }
+
+    #[test]
+    fn monthly_period_ends_on_last_day_of_month() {
+        assert_eq!(*period("monthly", "logs-2024.02").last_date(), date("2024-02-29"));
+        assert_eq!(*period("monthly", "logs-2023.02").last_date(), date("2023-02-28"));
+        assert_eq!(*period("%Y-%m", "logs-2024-12").last_date(), date("2024-12-31"));
+    }
+
+    #[test]
+    fn first_valid_date_in_index_name_is_used() {
+        let daily: IndexPeriod = IndexDateFormat::new(None, None)
+            .unwrap()
+            .parse_index_period("app-99999999-2025_01_10")
+            .unwrap();
+
+        assert_eq!(*daily.granularity(), DateGranularity::Daily);
+        assert_eq!(daily.start().date(), date("2025-01-10"));
+    }
+
+    #[test]
+    fn rollover_generation_is_not_read_as_a_date() {
+        let weekly: IndexDateFormat = IndexDateFormat::new(Some("weekly"), None).unwrap();
+
+        /* 000123 은 0001 년 23 주가 아니라 롤오버 세대 번호이다. */
+        assert!(weekly.parse_index_period("yummy-logs-000123").is_err());
+        assert_eq!(
+            weekly.parse_index_period("yummy-logs-2024-w05-000123").unwrap().start().date(),
+            date("2024-01-29")
+        );
+    }
+
+    #[test]
+    fn date_must_not_be_part_of_a_longer_number() {
+        let daily: IndexDateFormat = IndexDateFormat::new(None, None).unwrap();
+
+        assert_eq!(
+            daily.parse_index_period("yummy-logs-2025.01.10-000123").unwrap().start().date(),
+            date("2025-01-10")
+        );
+        assert_eq!(
+            daily.parse_index_period("yummy-logs-000123-20250110").unwrap().start().date(),
+            date("2025-01-10")
+        );
+        assert!(daily.parse_index_period("yummy-logs-202501100001").is_err());
+
+        assert_eq!(
+            *period("%Y%m%d", "logs-20250110-000001").last_date(),
+            date("2025-01-10")
+        );
+        assert!(IndexDateFormat::new(Some("%Y%m%d"), None)
+            .unwrap()
+            .parse_index_period("logs-120250110")
+            .is_err());
+    }
+
+    #[test]
+    fn years_out_of_range_are_not_dates() {
+        let daily: IndexDateFormat = IndexDateFormat::new(None, None).unwrap();
+
+        assert!(daily.parse_index_period("logs-1969.12.31").is_err());
+        assert!(daily.parse_index_period("logs-2201.01.01").is_err());
+        assert_eq!(
+            daily.parse_index_period("logs-1970.01.01").unwrap().start().date(),
+            date("1970-01-01")
+        );
+    }
+
+    #[test]
+    fn invalid_formats_are_rejected() {
+        assert!(IndexDateFormat::new(Some("%Y.%j"), None).is_err());
+        assert!(IndexDateFormat::new(Some("%Y"), None).is_err());
+        assert!(IndexDateFormat::new(Some("daily"), Some(r"(?P<year>\d{4})")).is_err());
+        assert!(IndexDateFormat::new(None, Some(r"(?P<year>\d{4})(?P<week>\d{2})")).is_ok());
+    }
+}
@@ -0,0 +1,160 @@
+use crate::common::*;
+
+use crate::model::archive_manifest::*;
+use crate::model::index_protection::*;
+use crate::model::retention_meta::*;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexAction {
+    Delete,
+    /* 닫거나 쓰기를 막아두고, 유예 기간이 지나면 삭제 */
+    SoftDelete,
+    Keep,
+    Skip,
+}
+
+impl std::fmt::Display for IndexAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let action: &str = match self {
+            IndexAction::Delete => "DELETE",
+            IndexAction::SoftDelete => "SOFT_DELETE",
+            IndexAction::Keep => "KEEP",
+            IndexAction::Skip => "SKIP",
+        };
+
+        f.pad(action)
+    }
+}
+
+impl IndexAction {
+    #[doc = "인덱스를 제거하는 (DELETE, SOFT_DELETE) 결정인지 확인해주는 함수"]
+    pub fn is_removal(&self) -> bool {
+        matches!(self, IndexAction::Delete | IndexAction::SoftDelete)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct IndexDecision {
+    pub index_name: String,
+    pub parsed_date: Option<NaiveDate>,
+    /* 나이 판단에 사용된 기준 시각 (정렬용, 날짜를 알 수 없는 경우 None) */
+    #[serde(default)]
+    pub index_time: Option<NaiveDateTime>,
+    pub cutoff_date: NaiveDate,
+    /* 인덱스 저장 용량 (byte) */
+    #[serde(default)]
+    pub store_size: Option<u64>,
+    /* 인덱스 문서 수 */
+    #[serde(default)]
+    pub docs_count: Option<u64>,
+    pub rule: String,
+    pub action: IndexAction,
+    pub reason: String,
+    /* 삭제 전에 스냅샷을 남길 저장소 명 (규칙의 snapshot_before_delete) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_repository: Option<String>,
+    /* 삭제 전에 아카이브를 남길 위치 (규칙의 archive_before_delete) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_target: Option<ArchiveTarget>,
+    /* SOFT_DELETE 인 경우 soft delete 방식 */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_delete_mode: Option<SoftDeleteMode>,
+    /* 이미 soft delete 된 인덱스인 경우 soft delete 된 시각 */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_deleted_at: Option<DateTime<Utc>>,
+    /* 인덱스를 삭제하지 않는 보호 사유 (alias write 인덱스, ILM, CCR, 보호 라벨) */
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protections: Vec<IndexProtection>,
+}
+
+impl IndexDecision {
+    #[doc = "판단 전의 인덱스 결정을 만들어주는 함수 - 기본값은 KEEP"]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    /// * `rule` - 인덱스가 속한 규칙의 인덱스 패턴
+    /// * `cutoff_date` - 보존기한 데드라인 일자
+    /// * `store_size` - 인덱스 저장 용량 (byte)
+    /// * `docs_count` - 인덱스 문서 수
+    ///
+    /// # Returns
+    /// * IndexDecision
+    pub fn new(
+        index_name: &str,
+        rule: &str,
+        cutoff_date: NaiveDate,
+        store_size: Option<u64>,
+        docs_count: Option<u64>,
+    ) -> Self {
+        Self {
+            index_name: index_name.to_string(),
+            parsed_date: None,
+            index_time: None,
+            cutoff_date,
+            store_size,
+            docs_count,
+            rule: rule.to_string(),
+            action: IndexAction::Keep,
+            reason: String::new(),
+            snapshot_repository: None,
+            archive_target: None,
+            soft_delete_mode: None,
+            soft_deleted_at: None,
+            protections: Vec::new(),
+        }
+    }
+
+    #[doc = "나이 판단에 사용된 기준 시각을 설정해주는 함수"]
+    pub fn with_index_time(mut self, index_time: NaiveDateTime) -> Self {
+        self.parsed_date = Some(index_time.date());
+        self.index_time = Some(index_time);
+        self
+    }
+
+    #[doc = "삭제 전에 스냅샷을 남길 저장소를 설정해주는 함수"]
+    pub fn with_snapshot_repository(mut self, snapshot_repository: Option<&str>) -> Self {
+        self.snapshot_repository = snapshot_repository.map(str::to_string);
+        self
+    }
+
+    #[doc = "삭제 전에 아카이브를 남길 위치를 설정해주는 함수"]
+    pub fn with_archive_target(mut self, archive_target: Option<ArchiveTarget>) -> Self {
+        self.archive_target = archive_target;
+        self
+    }
+
+    #[doc = "인덱스의 보호 사유를 설정해주는 함수 - 제거(DELETE, SOFT_DELETE)로 판단된 인덱스는 SKIP 으로 바꾼다."]
+    /// # Arguments
+    /// * `protections` - 인덱스의 보호 사유 목록
+    pub fn protect(&mut self, protections: Vec<IndexProtection>) {
+        if protections.is_empty() {
+            return;
+        }
+
+        if self.action.is_removal() {
+            let protection_desc: Vec<String> = protections
+                .iter()
+                .map(|protection| protection.to_string())
+                .collect();
+
+            self.reason = format!(
+                "protected ({}) - refused to {}: {}",
+                protection_desc.join(", "),
+                self.action,
+                self.reason
+            );
+            self.action = IndexAction::Skip;
+            self.soft_delete_mode = None;
+        }
+
+        self.protections = protections;
+    }
+
+    #[doc = "판단 결과와 사유를 설정해주는 함수"]
+    pub fn with_action(mut self, action: IndexAction, reason: String) -> Self {
+        self.action = action;
+        self.reason = reason;
+        self
+    }
+}
@@ -0,0 +1,164 @@
+use crate::common::*;
+
+#[doc = "인덱스 나이에 따라 적용할 단계 (예: hot -> warm) - 삭제는 규칙의 duration_days 로 판단한다."]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct IndexPhase {
+    /* 인덱스 나이가 이 일수를 넘으면 단계가 적용된다. */
+    pub after_days: u32,
+    /* 레플리카 수 (index.number_of_replicas) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replicas: Option<u32>,
+    /* 샤드별 최대 세그먼트 수 - 이보다 세그먼트가 많은 샤드가 있으면 forcemerge 한다. */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forcemerge_segments: Option<u32>,
+    /* 노드 속성 (index.routing.allocation.require.<속성>) - 예: { data = "warm" } */
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub require_attributes: HashMap<String, String>,
+}
+
+#[doc = "인덱스가 도달한 단계들을 합쳐서, 인덱스가 가져야 할 상태"]
+#[derive(Debug, Getters, Clone, Default)]
+#[getset(get = "pub")]
+pub struct PhaseTarget {
+    /* 도달한 마지막 단계의 after_days */
+    pub after_days: u32,
+    pub replicas: Option<u32>,
+    pub forcemerge_segments: Option<u32>,
+    pub require_attributes: HashMap<String, String>,
+}
+
+impl IndexPhase {
+    #[doc = "단계에 적용할 동작이 하나라도 있는지 확인해주는 함수"]
+    pub fn has_action(&self) -> bool {
+        self.replicas.is_some()
+            || self.forcemerge_segments.is_some()
+            || !self.require_attributes.is_empty()
+    }
+}
+
+impl PhaseTarget {
+    #[doc = "인덱스 시각 기준으로 도달한 단계들을 순서대로 합쳐주는 함수 (뒤의 단계가 앞의 단계를 덮어쓴다.)"]
+    /// # Arguments
+    /// * `phases` - 규칙의 단계 목록 (after_days 오름차순)
+    /// * `index_time` - 나이 판단에 사용되는 인덱스 시각
+    /// * `cur_utc_datetime` - 기준이 되는 현재 UTC 시각
+    ///
+    /// # Returns
+    /// * Option<PhaseTarget> - 도달한 단계가 없으면 None
+    pub fn from_phases(
+        phases: &[IndexPhase],
+        index_time: NaiveDateTime,
+        cur_utc_datetime: NaiveDateTime,
+    ) -> Option<Self> {
+        let mut phase_target: Option<PhaseTarget> = None;
+
+        for phase in phases {
+            if index_time > cur_utc_datetime - chrono::Duration::days(phase.after_days as i64) {
+                break;
+            }
+
+            let target: &mut PhaseTarget = phase_target.get_or_insert_with(PhaseTarget::default);
+
+            target.after_days = phase.after_days;
+
+            if phase.replicas.is_some() {
+                target.replicas = phase.replicas;
+            }
+
+            if phase.forcemerge_segments.is_some() {
+                target.forcemerge_segments = phase.forcemerge_segments;
+            }
+
+            target.require_attributes.extend(
+                phase
+                    .require_attributes
+                    .iter()
+                    .map(|(attribute, value)| (attribute.clone(), value.clone())),
+            );
+        }
+
+        phase_target
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(datetime: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn phases(phases_toml: &str) -> Vec<IndexPhase> {
+        #[derive(Deserialize)]
+        struct Phases {
+            phases: Vec<IndexPhase>,
+        }
+
+        toml::from_str::<Phases>(phases_toml).unwrap().phases
+    }
+
+    fn warm_and_cold() -> Vec<IndexPhase> {
+        phases(concat!(
+            "[[phases]]\nafter_days = 3\nreplicas = 1\nforcemerge_segments = 5\n",
+            "require_attributes = { data = \"warm\", zone = \"a\" }\n",
+            "[[phases]]\nafter_days = 10\nreplicas = 0\n",
+            "require_attributes = { data = \"cold\", rack = \"r1\" }\n",
+        ))
+    }
+
+    #[test]
+    fn index_younger_than_first_phase_has_no_target() {
+        let phase_target: Option<PhaseTarget> = PhaseTarget::from_phases(
+            &warm_and_cold(),
+            utc("2025-01-08 00:00:01"),
+            utc("2025-01-11 00:00:00"),
+        );
+
+        assert!(phase_target.is_none());
+    }
+
+    #[test]
+    fn phase_is_reached_at_its_after_days() {
+        let phase_target: PhaseTarget = PhaseTarget::from_phases(
+            &warm_and_cold(),
+            utc("2025-01-08 00:00:00"),
+            utc("2025-01-11 00:00:00"),
+        )
+        .unwrap();
+
+        assert_eq!(phase_target.after_days, 3);
+        assert_eq!(phase_target.replicas, Some(1));
+        assert_eq!(phase_target.forcemerge_segments, Some(5));
+        assert_eq!(phase_target.require_attributes.len(), 2);
+    }
+
+    #[test]
+    fn later_phase_overrides_earlier_phase() {
+        let phase_target: PhaseTarget = PhaseTarget::from_phases(
+            &warm_and_cold(),
+            utc("2025-01-01 00:00:00"),
+            utc("2025-01-11 00:00:00"),
+        )
+        .unwrap();
+
+        assert_eq!(phase_target.after_days, 10);
+        assert_eq!(phase_target.replicas, Some(0));
+        /* cold 단계에 forcemerge 가 없으면 warm 단계의 값이 남는다. */
+        assert_eq!(phase_target.forcemerge_segments, Some(5));
+        /* 노드 속성은 단계마다 쌓이고, 같은 속성은 뒤의 단계가 덮어쓴다. */
+        assert_eq!(phase_target.require_attributes["data"], "cold");
+        assert_eq!(phase_target.require_attributes["zone"], "a");
+        assert_eq!(phase_target.require_attributes["rack"], "r1");
+    }
+
+    #[test]
+    fn phase_without_action_is_detected() {
+        let phases: Vec<IndexPhase> =
+            phases("[[phases]]\nafter_days = 3\n[[phases]]\nafter_days = 5\nreplicas = 0\n");
+
+        assert!(!phases[0].has_action());
+        assert!(phases[1].has_action());
+    }
+}
@@ -0,0 +1,33 @@
+use crate::common::*;
+
+use crate::model::legal_hold::*;
+
+#[doc = "보존기한이 지나더라도 인덱스를 삭제하지 않는 사유"]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "kind", content = "name")]
+pub enum IndexProtection {
+    /* alias 의 write 인덱스 (is_write_index = true, 또는 alias 가 가리키는 유일한 인덱스) */
+    WriteAlias(String),
+    /* ILM 정책이 관리하는 인덱스 (index.lifecycle.name) */
+    IlmManaged(String),
+    /* CCR 팔로워 인덱스 (index.xpack.ccr.following_index) */
+    CcrFollower,
+    /* 규칙의 protect_labels 에 해당하는 라벨이 붙은 인덱스 (_meta.yummy_retention.labels) */
+    Label(String),
+    /* 리갈 홀드가 걸린 인덱스 (리갈 홀드 파일 또는 _meta.yummy_retention.legal_hold) */
+    LegalHold(LegalHold),
+}
+
+impl std::fmt::Display for IndexProtection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let protection: String = match self {
+            IndexProtection::WriteAlias(alias) => format!("write index of alias '{}'", alias),
+            IndexProtection::IlmManaged(policy) => format!("managed by ILM policy '{}'", policy),
+            IndexProtection::CcrFollower => String::from("CCR follower index"),
+            IndexProtection::Label(label) => format!("protect label '{}'", label),
+            IndexProtection::LegalHold(legal_hold) => legal_hold.to_string(),
+        };
+
+        f.pad(&protection)
+    }
+}
@@ -0,0 +1,213 @@
+use crate::common::*;
+
+use crate::model::target_index::*;
+
+#[doc = "리갈 홀드 - 홀드가 걸린 인덱스는 보존기한과 관계없이 만료일까지 삭제하지 않는다."]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, PartialEq, Eq)]
+#[getset(get = "pub")]
+pub struct LegalHold {
+    /* 홀드 사유 (예: 조사 요청 번호) */
+    pub reason: String,
+    /* 홀드를 건 담당자 */
+    pub owner: String,
+    /* 이 일자까지 홀드가 유지된다. (None 인 경우 홀드를 지울 때까지 유지) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+}
+
+#[doc = "리갈 홀드 파일의 홀드 한 건 (index_list.toml 과 같은 인덱스 패턴 사용)"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct LegalHoldEntry {
+    /* 홀드를 걸 인덱스 명 또는 패턴 (콤마 구분, '*' 와일드카드) */
+    pub index: String,
+    #[serde(flatten)]
+    pub hold: LegalHold,
+}
+
+#[doc = "리갈 홀드 파일 ([[hold]] 목록)"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, Default)]
+#[getset(get = "pub")]
+pub struct LegalHoldList {
+    #[serde(default)]
+    pub hold: Vec<LegalHoldEntry>,
+}
+
+impl std::fmt::Display for LegalHold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let expires: String = match self.expires {
+            Some(expires) => format!("until {}", expires),
+            None => String::from("until released"),
+        };
+
+        f.pad(&format!(
+            "legal hold by {} {}: {}",
+            self.owner, expires, self.reason
+        ))
+    }
+}
+
+impl LegalHold {
+    #[doc = "기준 일자에 홀드가 유효한지 확인해주는 함수 (만료일 당일까지 유효)"]
+    pub fn is_active(&self, today: NaiveDate) -> bool {
+        self.expires.is_none_or(|expires| today <= expires)
+    }
+}
+
+impl LegalHoldEntry {
+    #[doc = "인덱스 명이 홀드의 인덱스 패턴에 해당하는지 확인해주는 함수"]
+    pub fn is_match_index(&self, index_name: &str) -> bool {
+        self.index
+            .split(',')
+            .map(|pattern| pattern.trim())
+            .filter(|pattern| !pattern.is_empty())
+            .any(|pattern| is_wildcard_match(pattern, index_name))
+    }
+}
+
+impl LegalHoldList {
+    #[doc = "리갈 홀드 파일의 내용을 검증해주는 함수"]
+    /// # Returns
+    /// * Result<(), anyhow::Error> - 문제가 있는 경우 모든 문제를 모아서 반환한다.
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        let mut problems: Vec<String> = Vec::new();
+
+        for entry in &self.hold {
+            if entry.index.trim().is_empty() {
+                problems.push(String::from("index must not be empty"));
+                continue;
+            }
+
+            if entry.hold.reason.trim().is_empty() {
+                problems.push(format!("{}: reason must not be empty", entry.index));
+            }
+
+            if entry.hold.owner.trim().is_empty() {
+                problems.push(format!("{}: owner must not be empty", entry.index));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "[Error][LegalHoldList->validate] Invalid legal hold config:\n - {}",
+                problems.join("\n - ")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::model::index_decision::*;
+    use crate::model::index_protection::*;
+    use crate::model::retention_meta::*;
+
+    fn date(ymd: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(ymd, "%Y-%m-%d").unwrap()
+    }
+
+    fn holds(holds_toml: &str) -> LegalHoldList {
+        toml::from_str(holds_toml).unwrap()
+    }
+
+    fn entry(index: &str) -> LegalHoldEntry {
+        LegalHoldEntry {
+            index: index.to_string(),
+            hold: LegalHold {
+                reason: String::from("CASE-1"),
+                owner: String::from("legal"),
+                expires: None,
+            },
+        }
+    }
+
+    #[test]
+    fn hold_is_active_through_its_expiry_date() {
+        let legal_hold: LegalHold = LegalHold {
+            reason: String::from("CASE-1"),
+            owner: String::from("legal"),
+            expires: Some(date("2025-01-31")),
+        };
+
+        assert!(legal_hold.is_active(date("2025-01-01")));
+        assert!(legal_hold.is_active(date("2025-01-31")));
+        assert!(!legal_hold.is_active(date("2025-02-01")));
+    }
+
+    #[test]
+    fn hold_without_expiry_never_expires() {
+        let legal_hold: LegalHold = entry("logs-*").hold;
+
+        assert!(legal_hold.is_active(date("1970-01-01")));
+        assert!(legal_hold.is_active(date("2200-12-31")));
+        assert_eq!(legal_hold.to_string(), "legal hold by legal until released: CASE-1");
+    }
+
+    #[test]
+    fn comma_separated_and_wildcard_patterns_match() {
+        let legal_hold_entry: LegalHoldEntry = entry("audit-2025.01.10, logs-2025.01.*,,");
+
+        assert!(legal_hold_entry.is_match_index("audit-2025.01.10"));
+        assert!(legal_hold_entry.is_match_index("logs-2025.01.01"));
+        assert!(legal_hold_entry.is_match_index("logs-2025.01.31"));
+        assert!(!legal_hold_entry.is_match_index("logs-2025.02.01"));
+        assert!(!legal_hold_entry.is_match_index("audit-2025.01.11"));
+        assert!(!legal_hold_entry.is_match_index(""));
+    }
+
+    #[test]
+    fn empty_reason_or_owner_is_rejected() {
+        let legal_holds: LegalHoldList = holds(concat!(
+            "[[hold]]\nindex = \"logs-1\"\nreason = \" \"\nowner = \"legal\"\n",
+            "[[hold]]\nindex = \"logs-2\"\nreason = \"CASE-2\"\nowner = \"\"\n",
+            "[[hold]]\nindex = \"logs-3\"\nreason = \"CASE-3\"\nowner = \"legal\"\n",
+        ));
+
+        let err: String = legal_holds.validate().unwrap_err().to_string();
+
+        assert!(err.contains("logs-1: reason must not be empty"));
+        assert!(err.contains("logs-2: owner must not be empty"));
+        assert!(!err.contains("logs-3"));
+        assert!(holds("[[hold]]\nindex = \"logs-3\"\nreason = \"CASE-3\"\nowner = \"legal\"")
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn meta_hold_protects_index_that_age_retention_would_delete() {
+        let retention_metas: HashMap<String, IndexRetentionMeta> =
+            parse_index_retention_metas(&json!({
+                "logs-2024.01.01": { "mappings": { "_meta": { "yummy_retention": {
+                    "legal_hold": { "reason": "CASE-1", "owner": "legal", "expires": "2025-12-31" }
+                } } } }
+            }));
+        let legal_hold: LegalHold = retention_metas["logs-2024.01.01"]
+            .legal_hold()
+            .clone()
+            .unwrap();
+
+        let mut decision: IndexDecision = IndexDecision::new(
+            "logs-2024.01.01",
+            "logs-*",
+            date("2024-12-01"),
+            Some(1024),
+            None,
+        )
+        .with_action(
+            IndexAction::Delete,
+            String::from("Daily index period ending 2024-01-01 is on or before the cutoff date"),
+        );
+
+        assert!(legal_hold.is_active(date("2025-01-10")));
+        decision.protect(vec![IndexProtection::LegalHold(legal_hold)]);
+
+        assert_eq!(decision.action, IndexAction::Skip);
+        assert!(decision
+            .reason()
+            .starts_with("protected (legal hold by legal until 2025-12-31: CASE-1)"));
+    }
+}
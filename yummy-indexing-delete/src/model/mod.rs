@@ -1 +1,17 @@
+pub mod archive_manifest;
+pub mod audit_entry;
+pub mod cat_index;
+pub mod data_stream;
+pub mod deletion_plan;
+pub mod disk_allocation;
+pub mod emergency_config;
+pub mod es_node_stats;
+pub mod index_date_format;
+pub mod index_decision;
+pub mod index_phase;
+pub mod index_protection;
+pub mod legal_hold;
+pub mod retention_meta;
+pub mod safety_config;
+pub mod schedule_config;
 pub mod target_index;
@@ -0,0 +1,100 @@
+use crate::common::*;
+
+use crate::model::legal_hold::*;
+
+/* 인덱스 매핑의 _meta 중에서 이 프로그램이 사용하는 키 */
+pub const RETENTION_META_KEY: &str = "yummy_retention";
+
+#[doc = "인덱스 매핑의 _meta.yummy_retention - 보존기한 규칙이 인덱스별로 참고하는 정보"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, Default)]
+#[getset(get = "pub")]
+pub struct IndexRetentionMeta {
+    /* 스냅샷에서 복원된 인덱스인 경우 복원 정보 */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restored: Option<RestoredMeta>,
+    /* soft delete 된 인덱스인 경우 soft delete 정보 */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_deleted: Option<SoftDeletedMeta>,
+    /* 운영자가 붙인 라벨 - 규칙의 protect_labels 에 해당하는 라벨이 있으면 삭제하지 않는다. */
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /* 인덱스에 직접 건 리갈 홀드 */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub legal_hold: Option<LegalHold>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SoftDeleteMode {
+    /* 인덱스를 닫는다. (_open 으로 되돌릴 수 있음) */
+    #[default]
+    Close,
+    /* index.blocks.write 로 쓰기만 막는다. (검색은 계속 가능) */
+    WriteBlock,
+}
+
+#[doc = "soft delete 된 인덱스의 표시 - 유예 기간이 지나야 실제로 삭제된다."]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct SoftDeletedMeta {
+    pub soft_deleted_at: DateTime<Utc>,
+    pub mode: SoftDeleteMode,
+    pub run_id: String,
+}
+
+#[doc = "복원된 인덱스의 복원 정보 - 보존기한 규칙은 원래 날짜 대신 이 정보로 판단한다."]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, new)]
+#[getset(get = "pub")]
+pub struct RestoredMeta {
+    pub restored_at: DateTime<Utc>,
+    pub source_index: String,
+    /* 복원에 사용된 스냅샷 ("<저장소>/<스냅샷>") */
+    pub source: String,
+    /* 복원 후 보존할 일수 - None 인 경우 보존기한 규칙이 삭제하지 않는다. */
+    #[serde(default)]
+    pub ttl_days: Option<u32>,
+}
+
+impl RestoredMeta {
+    #[doc = "복원된 인덱스의 만료 시각을 반환해주는 함수 (ttl_days 가 없으면 None)"]
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.ttl_days
+            .map(|ttl_days| self.restored_at + chrono::Duration::days(ttl_days as i64))
+    }
+}
+
+#[doc = "_mapping 응답(filter_path=*.mappings._meta)에서 인덱스별 보존 메타데이터를 뽑아주는 함수"]
+/// # Arguments
+/// * `res` - { "<index>": { "mappings": { "_meta": { "yummy_retention": { .. } } } } }
+///
+/// # Returns
+/// * HashMap<String, IndexRetentionMeta> - 메타데이터가 없는 인덱스는 포함되지 않는다.
+pub fn parse_index_retention_metas(res: &Value) -> HashMap<String, IndexRetentionMeta> {
+    let mut retention_metas: HashMap<String, IndexRetentionMeta> = HashMap::new();
+
+    let indices = match res.as_object() {
+        Some(indices) => indices,
+        None => return retention_metas,
+    };
+
+    for (index_name, index_mapping) in indices {
+        let meta: &Value = match index_mapping.pointer(&format!("/mappings/_meta/{}", RETENTION_META_KEY)) {
+            Some(meta) => meta,
+            None => continue,
+        };
+
+        match serde_json::from_value::<IndexRetentionMeta>(meta.clone()) {
+            Ok(retention_meta) => {
+                retention_metas.insert(index_name.clone(), retention_meta);
+            }
+            Err(e) => {
+                error!(
+                    "[Error][parse_index_retention_metas] Invalid _meta.{} in {}: {:?}",
+                    RETENTION_META_KEY, index_name, e
+                );
+            }
+        }
+    }
+
+    retention_metas
+}
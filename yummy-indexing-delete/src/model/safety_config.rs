@@ -0,0 +1,253 @@
+use crate::common::*;
+
+use crate::model::index_decision::*;
+
+use crate::utils_module::size_utils::*;
+
+#[doc = "한 번의 실행에서 지울 수 있는 양의 상한 (index_list.toml 의 [safety]) - 설정 실수나 시계 오류로 전체 로그가 지워지는 것을 막는다."]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone, Default)]
+#[getset(get = "pub")]
+pub struct SafetyConfig {
+    /* 한 번의 실행에서 제거(soft delete 포함)할 수 있는 최대 인덱스 수 */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_deletions_per_run: Option<usize>,
+    /* 한 번의 실행에서 제거할 수 있는 최대 용량 (예: "500gb") */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes_per_run: Option<String>,
+    /* 규칙(패턴) 하나에서 한 번에 제거할 수 있는 인덱스 비율 (0.0 ~ 1.0) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_delete_fraction: Option<f64>,
+}
+
+impl SafetyConfig {
+    #[doc = "안전 상한이 하나라도 설정되어 있는지 확인해주는 함수"]
+    pub fn is_enabled(&self) -> bool {
+        self.max_deletions_per_run.is_some()
+            || self.max_bytes_per_run.is_some()
+            || self.max_delete_fraction.is_some()
+    }
+
+    #[doc = "실행당 최대 제거 용량을 byte 로 반환해주는 함수"]
+    pub fn max_bytes_per_run_bytes(&self) -> Result<Option<u64>, anyhow::Error> {
+        self.max_bytes_per_run
+            .as_deref()
+            .map(parse_byte_size)
+            .transpose()
+    }
+
+    #[doc = "안전 상한 설정을 검증해주는 함수"]
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.max_deletions_per_run == Some(0) {
+            return Err(anyhow!("[Error][SafetyConfig->validate] max_deletions_per_run must be greater than 0."));
+        }
+
+        if let Err(e) = self.max_bytes_per_run_bytes() {
+            return Err(anyhow!("[Error][SafetyConfig->validate] max_bytes_per_run: {}", e));
+        }
+
+        if let Some(max_delete_fraction) = self.max_delete_fraction {
+            if !(max_delete_fraction > 0.0 && max_delete_fraction <= 1.0) {
+                return Err(anyhow!("[Error][SafetyConfig->validate] max_delete_fraction ({}) must be greater than 0 and at most 1.", max_delete_fraction));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[doc = "삭제 계획이 안전 상한을 넘는지 확인해주는 함수"]
+    /// # Arguments
+    /// * `index_plan` - 확인할 삭제 계획 (비율 계산을 위해 KEEP/SKIP 을 포함한 규칙 전체의 결정)
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error> - 상한을 넘은 경우 넘은 항목을 모두 모아서 반환한다.
+    pub fn check(&self, index_plan: &[IndexDecision]) -> Result<(), anyhow::Error> {
+        let mut violations: Vec<String> = Vec::new();
+
+        let removal_decisions: Vec<&IndexDecision> = index_plan
+            .iter()
+            .filter(|decision| decision.action.is_removal())
+            .collect();
+
+        if let Some(max_deletions_per_run) = self.max_deletions_per_run {
+            if removal_decisions.len() > max_deletions_per_run {
+                violations.push(format!(
+                    "{} indices to delete > max_deletions_per_run ({})",
+                    removal_decisions.len(),
+                    max_deletions_per_run
+                ));
+            }
+        }
+
+        if let Some(max_bytes_per_run) = self.max_bytes_per_run_bytes()? {
+            let removal_bytes: u64 = removal_decisions
+                .iter()
+                .filter_map(|decision| decision.store_size)
+                .sum();
+
+            if removal_bytes > max_bytes_per_run {
+                violations.push(format!(
+                    "{} to delete > max_bytes_per_run ({})",
+                    format_byte_size(removal_bytes),
+                    format_byte_size(max_bytes_per_run)
+                ));
+            }
+        }
+
+        if let Some(max_delete_fraction) = self.max_delete_fraction {
+            /* 규칙명 -> (제거할 인덱스 수, 전체 인덱스 수) */
+            let mut rule_counts: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+
+            for decision in index_plan {
+                let rule_count: &mut (usize, usize) =
+                    rule_counts.entry(decision.rule().as_str()).or_default();
+
+                if decision.action.is_removal() {
+                    rule_count.0 += 1;
+                }
+
+                rule_count.1 += 1;
+            }
+
+            for (rule, (removal_cnt, total_cnt)) in rule_counts {
+                let fraction: f64 = removal_cnt as f64 / total_cnt as f64;
+
+                if fraction > max_delete_fraction {
+                    violations.push(format!(
+                        "{}: {} of {} indices to delete ({:.1}%) > max_delete_fraction ({:.1}%)",
+                        rule,
+                        removal_cnt,
+                        total_cnt,
+                        fraction * 100.0,
+                        max_delete_fraction * 100.0
+                    ));
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "[Error][SafetyConfig->check] Safety cap exceeded. Aborted before deleting any index:\n - {}\n(check index_list.toml and the system clock, or pass --override-safety-caps if this is intended)",
+                violations.join("\n - ")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn safety(safety_toml: &str) -> SafetyConfig {
+        toml::from_str(safety_toml).unwrap()
+    }
+
+    fn plan(rule: &str, removal_cnt: usize, keep_cnt: usize) -> Vec<IndexDecision> {
+        (0..removal_cnt + keep_cnt)
+            .map(|idx| {
+                let mut decision: IndexDecision = IndexDecision::new(
+                    &format!("{}-{}", rule, idx),
+                    rule,
+                    NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                    Some(1024 * 1024 * 1024),
+                    None,
+                );
+
+                if idx < removal_cnt {
+                    decision.action = IndexAction::Delete;
+                }
+
+                decision
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_plan_passes_every_cap() {
+        let safety_config: SafetyConfig = safety(
+            "max_deletions_per_run = 1\nmax_bytes_per_run = \"1b\"\nmax_delete_fraction = 0.01",
+        );
+
+        assert!(safety_config.check(&[]).is_ok());
+        assert!(safety_config.check(&plan("logs", 0, 5)).is_ok());
+    }
+
+    #[test]
+    fn fraction_equal_to_cap_is_allowed() {
+        let safety_config: SafetyConfig = safety("max_delete_fraction = 0.3");
+
+        assert!(safety_config.check(&plan("logs", 3, 7)).is_ok());
+        assert!(safety_config.check(&plan("logs", 30, 70)).is_ok());
+        assert!(safety_config.check(&plan("logs", 4, 6)).is_err());
+        assert!(safety_config.check(&plan("logs", 31, 69)).is_err());
+    }
+
+    #[test]
+    fn repeating_fraction_is_compared_without_rounding() {
+        let err: String = safety("max_delete_fraction = 0.33")
+            .check(&plan("logs", 1, 2))
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("(33.3%) > max_delete_fraction (33.0%)"));
+        assert!(safety("max_delete_fraction = 0.34").check(&plan("logs", 1, 2)).is_ok());
+        assert!(safety("max_delete_fraction = 0.5").check(&plan("logs", 1, 1)).is_ok());
+        assert!(safety("max_delete_fraction = 1.0").check(&plan("logs", 4, 0)).is_ok());
+    }
+
+    #[test]
+    fn fraction_is_checked_per_rule() {
+        let safety_config: SafetyConfig = safety("max_delete_fraction = 0.5");
+        let mut index_plan: Vec<IndexDecision> = plan("logs", 1, 9);
+        index_plan.extend(plan("metrics", 2, 1));
+
+        let err: String = safety_config.check(&index_plan).unwrap_err().to_string();
+
+        assert!(err.contains("metrics: 2 of 3 indices to delete"));
+        assert!(!err.contains("logs:"));
+    }
+
+    #[test]
+    fn soft_delete_counts_towards_deletions() {
+        let safety_config: SafetyConfig = safety("max_deletions_per_run = 2");
+        let mut index_plan: Vec<IndexDecision> = plan("logs", 2, 1);
+        index_plan[2].action = IndexAction::SoftDelete;
+
+        let err: String = safety_config.check(&index_plan).unwrap_err().to_string();
+
+        assert!(err.contains("3 indices to delete > max_deletions_per_run (2)"));
+        assert!(safety_config.check(&plan("logs", 2, 10)).is_ok());
+    }
+
+    #[test]
+    fn bytes_cap_sums_removed_indices_only() {
+        let safety_config: SafetyConfig = safety("max_bytes_per_run = \"2gb\"");
+
+        assert!(safety_config.check(&plan("logs", 2, 10)).is_ok());
+        assert!(safety_config.check(&plan("logs", 3, 0)).is_err());
+    }
+
+    #[test]
+    fn every_exceeded_cap_is_reported() {
+        let safety_config: SafetyConfig = safety(
+            "max_deletions_per_run = 1\nmax_bytes_per_run = \"1gb\"\nmax_delete_fraction = 0.5",
+        );
+
+        let err: String = safety_config.check(&plan("logs", 3, 1)).unwrap_err().to_string();
+
+        assert!(err.contains("max_deletions_per_run"));
+        assert!(err.contains("max_bytes_per_run"));
+        assert!(err.contains("max_delete_fraction"));
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected() {
+        assert!(safety("max_deletions_per_run = 0").validate().is_err());
+        assert!(safety("max_bytes_per_run = \"lots\"").validate().is_err());
+        assert!(safety("max_delete_fraction = 0.0").validate().is_err());
+        assert!(safety("max_delete_fraction = 1.5").validate().is_err());
+        assert!(safety("max_delete_fraction = 1.0").validate().is_ok());
+        assert!(safety("").validate().is_ok());
+    }
+}
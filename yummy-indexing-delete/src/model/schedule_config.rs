@@ -0,0 +1,76 @@
+use crate::common::*;
+
+#[doc = "데몬 모드 스케줄 설정 (index_list.toml 의 [schedule])"]
+#[derive(Debug, Deserialize, Serialize, Getters, Clone)]
+#[getset(get = "pub")]
+pub struct ScheduleConfig {
+    /* 규칙에 schedule 이 없는 경우 사용할 cron 표현식 */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron: Option<String>,
+    /* cron 표현식을 해석할 타임존 (예: "Asia/Seoul") - 미지정 시 UTC */
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+}
+
+fn default_timezone() -> String {
+    String::from("UTC")
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            cron: None,
+            timezone: default_timezone(),
+        }
+    }
+}
+
+impl ScheduleConfig {
+    #[doc = "스케줄 설정을 검증해주는 함수"]
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        parse_timezone(&self.timezone)?;
+
+        if let Some(cron) = &self.cron {
+            parse_cron_schedule(cron)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[doc = "cron 표현식을 파싱해주는 함수 - 5개 필드(분 시 일 월 요일)의 표준 cron 표현식은 0초로 보정해준다."]
+/// # Arguments
+/// * `cron_expr` - cron 표현식 (예: "0 3 * * *", "0 0 3 * * *")
+///
+/// # Returns
+/// * Result<Schedule, anyhow::Error>
+pub fn parse_cron_schedule(cron_expr: &str) -> Result<Schedule, anyhow::Error> {
+    let cron_expr: &str = cron_expr.trim();
+
+    let normalized_expr: String = if cron_expr.split_whitespace().count() == 5 {
+        format!("0 {}", cron_expr)
+    } else {
+        cron_expr.to_string()
+    };
+
+    Schedule::from_str(&normalized_expr).map_err(|e| {
+        anyhow!(
+            "[Error][parse_cron_schedule] Invalid cron expression '{}': {}",
+            cron_expr,
+            e
+        )
+    })
+}
+
+#[doc = "타임존 이름을 파싱해주는 함수"]
+/// # Arguments
+/// * `timezone` - IANA 타임존 이름 (예: "Asia/Seoul", "UTC")
+///
+/// # Returns
+/// * Result<Tz, anyhow::Error>
+pub fn parse_timezone(timezone: &str) -> Result<Tz, anyhow::Error> {
+    timezone
+        .trim()
+        .parse::<Tz>()
+        .map_err(|e| anyhow!("[Error][parse_timezone] Invalid timezone '{}': {}", timezone, e))
+}
@@ -1,14 +1,419 @@
 use crate::common::*;
 
+use crate::model::archive_manifest::*;
+use crate::model::emergency_config::*;
+use crate::model::index_date_format::*;
+use crate::model::index_phase::*;
+use crate::model::retention_meta::*;
+use crate::model::safety_config::*;
+use crate::model::schedule_config::*;
+
+use crate::utils_module::size_utils::*;
+
 #[derive(Debug, Deserialize, Serialize, Getters, Clone)]
 #[getset(get = "pub")]
 pub struct TargetIndex {
     pub index_name: String,
+    /* index_name 을 데이터 스트림 명으로 보고, write 인덱스를 제외한 backing 인덱스를 세대 생성일자로 삭제한다. */
+    #[serde(default)]
+    pub data_stream: bool,
     pub duration_days: u32,
+    /* 프리셋(daily, monthly, weekly, hourly) 또는 strftime 양식 (예: "%Y.%m.%d") - 미지정 시 daily */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    /* year, month, day, week, hour 이름의 그룹을 가진 정규식 (date_format 과 함께 사용 불가) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_regex: Option<String>,
+    /* 인덱스의 나이를 판단할 기준 - 미지정 시 name */
+    #[serde(default)]
+    pub age_source: AgeSource,
+    /* 나이와 관계없이 항상 보존할 최신 인덱스 개수 */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_keep: Option<usize>,
+    /* 보존할 최대 인덱스 개수 - 초과분은 오래된 인덱스부터 삭제 */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_keep: Option<usize>,
+    /* 규칙에 속한 인덱스 전체 용량 상한 (예: "200gb") - 초과 시 오래된 인덱스부터 삭제 */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_size: Option<String>,
+    /* 긴급 정리 시 중요도 - 낮은 규칙의 인덱스부터 삭제된다. */
+    #[serde(default)]
+    pub priority: u32,
+    /* 긴급 정리 시에도 이 일수보다 최근 인덱스는 삭제하지 않는다. */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub never_below_days: Option<u32>,
+    /* 데몬 모드에서 이 규칙을 실행할 cron 표현식 - 미지정 시 [schedule] 의 cron */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
+    /* 삭제 전에 인덱스를 스냅샷으로 남길지 여부 - 스냅샷에 실패하면 삭제하지 않는다. */
+    #[serde(default)]
+    pub snapshot_before_delete: bool,
+    /* 스냅샷을 남길 Elasticsearch 스냅샷 저장소 명 (snapshot_before_delete = true 인 경우 필수) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_repository: Option<String>,
+    /* 삭제 전에 인덱스의 모든 문서를 압축된 NDJSON 파일로 내보낼지 여부 - 아카이브 문서 수가 맞지 않으면 삭제하지 않는다. */
+    #[serde(default)]
+    pub archive_before_delete: bool,
+    /* 아카이브를 남길 로컬 디렉토리 (archive_before_delete = true 인 경우 필수) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_dir: Option<String>,
+    /* 아카이브 압축 방식 (gzip, zstd) - 미지정 시 gzip */
+    #[serde(default)]
+    pub archive_compression: ArchiveCompression,
+    /* 보존기한이 지난 인덱스를 바로 삭제하지 않고 닫거나 쓰기를 막아둔 뒤, 이 일수가 지나면 삭제한다. */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_delete_days: Option<u32>,
+    /* soft delete 방식 (close, write_block) - 미지정 시 close */
+    #[serde(default)]
+    pub soft_delete_mode: SoftDeleteMode,
+    /* 나이에 따라 순서대로 적용할 단계 (레플리카 수, forcemerge, 노드 이동) */
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<IndexPhase>,
+    /* 인덱스 _meta.yummy_retention.labels 에 이 중 하나라도 있으면 보존기한과 관계없이 삭제하지 않는다. */
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protect_labels: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AgeSource {
+    /* 인덱스 이름의 날짜 */
+    #[default]
+    Name,
+    /* 인덱스 생성일자 (_cat/indices 의 creation.date) */
+    CreationDate,
+    /* 인덱스 이름의 날짜, 이름에 날짜가 없으면 인덱스 생성일자 */
+    NameThenCreation,
+    /* 롤오버된 시각 (아직 롤오버되지 않은 write 인덱스는 보존) */
+    RolloverDate,
 }
 
 #[derive(Debug, Deserialize, Serialize, Getters, Clone)]
 #[getset(get = "pub")]
 pub struct TargetIndexList {
     pub index: Vec<TargetIndex>,
+    #[serde(default)]
+    pub emergency: Option<EmergencyConfig>,
+    #[serde(default)]
+    pub schedule: ScheduleConfig,
+    #[serde(default)]
+    pub safety: SafetyConfig,
+}
+
+impl TargetIndex {
+    #[doc = "규칙의 전체 용량 상한을 byte 로 반환해주는 함수"]
+    pub fn max_total_size_bytes(&self) -> Result<Option<u64>, anyhow::Error> {
+        self.max_total_size
+            .as_deref()
+            .map(parse_byte_size)
+            .transpose()
+    }
+
+    #[doc = "삭제 전에 스냅샷을 남길 저장소 명을 반환해주는 함수 (snapshot_before_delete = false 인 경우 None)"]
+    pub fn snapshot_target_repository(&self) -> Option<&str> {
+        if self.snapshot_before_delete {
+            self.snapshot_repository.as_deref()
+        } else {
+            None
+        }
+    }
+
+    #[doc = "삭제 전에 아카이브를 남길 위치를 반환해주는 함수 (archive_before_delete = false 인 경우 None)"]
+    pub fn archive_target(&self) -> Option<ArchiveTarget> {
+        if !self.archive_before_delete {
+            return None;
+        }
+
+        self.archive_dir
+            .as_ref()
+            .map(|archive_dir| ArchiveTarget::new(archive_dir.clone(), self.archive_compression))
+    }
+
+    #[doc = "규칙에 설정된 인덱스 이름 날짜 양식을 만들어주는 함수"]
+    pub fn index_date_format(&self) -> Result<IndexDateFormat, anyhow::Error> {
+        /* 데이터 스트림은 양식을 지정하지 않으면 backing 인덱스 이름의 세대 생성일자를 사용한다. */
+        if self.data_stream && self.date_format.is_none() && self.date_regex.is_none() {
+            return IndexDateFormat::new(None, Some(DATA_STREAM_DATE_REGEX));
+        }
+
+        IndexDateFormat::new(self.date_format.as_deref(), self.date_regex.as_deref())
+    }
+
+    #[doc = "인덱스 명이 규칙의 인덱스 패턴에 해당하는지 확인해주는 함수 (Elasticsearch 와 동일하게 콤마 구분, '*' 와일드카드, '-' 제외 패턴 지원)"]
+    /// # Arguments
+    /// * `index_name` - 확인할 인덱스 명
+    ///
+    /// # Returns
+    /// * bool
+    ///
+    /// 데이터 스트림 규칙은 스트림 명 대신 backing 인덱스 명(.ds-<스트림명>-*)으로 비교한다.
+    pub fn is_match_index(&self, index_name: &str) -> bool {
+        let mut is_match: bool = false;
+
+        let is_pattern_match = |pattern: &str| -> bool {
+            if self.data_stream {
+                is_wildcard_match(&format!(".ds-{}-*", pattern), index_name)
+            } else {
+                is_wildcard_match(pattern, index_name)
+            }
+        };
+
+        for pattern in self
+            .index_name
+            .split(',')
+            .map(|pattern| pattern.trim())
+            .filter(|pattern| !pattern.is_empty())
+        {
+            match pattern.strip_prefix('-') {
+                Some(exclude_pattern) => {
+                    if is_pattern_match(exclude_pattern) {
+                        is_match = false;
+                    }
+                }
+                None => {
+                    if is_pattern_match(pattern) {
+                        is_match = true;
+                    }
+                }
+            }
+        }
+
+        is_match
+    }
+}
+
+impl TargetIndexList {
+    #[doc = "인덱스 보존 규칙 파일의 내용을 검증해주는 함수"]
+    /// # Returns
+    /// * Result<(), anyhow::Error> - 문제가 있는 경우 모든 문제를 모아서 반환한다.
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        let mut problems: Vec<String> = Vec::new();
+        let mut seen_patterns: HashSet<&str> = HashSet::new();
+
+        if self.index.is_empty() {
+            problems.push(String::from("no [[index]] rule is defined"));
+        }
+
+        for target_index in &self.index {
+            let index_name: &str = target_index.index_name().trim();
+
+            if index_name.is_empty() {
+                problems.push(String::from("index_name must not be empty"));
+                continue;
+            }
+
+            if !seen_patterns.insert(index_name) {
+                problems.push(format!("index_name '{}' is defined more than once", index_name));
+            }
+
+            if let Err(e) = target_index.index_date_format() {
+                problems.push(format!("{}: {}", index_name, e));
+            }
+
+            if let Err(e) = target_index.max_total_size_bytes() {
+                problems.push(format!("{}: max_total_size: {}", index_name, e));
+            }
+
+            if let (Some(min_keep), Some(max_keep)) = (target_index.min_keep, target_index.max_keep) {
+                if min_keep > max_keep {
+                    problems.push(format!(
+                        "{}: min_keep ({}) must not be greater than max_keep ({})",
+                        index_name, min_keep, max_keep
+                    ));
+                }
+            }
+
+            if target_index.snapshot_before_delete
+                && target_index
+                    .snapshot_repository
+                    .as_deref()
+                    .is_none_or(|repository| repository.trim().is_empty())
+            {
+                problems.push(format!(
+                    "{}: snapshot_repository must be set when snapshot_before_delete = true",
+                    index_name
+                ));
+            }
+
+            if target_index.archive_before_delete
+                && target_index
+                    .archive_dir
+                    .as_deref()
+                    .is_none_or(|archive_dir| archive_dir.trim().is_empty())
+            {
+                problems.push(format!(
+                    "{}: archive_dir must be set when archive_before_delete = true",
+                    index_name
+                ));
+            }
+
+            if target_index.soft_delete_days == Some(0) {
+                problems.push(format!(
+                    "{}: soft_delete_days must be greater than 0 (remove it to delete immediately)",
+                    index_name
+                ));
+            }
+
+            let mut prev_after_days: Option<u32> = None;
+
+            for phase in target_index.phases() {
+                if prev_after_days.is_some_and(|prev_after_days| phase.after_days <= prev_after_days) {
+                    problems.push(format!(
+                        "{}: phases must be ordered by after_days without duplicates (after_days = {})",
+                        index_name, phase.after_days
+                    ));
+                }
+
+                if phase.after_days >= target_index.duration_days {
+                    problems.push(format!(
+                        "{}: phase after_days ({}) must be less than duration_days ({})",
+                        index_name, phase.after_days, target_index.duration_days
+                    ));
+                }
+
+                if !phase.has_action() {
+                    problems.push(format!(
+                        "{}: phase after_days = {} has no action (set replicas, forcemerge_segments or require_attributes)",
+                        index_name, phase.after_days
+                    ));
+                }
+
+                if phase.forcemerge_segments == Some(0) {
+                    problems.push(format!(
+                        "{}: phase after_days = {}: forcemerge_segments must be greater than 0",
+                        index_name, phase.after_days
+                    ));
+                }
+
+                prev_after_days = Some(phase.after_days);
+            }
+
+            if let Some(schedule) = target_index.schedule() {
+                if let Err(e) = parse_cron_schedule(schedule) {
+                    problems.push(format!("{}: schedule: {}", index_name, e));
+                }
+            }
+        }
+
+        if let Some(emergency) = &self.emergency {
+            if let Err(e) = emergency.validate() {
+                problems.push(format!("[emergency]: {}", e));
+            }
+        }
+
+        if let Err(e) = self.schedule.validate() {
+            problems.push(format!("[schedule]: {}", e));
+        }
+
+        if let Err(e) = self.safety.validate() {
+            problems.push(format!("[safety]: {}", e));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "[Error][TargetIndexList->validate] Invalid index rule config:\n - {}",
+                problems.join("\n - ")
+            ))
+        }
+    }
+}
+
+#[doc = "'*' 와일드카드만 지원하는 간단한 패턴 매칭 함수"]
+pub fn is_wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p_idx, mut t_idx) = (0, 0);
+    let mut star_idx: Option<usize> = None;
+    let mut match_idx: usize = 0;
+
+    while t_idx < text.len() {
+        if p_idx < pattern.len() && pattern[p_idx] != '*' && pattern[p_idx] == text[t_idx] {
+            p_idx += 1;
+            t_idx += 1;
+        } else if p_idx < pattern.len() && pattern[p_idx] == '*' {
+            star_idx = Some(p_idx);
+            match_idx = t_idx;
+            p_idx += 1;
+        } else if let Some(star) = star_idx {
+            p_idx = star + 1;
+            match_idx += 1;
+            t_idx = match_idx;
+        } else {
+            return false;
+        }
+    }
+
+    while p_idx < pattern.len() && pattern[p_idx] == '*' {
+        p_idx += 1;
+    }
+
+    p_idx == pattern.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(rule_toml: &str) -> TargetIndex {
+        toml::from_str(rule_toml).unwrap()
+    }
+
+    #[test]
+    fn wildcard_matches_any_run_of_characters() {
+        assert!(is_wildcard_match("logs-*", "logs-2025.01.01"));
+        assert!(is_wildcard_match("logs-*", "logs-"));
+        assert!(is_wildcard_match("*-2025.*", "app-logs-2025.01.01"));
+        assert!(is_wildcard_match("l*s-*-01", "logs-2025.01-01"));
+        assert!(is_wildcard_match("**", ""));
+        assert!(is_wildcard_match("*", "anything"));
+    }
+
+    #[test]
+    fn wildcard_needs_the_whole_text_to_match() {
+        assert!(is_wildcard_match("logs-2025.01.01", "logs-2025.01.01"));
+        assert!(!is_wildcard_match("logs-2025.01.01", "logs-2025.01.011"));
+        assert!(!is_wildcard_match("logs-*", "app-logs-2025.01.01"));
+        assert!(!is_wildcard_match("*-01", "logs-2025.01.02"));
+        assert!(!is_wildcard_match("logs-*", "logs"));
+        assert!(!is_wildcard_match("", "logs"));
+    }
+
+    #[test]
+    fn wildcard_backtracks_after_partial_match() {
+        assert!(is_wildcard_match("*ab", "aab"));
+        assert!(is_wildcard_match("a*b*c", "abbbcbc"));
+        assert!(!is_wildcard_match("a*b*c", "abbbcb"));
+    }
+
+    #[test]
+    fn index_pattern_supports_lists_and_exclusions() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-*, metrics-*, -logs-audit-*\"\nduration_days = 30");
+
+        assert!(target_index.is_match_index("logs-2025.01.01"));
+        assert!(target_index.is_match_index("metrics-2025.01.01"));
+        assert!(!target_index.is_match_index("logs-audit-2025.01.01"));
+        assert!(!target_index.is_match_index("traces-2025.01.01"));
+    }
+
+    #[test]
+    fn later_patterns_override_earlier_exclusions() {
+        let target_index: TargetIndex =
+            rule("index_name = \"-logs-audit-*, logs-*\"\nduration_days = 30");
+
+        assert!(target_index.is_match_index("logs-audit-2025.01.01"));
+    }
+
+    #[test]
+    fn data_stream_rule_matches_backing_indices() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-app\"\ndata_stream = true\nduration_days = 30");
+
+        assert!(target_index.is_match_index(".ds-logs-app-2025.01.01-000001"));
+        assert!(!target_index.is_match_index("logs-app"));
+        assert!(!target_index.is_match_index(".ds-logs-other-2025.01.01-000001"));
+    }
 }
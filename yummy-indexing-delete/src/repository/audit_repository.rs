@@ -0,0 +1,81 @@
+use crate::common::*;
+
+use crate::configs::audit_config::*;
+
+use crate::model::audit_entry::*;
+
+use crate::repository::es_repository::*;
+
+/* 여러 규칙이 동시에 감사 로그를 남기므로 한 줄씩 쓰도록 막아준다. */
+static AUDIT_FILE_LOCK: once_lazy<Mutex<()>> = once_lazy::new(|| Mutex::new(()));
+
+#[doc = "감사 로그 한 건을 파일에, 설정된 경우 Elasticsearch 감사 인덱스에도 남겨주는 함수"]
+/// # Arguments
+/// * `audit_entry` - 감사 로그
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub async fn write_audit_entry(audit_entry: &AuditEntry) -> Result<(), anyhow::Error> {
+    let audit_config: &AuditConfig = get_audit_config();
+
+    append_audit_file(audit_config.audit_log_path(), audit_entry)?;
+
+    if let Some(audit_es_index) = audit_config.audit_es_index() {
+        let document: Value = serde_json::to_value(audit_entry)?;
+
+        let index_result: Result<(), anyhow::Error> = async {
+            get_elastic_guard_conn()
+                .await?
+                .index_document(audit_es_index, &document)
+                .await
+        }
+        .await;
+
+        index_result.map_err(|e| {
+                anyhow!(
+                    "[Error][write_audit_entry] Failed to index audit entry into '{}': {:?}",
+                    audit_es_index,
+                    e
+                )
+            })?;
+    }
+
+    Ok(())
+}
+
+#[doc = "감사 로그 파일에 한 줄(JSON)을 덧붙여주는 함수"]
+/// # Arguments
+/// * `file_path` - 감사 로그 파일 경로
+/// * `audit_entry` - 감사 로그
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+fn append_audit_file(file_path: &str, audit_entry: &AuditEntry) -> Result<(), anyhow::Error> {
+    let mut line: String = serde_json::to_string(audit_entry)?;
+    line.push('\n');
+
+    let _lock = AUDIT_FILE_LOCK
+        .lock()
+        .map_err(|e| anyhow!("[Error][append_audit_file] Audit file lock poisoned: {:?}", e))?;
+
+    if let Some(parent_dir) = Path::new(file_path).parent() {
+        std::fs::create_dir_all(parent_dir)?;
+    }
+
+    let mut file: File = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_path)
+        .map_err(|e| {
+            anyhow!(
+                "[Error][append_audit_file] Failed to open audit log '{}': {:?}",
+                file_path,
+                e
+            )
+        })?;
+
+    file.write_all(line.as_bytes())?;
+    file.sync_data()?;
+
+    Ok(())
+}
@@ -0,0 +1,558 @@
+use crate::common::*;
+
+use crate::metrics::retention_metrics::*;
+
+use crate::model::es_node_stats::*;
+
+/* 죽은 노드를 처음 다시 시도하기까지의 대기 시간 - 연속으로 실패할 때마다 두 배로 늘어난다. */
+const DEAD_NODE_BASE_BACKOFF: Duration = Duration::from_secs(10);
+const DEAD_NODE_MAX_BACKOFF: Duration = Duration::from_secs(10 * 60);
+
+/* 죽은 노드를 ping 으로 확인하는 주기와 ping 타임아웃 */
+const RESURRECT_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const RESURRECT_PING_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[doc = "노드 탓으로 볼 수 있는 요청 실패의 종류"]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NodeFailure {
+    /* 노드에 연결하지 못했다. (요청이 전달되지 않았으므로 어떤 요청이든 다른 노드로 다시 보낼 수 있다.) */
+    Unreachable,
+    /* 노드가 요청을 처리할 수 없다고 응답했다. (502/503/504) */
+    Unavailable,
+    /* 노드가 과부하로 요청을 거절했다. (429) */
+    Overloaded,
+    /* 응답 시간을 넘겼다. (요청이 처리되었는지 알 수 없다.) */
+    Timeout,
+}
+
+impl NodeFailure {
+    #[doc = "응답 상태 코드로 노드 실패 여부를 판단해주는 함수 - 그 밖의 상태 코드는 호출하는 쪽에서 처리한다."]
+    pub(crate) fn from_status(status_code: StatusCode) -> Option<Self> {
+        match status_code.as_u16() {
+            429 => Some(NodeFailure::Overloaded),
+            502..=504 => Some(NodeFailure::Unavailable),
+            _ => None,
+        }
+    }
+
+    #[doc = "요청 에러로 노드 실패 여부를 판단해주는 함수 - 연결/시간 초과가 아닌 에러는 노드 탓으로 보지 않는다."]
+    pub(crate) fn from_error(err: &anyhow::Error) -> Option<Self> {
+        let http_err: &reqwest::Error = err
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<reqwest::Error>())?;
+
+        if http_err.is_connect() {
+            Some(NodeFailure::Unreachable)
+        } else if http_err.is_timeout() {
+            Some(NodeFailure::Timeout)
+        } else {
+            None
+        }
+    }
+
+    #[doc = "노드를 죽은 노드로 표시해야 하는지 - 느리거나 과부하인 노드는 살아있는 것으로 본다."]
+    pub(crate) fn is_node_down(&self) -> bool {
+        matches!(self, NodeFailure::Unreachable | NodeFailure::Unavailable)
+    }
+
+    #[doc = "다른 노드로 다시 보내도 되는지 - 멱등하지 않은 요청은 연결조차 못 한 경우에만 다시 보낸다."]
+    pub(crate) fn can_retry(&self, idempotent: bool) -> bool {
+        idempotent || *self == NodeFailure::Unreachable
+    }
+}
+
+#[doc = "노드 상태에 남길 에러 설명 - 요청 URL 이 포함된 에러와 근본 원인(예: Connection refused)"]
+pub(crate) fn describe_node_error(err: &anyhow::Error) -> String {
+    let root_cause: String = err.root_cause().to_string();
+    let message: String = err.to_string();
+
+    if message.contains(&root_cause) {
+        message
+    } else {
+        format!("{} ({})", message, root_cause)
+    }
+}
+
+#[doc = "연속 실패 횟수에 따라 죽은 노드를 다시 시도하기까지의 대기 시간을 계산해주는 함수"]
+fn dead_node_backoff(consecutive_failures: u32) -> Duration {
+    DEAD_NODE_BASE_BACKOFF
+        .saturating_mul(2u32.saturating_pow(consecutive_failures.clamp(1, 16) - 1))
+        .min(DEAD_NODE_MAX_BACKOFF)
+}
+
+#[doc = "요청 하나를 보낼 노드와 커넥션"]
+#[derive(Debug, Getters, Clone, new)]
+pub(crate) struct EsClient {
+    pub(crate) host: String,
+    pub(crate) es_conn: Elasticsearch,
+}
+
+#[doc = "노드 하나의 상태"]
+#[derive(Debug, Default)]
+struct NodeHealth {
+    /* 죽은 노드로 표시된 경우, 다시 시도할 수 있는 시각 */
+    dead_until: Option<Instant>,
+    consecutive_failures: u32,
+    requests: u64,
+    failures: u64,
+    last_error: Option<String>,
+}
+
+#[derive(Debug)]
+struct EsNode {
+    host: String,
+    /* 인증 정보가 교체되면 커넥션만 바꿔 끼운다. (노드 상태는 유지) */
+    es_conn: RwLock<Elasticsearch>,
+    health: Mutex<NodeHealth>,
+}
+
+#[doc = "노드 상태를 추적하는 Elasticsearch 커넥션 풀 - 살아있는 노드를 라운드로빈으로 우선 사용한다."]
+#[derive(Debug)]
+pub struct EsNodePool {
+    nodes: Vec<EsNode>,
+    /* 라운드로빈 시작 위치 */
+    cursor: AtomicUsize,
+}
+
+impl EsNode {
+    fn health(&self) -> std::sync::MutexGuard<'_, NodeHealth> {
+        self.health
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn es_client(&self) -> EsClient {
+        let es_conn: Elasticsearch = self
+            .es_conn
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone();
+
+        EsClient::new(self.host.clone(), es_conn)
+    }
+}
+
+impl EsNodePool {
+    #[doc = "노드 목록으로 커넥션 풀을 만들어주는 함수"]
+    /// # Arguments
+    /// * `es_clients` - 노드별 커넥션 (처음에는 모두 살아있는 노드로 본다.)
+    pub fn new(es_clients: Vec<EsClient>) -> Self {
+        let nodes: Vec<EsNode> = es_clients
+            .into_iter()
+            .map(|es_client| {
+                get_retention_metrics()
+                    .es_node_up
+                    .with_label_values(&[&es_client.host])
+                    .set(1.0);
+
+                EsNode {
+                    host: es_client.host,
+                    es_conn: RwLock::new(es_client.es_conn),
+                    health: Mutex::new(NodeHealth::default()),
+                }
+            })
+            .collect();
+
+        Self {
+            nodes,
+            cursor: AtomicUsize::new(0),
+        }
+    }
+
+    #[doc = "요청을 시도할 노드 순서를 정해주는 함수"]
+    /// # Returns
+    /// * Vec<(usize, EsClient)> - 살아있는 노드(라운드로빈 순서) 다음에 죽은 노드(다시 시도할 시각이 빠른 순서)
+    ///
+    /// 살아있는 노드가 모두 실패한 경우에만 죽은 노드에 요청이 간다.
+    pub(crate) fn select_nodes(&self) -> Vec<(usize, EsClient)> {
+        let node_cnt: usize = self.nodes.len();
+        let start: usize = self.cursor.fetch_add(1, Ordering::Relaxed);
+
+        let mut alive_nodes: Vec<usize> = Vec::new();
+        let mut dead_nodes: Vec<(Instant, usize)> = Vec::new();
+
+        for offset in 0..node_cnt {
+            let node_idx: usize = (start + offset) % node_cnt;
+
+            match self.nodes[node_idx].health().dead_until {
+                Some(dead_until) => dead_nodes.push((dead_until, node_idx)),
+                None => alive_nodes.push(node_idx),
+            }
+        }
+
+        dead_nodes.sort_by_key(|(dead_until, _)| *dead_until);
+
+        alive_nodes
+            .into_iter()
+            .chain(dead_nodes.into_iter().map(|(_, node_idx)| node_idx))
+            .map(|node_idx| (node_idx, self.nodes[node_idx].es_client()))
+            .collect()
+    }
+
+    #[doc = "노드로 보낸 요청 수를 세어주는 함수 (상태 확인용 ping 포함)"]
+    pub(crate) fn record_request(&self, node_idx: usize) {
+        self.nodes[node_idx].health().requests += 1;
+    }
+
+    #[doc = "노드가 응답한 경우 살아있는 노드로 표시해주는 함수"]
+    pub(crate) fn mark_alive(&self, node_idx: usize) {
+        let node: &EsNode = &self.nodes[node_idx];
+        let mut health = node.health();
+
+        if health.dead_until.take().is_some() {
+            info!(
+                "[EsNodePool] Elasticsearch node '{}' is alive again after {} failures",
+                node.host, health.consecutive_failures
+            );
+            get_retention_metrics()
+                .es_node_up
+                .with_label_values(&[&node.host])
+                .set(1.0);
+        }
+
+        health.consecutive_failures = 0;
+    }
+
+    #[doc = "노드의 요청 실패를 기록해주는 함수 - 죽은 노드로 표시하지는 않는다. (시간 초과, 과부하)"]
+    pub(crate) fn record_failure(&self, node_idx: usize, err: &str) {
+        let node: &EsNode = &self.nodes[node_idx];
+        let mut health = node.health();
+
+        health.failures += 1;
+        health.last_error = Some(err.to_string());
+
+        get_retention_metrics()
+            .es_node_failures
+            .with_label_values(&[&node.host])
+            .inc();
+    }
+
+    #[doc = "노드가 실패한 경우 죽은 노드로 표시해주는 함수 - 연속 실패 횟수에 따라 다시 시도할 시각을 늦춘다."]
+    pub(crate) fn mark_dead(&self, node_idx: usize, err: &str) {
+        self.record_failure(node_idx, err);
+
+        let node: &EsNode = &self.nodes[node_idx];
+        let mut health = node.health();
+
+        health.consecutive_failures += 1;
+
+        let backoff: Duration = dead_node_backoff(health.consecutive_failures);
+        health.dead_until = Some(Instant::now() + backoff);
+
+        error!(
+            "[Error][EsNodePool] Elasticsearch node '{}' marked dead for {}s (consecutive failures: {})",
+            node.host,
+            backoff.as_secs(),
+            health.consecutive_failures
+        );
+
+        get_retention_metrics()
+            .es_node_up
+            .with_label_values(&[&node.host])
+            .set(0.0);
+    }
+
+    #[doc = "인증 정보가 교체된 경우 노드별 커넥션을 바꿔주는 함수 (노드 상태와 통계는 유지)"]
+    /// # Arguments
+    /// * `es_clients` - 새 인증 정보로 만든 노드별 커넥션
+    pub fn replace_connections(&self, es_clients: Vec<EsClient>) {
+        for es_client in es_clients {
+            if let Some(node) = self.nodes.iter().find(|node| node.host == es_client.host) {
+                *node
+                    .es_conn
+                    .write()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner) = es_client.es_conn;
+            }
+        }
+    }
+
+    #[doc = "다시 시도할 시각이 지난 죽은 노드에 ping 을 보내서 되살려주는 함수"]
+    pub async fn resurrect_dead_nodes(&self) {
+        let now: Instant = Instant::now();
+
+        for (node_idx, node) in self.nodes.iter().enumerate() {
+            if node
+                .health()
+                .dead_until
+                .is_none_or(|dead_until| dead_until > now)
+            {
+                continue;
+            }
+
+            self.record_request(node_idx);
+
+            let es_client: EsClient = node.es_client();
+            let result: Result<Response, anyhow::Error> = es_client
+                .es_conn
+                .ping()
+                .request_timeout(RESURRECT_PING_TIMEOUT)
+                .send()
+                .await
+                .map_err(anyhow::Error::from);
+
+            match result {
+                Ok(response) if response.status_code().is_success() => self.mark_alive(node_idx),
+                Ok(response) => self.mark_dead(
+                    node_idx,
+                    &format!("ping failed: Status Code: {}", response.status_code()),
+                ),
+                Err(err) => self.mark_dead(node_idx, &describe_node_error(&err)),
+            }
+        }
+    }
+
+    #[doc = "죽은 노드를 주기적으로 확인하는 백그라운드 작업을 시작해주는 함수"]
+    pub fn spawn_resurrection(node_pool: Arc<EsNodePool>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(RESURRECT_CHECK_INTERVAL).await;
+                node_pool.resurrect_dead_nodes().await;
+            }
+        })
+    }
+
+    #[doc = "노드별 상태와 요청 통계를 가져와주는 함수"]
+    pub fn node_stats(&self) -> Vec<EsNodeStats> {
+        let now: Instant = Instant::now();
+
+        self.nodes
+            .iter()
+            .map(|node| {
+                let health = node.health();
+
+                EsNodeStats::new(
+                    node.host.clone(),
+                    health.dead_until.is_none(),
+                    health.consecutive_failures,
+                    health.requests,
+                    health.failures,
+                    health
+                        .dead_until
+                        .map(|dead_until| dead_until.saturating_duration_since(now).as_secs()),
+                    health.last_error.clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_pool(hosts: &[&str]) -> EsNodePool {
+        let es_clients: Vec<EsClient> = hosts
+            .iter()
+            .map(|host| {
+                let transport: EsTransport = EsTransport::single_node(host).unwrap();
+                EsClient::new(host.to_string(), Elasticsearch::new(transport))
+            })
+            .collect();
+
+        EsNodePool::new(es_clients)
+    }
+
+    fn selected_hosts(node_pool: &EsNodePool) -> Vec<String> {
+        node_pool
+            .select_nodes()
+            .into_iter()
+            .map(|(_, es_client)| es_client.host)
+            .collect()
+    }
+
+    fn expire_dead_until(node_pool: &EsNodePool, node_idx: usize) {
+        node_pool.nodes[node_idx].health().dead_until =
+            Some(Instant::now() - Duration::from_secs(1));
+    }
+
+    #[test]
+    fn backoff_doubles_per_failure_up_to_max() {
+        assert_eq!(dead_node_backoff(0), Duration::from_secs(10));
+        assert_eq!(dead_node_backoff(1), Duration::from_secs(10));
+        assert_eq!(dead_node_backoff(2), Duration::from_secs(20));
+        assert_eq!(dead_node_backoff(3), Duration::from_secs(40));
+        assert_eq!(dead_node_backoff(6), Duration::from_secs(320));
+        assert_eq!(dead_node_backoff(7), DEAD_NODE_MAX_BACKOFF);
+        assert_eq!(dead_node_backoff(u32::MAX), DEAD_NODE_MAX_BACKOFF);
+    }
+
+    #[test]
+    fn alive_nodes_are_selected_round_robin() {
+        let node_pool: EsNodePool = test_pool(&["http://a:9200", "http://b:9200", "http://c:9200"]);
+
+        assert_eq!(
+            selected_hosts(&node_pool),
+            ["http://a:9200", "http://b:9200", "http://c:9200"]
+        );
+        assert_eq!(
+            selected_hosts(&node_pool),
+            ["http://b:9200", "http://c:9200", "http://a:9200"]
+        );
+        assert_eq!(
+            selected_hosts(&node_pool),
+            ["http://c:9200", "http://a:9200", "http://b:9200"]
+        );
+        assert_eq!(
+            selected_hosts(&node_pool),
+            ["http://a:9200", "http://b:9200", "http://c:9200"]
+        );
+    }
+
+    #[test]
+    fn dead_nodes_are_tried_last_in_retry_order() {
+        let node_pool: EsNodePool = test_pool(&["http://a:9200", "http://b:9200", "http://c:9200"]);
+
+        node_pool.mark_dead(0, "connection refused");
+        node_pool.mark_dead(0, "connection refused");
+        node_pool.mark_dead(1, "connection refused");
+
+        /* a 는 두 번 실패해서 b 보다 늦게 다시 시도한다. */
+        assert_eq!(
+            selected_hosts(&node_pool),
+            ["http://c:9200", "http://b:9200", "http://a:9200"]
+        );
+
+        node_pool.mark_alive(0);
+
+        let stats: Vec<EsNodeStats> = node_pool.node_stats();
+        assert!(stats[0].alive);
+        assert_eq!(stats[0].consecutive_failures, 0);
+        assert_eq!(stats[0].failures, 2);
+        assert!(!stats[1].alive);
+    }
+
+    #[test]
+    fn slow_or_overloaded_nodes_stay_alive() {
+        let node_pool: EsNodePool = test_pool(&["http://a:9200"]);
+
+        node_pool.record_request(0);
+        node_pool.record_failure(0, "operation timed out");
+
+        let stats: Vec<EsNodeStats> = node_pool.node_stats();
+        assert!(stats[0].alive);
+        assert_eq!(stats[0].requests, 1);
+        assert_eq!(stats[0].failures, 1);
+    }
+
+    #[test]
+    fn node_failure_from_status() {
+        assert_eq!(NodeFailure::from_status(StatusCode::OK), None);
+        assert_eq!(NodeFailure::from_status(StatusCode::NOT_FOUND), None);
+        assert_eq!(
+            NodeFailure::from_status(StatusCode::INTERNAL_SERVER_ERROR),
+            None
+        );
+        assert_eq!(
+            NodeFailure::from_status(StatusCode::TOO_MANY_REQUESTS),
+            Some(NodeFailure::Overloaded)
+        );
+        assert_eq!(
+            NodeFailure::from_status(StatusCode::BAD_GATEWAY),
+            Some(NodeFailure::Unavailable)
+        );
+        assert_eq!(
+            NodeFailure::from_status(StatusCode::SERVICE_UNAVAILABLE),
+            Some(NodeFailure::Unavailable)
+        );
+        assert_eq!(
+            NodeFailure::from_status(StatusCode::GATEWAY_TIMEOUT),
+            Some(NodeFailure::Unavailable)
+        );
+    }
+
+    #[test]
+    fn only_unreachable_failures_retry_non_idempotent_requests() {
+        for node_failure in [
+            NodeFailure::Unreachable,
+            NodeFailure::Unavailable,
+            NodeFailure::Overloaded,
+            NodeFailure::Timeout,
+        ] {
+            assert!(node_failure.can_retry(true));
+            assert_eq!(
+                node_failure.can_retry(false),
+                node_failure == NodeFailure::Unreachable
+            );
+        }
+
+        assert!(NodeFailure::Unreachable.is_node_down());
+        assert!(NodeFailure::Unavailable.is_node_down());
+        assert!(!NodeFailure::Overloaded.is_node_down());
+        assert!(!NodeFailure::Timeout.is_node_down());
+    }
+
+    #[tokio::test]
+    async fn connection_refused_is_unreachable() {
+        let node_pool: EsNodePool = test_pool(&["http://127.0.0.1:1"]);
+        let (_, es_client) = node_pool.select_nodes().remove(0);
+
+        let err: anyhow::Error = es_client
+            .es_conn
+            .ping()
+            .send()
+            .await
+            .map_err(anyhow::Error::from)
+            .unwrap_err();
+
+        assert_eq!(
+            NodeFailure::from_error(&err),
+            Some(NodeFailure::Unreachable)
+        );
+        assert_eq!(
+            NodeFailure::from_error(&anyhow!("invalid response body")),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn resurrection_pings_only_due_nodes_and_counts_pings() {
+        /* ping 에 200 으로 응답하는 노드 */
+        let listener: tokio::net::TcpListener =
+            tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let alive_host: String = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf: [u8; 1024] = [0u8; 1024];
+                let _ = tokio::io::AsyncReadExt::read(&mut stream, &mut buf).await;
+                let _ = tokio::io::AsyncWriteExt::write_all(
+                    &mut stream,
+                    b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                )
+                .await;
+            }
+        });
+
+        let node_pool: EsNodePool =
+            test_pool(&[&alive_host, "http://127.0.0.1:1", "http://127.0.0.1:2"]);
+
+        for node_idx in 0..3 {
+            node_pool.record_request(node_idx);
+            node_pool.mark_dead(node_idx, "connection refused");
+        }
+
+        /* 다시 시도할 시각이 지난 노드만 ping 한다. (2 번 노드는 아직 대기중) */
+        expire_dead_until(&node_pool, 0);
+        expire_dead_until(&node_pool, 1);
+
+        node_pool.resurrect_dead_nodes().await;
+
+        let stats: Vec<EsNodeStats> = node_pool.node_stats();
+
+        assert!(stats[0].alive);
+        assert_eq!(stats[0].consecutive_failures, 0);
+
+        assert!(!stats[1].alive);
+        assert_eq!(stats[1].consecutive_failures, 2);
+        assert!(stats[1]
+            .retry_in_secs
+            .is_some_and(|retry_in_secs| retry_in_secs > 10));
+
+        assert_eq!(stats[2].requests, 1);
+        assert_eq!(stats[2].consecutive_failures, 1);
+
+        for node_stats in &stats {
+            assert!(node_stats.failures <= node_stats.requests);
+        }
+    }
+}
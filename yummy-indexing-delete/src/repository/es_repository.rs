@@ -2,30 +2,85 @@ use crate::common::*;
 
 use crate::configs::elastic_server_config::*;
 
-static ELASTICSEARCH_CONN_SEMAPHORE_POOL: once_lazy<Vec<Arc<EsRepositoryPub>>> = once_lazy::new(
-    || {
-        let config: &ElasticServerConfig = get_elastic_config();
-        let pool_cnt: i32 = *config.elastic_pool_cnt();
-        let es_host: &Vec<String> = config.elastic_host();
-        let es_id: String = config.elastic_id().clone().unwrap_or(String::from(""));
-        let es_pw: String = config.elastic_pw().clone().unwrap_or(String::from(""));
-        
-        (0..pool_cnt)
-        .map(|_| {
-            Arc::new(
-                EsRepositoryPub::new(es_host.clone(), &es_id, &es_pw)
-                    .expect("[Error][ELASTICSEARCH_CONN_SEMA_POOL] Failed to create Elasticsearch client"),
-            )
-        })
-        .collect()
-    },
-);
+use crate::metrics::retention_metrics::*;
+
+use crate::model::es_node_stats::*;
+
+use crate::repository::es_node_pool::*;
+
+/* 모든 작업이 함께 사용하는 하나의 커넥션 풀 - 노드 상태를 추적하며, 죽은 노드는 백그라운드 ping 으로 되살린다. */
+static ELASTICSEARCH_CONN_POOL: once_lazy<Arc<EsRepositoryPub>> = once_lazy::new(|| {
+    let config: &ElasticServerConfig = get_elastic_config();
+    let elastic_auth: ElasticAuth = config.current_auth();
+
+    info!(
+        "[ELASTICSEARCH_CONN_POOL] nodes: {:?}, credentials: {} ({}), tls: {}",
+        config.elastic_host(),
+        config.credential_provider().describe(),
+        elastic_auth.kind(),
+        config.elastic_tls().is_enabled()
+    );
+
+    let es_repository: EsRepositoryPub = EsRepositoryPub::new(config, &elastic_auth)
+        .expect("[Error][ELASTICSEARCH_CONN_POOL] Failed to create Elasticsearch client");
+
+    EsNodePool::spawn_resurrection(Arc::clone(&es_repository.node_pool));
 
+    Arc::new(es_repository)
+});
+
+/* 동시에 보낼 수 있는 요청 수만 제한한다. (커넥션 풀은 하나를 공유) */
 static SEMAPHORE: once_lazy<Arc<Semaphore>> = once_lazy::new(|| {
     let config: &ElasticServerConfig = get_elastic_config();
     Arc::new(Semaphore::new(*config.elastic_pool_cnt() as usize))
 });
 
+#[doc = "인증 정보 공급자를 다시 읽고, 인증 정보가 교체된 경우 노드별 커넥션을 다시 만들어주는 함수"]
+/// # Returns
+/// * Result<bool, anyhow::Error> - 인증 정보가 교체된 경우 true
+///
+/// 공급자를 읽지 못하거나 커넥션을 만들지 못하면 기존 커넥션을 그대로 사용한다. (노드 상태는 유지된다.)
+pub fn refresh_elastic_credentials() -> Result<bool, anyhow::Error> {
+    let config: &ElasticServerConfig = get_elastic_config();
+    let previous_auth: ElasticAuth = config.current_auth();
+    let elastic_auth: ElasticAuth = config.credential_provider().load()?;
+
+    if elastic_auth == previous_auth {
+        return Ok(false);
+    }
+
+    let es_clients: Vec<EsClient> = EsRepositoryPub::create_node_clients(config, &elastic_auth)?;
+    ELASTICSEARCH_CONN_POOL.node_pool.replace_connections(es_clients);
+
+    info!(
+        "[refresh_elastic_credentials] Elasticsearch credentials rotated ({} -> {})",
+        previous_auth.kind(),
+        elastic_auth.kind()
+    );
+
+    config.set_auth(elastic_auth);
+
+    Ok(true)
+}
+
+#[doc = "커넥션 풀의 노드별 상태와 요청 통계를 가져와주는 함수"]
+pub fn get_es_node_stats() -> Vec<EsNodeStats> {
+    ELASTICSEARCH_CONN_POOL.node_pool.node_stats()
+}
+
+/* 스냅샷 복원, 아카이브(point in time), _bulk 처럼 오래 걸리는 요청의 타임아웃 */
+const LONG_REQUEST_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/* _cat/indices 에서 가져올 컬럼 */
+const CAT_INDICES_COLUMNS: [&str; 6] = [
+    "index",
+    "health",
+    "status",
+    "creation.date",
+    "store.size",
+    "docs.count",
+];
+
 #[derive(Debug)]
 pub struct ElasticConnGuard {
     client: Arc<EsRepositoryPub>,
@@ -35,14 +90,15 @@ pub struct ElasticConnGuard {
 impl ElasticConnGuard {
     pub async fn new() -> Result<Self, anyhow::Error> {
         info!("[ElasticConnGuard] Available permits: {}", SEMAPHORE.available_permits());
+        let wait_start: Instant = Instant::now();
         let permit: OwnedSemaphorePermit = SEMAPHORE.clone().acquire_owned().await?;
+        get_retention_metrics()
+            .es_conn_wait_duration
+            .observe(wait_start.elapsed().as_secs_f64());
         info!("[ElasticConnGuard] Acquired semaphore");
 
-        /* 임의로 하나의 클라이언트를 가져옴 (랜덤 선택 가능) */
-        let client: Arc<EsRepositoryPub> = ELASTICSEARCH_CONN_SEMAPHORE_POOL
-            .choose(&mut rand::thread_rng())
-            .cloned()
-            .expect("[Error][EalsticConnGuard -> new] No clients available");
+        /* 노드 선택은 요청마다 커넥션 풀이 한다. */
+        let client: Arc<EsRepositoryPub> = Arc::clone(&ELASTICSEARCH_CONN_POOL);
 
         Ok(Self {
             client,
@@ -73,32 +129,94 @@ pub async fn get_elastic_guard_conn() -> Result<ElasticConnGuard, anyhow::Error>
 #[async_trait]
 pub trait EsRepository {
     async fn get_index_belong_pattern(&self, index_pattern: &str) -> Result<Value, anyhow::Error>;
+    async fn get_index_rollover_info(&self, index_pattern: &str) -> Result<Value, anyhow::Error>;
+    async fn get_disk_allocation(&self) -> Result<Value, anyhow::Error>;
     async fn delete_index(&self, index_name: &str) -> Result<(), anyhow::Error>;
+    async fn index_document(&self, index_name: &str, document: &Value) -> Result<(), anyhow::Error>;
+    async fn create_snapshot(
+        &self,
+        repository: &str,
+        snapshot_name: &str,
+        index_name: &str,
+        metadata: &Value,
+    ) -> Result<(), anyhow::Error>;
+    async fn get_snapshot(&self, repository: &str, snapshot_name: &str) -> Result<Value, anyhow::Error>;
+    async fn restore_snapshot(
+        &self,
+        repository: &str,
+        snapshot_name: &str,
+        index_name: &str,
+        restored_index_name: &str,
+    ) -> Result<Value, anyhow::Error>;
+    async fn get_index_meta(&self, index_pattern: &str) -> Result<Value, anyhow::Error>;
+    async fn put_index_meta(&self, index_name: &str, meta: &Value) -> Result<(), anyhow::Error>;
+    async fn get_index_definition(&self, index_name: &str) -> Result<Value, anyhow::Error>;
+    async fn count_index(&self, index_name: &str) -> Result<u64, anyhow::Error>;
+    async fn open_point_in_time(&self, index_name: &str, keep_alive: &str) -> Result<String, anyhow::Error>;
+    async fn search_after_page(
+        &self,
+        pit_id: &str,
+        keep_alive: &str,
+        search_after: Option<&Value>,
+        page_size: usize,
+    ) -> Result<Value, anyhow::Error>;
+    async fn close_point_in_time(&self, pit_id: &str) -> Result<(), anyhow::Error>;
+    async fn create_index(&self, index_name: &str, body: &Value) -> Result<(), anyhow::Error>;
+    async fn bulk_index(&self, index_name: &str, lines: &[Value]) -> Result<Value, anyhow::Error>;
+    async fn refresh_index(&self, index_name: &str) -> Result<(), anyhow::Error>;
+    async fn close_index(&self, index_name: &str) -> Result<(), anyhow::Error>;
+    async fn add_write_block(&self, index_name: &str) -> Result<(), anyhow::Error>;
+    async fn get_index_write_blocks(&self, index_pattern: &str) -> Result<Value, anyhow::Error>;
+    async fn get_index_flat_settings(&self, index_pattern: &str) -> Result<Value, anyhow::Error>;
+    async fn get_index_segment_counts(&self, index_pattern: &str) -> Result<Value, anyhow::Error>;
+    async fn update_index_settings(&self, index_name: &str, settings: &Value) -> Result<(), anyhow::Error>;
+    async fn forcemerge_index(&self, index_name: &str, max_num_segments: u32) -> Result<(), anyhow::Error>;
+    async fn get_data_streams(&self, data_stream_pattern: &str) -> Result<Value, anyhow::Error>;
+    async fn get_index_aliases(&self, index_pattern: &str) -> Result<Value, anyhow::Error>;
+    async fn get_alias_indices(&self, alias_names: &[&str]) -> Result<Value, anyhow::Error>;
+    async fn get_index_protection_settings(&self, index_pattern: &str) -> Result<Value, anyhow::Error>;
+    async fn get_cluster_date(&self) -> Result<Option<DateTime<Utc>>, anyhow::Error>;
 }
 
 #[derive(Debug, Getters, Clone)]
 pub struct EsRepositoryPub {
-    es_clients: Vec<EsClient>,
-}
-
-#[derive(Debug, Getters, Clone, new)]
-pub(crate) struct EsClient {
-    host: String,
-    es_conn: Elasticsearch,
+    node_pool: Arc<EsNodePool>,
 }
 
 impl EsRepositoryPub {
-    pub fn new(es_url_vec: Vec<String>, es_id: &str, es_pw: &str) -> Result<Self, anyhow::Error> {
+    pub fn new(config: &ElasticServerConfig, elastic_auth: &ElasticAuth) -> Result<Self, anyhow::Error> {
+        let es_clients: Vec<EsClient> = Self::create_node_clients(config, elastic_auth)?;
+
+        Ok(EsRepositoryPub {
+            node_pool: Arc::new(EsNodePool::new(es_clients)),
+        })
+    }
+
+    #[doc = "노드별 커넥션을 만들어주는 함수"]
+    /// # Arguments
+    /// * `config` - Elasticsearch 접속 설정
+    /// * `elastic_auth` - 인증 정보
+    ///
+    /// # Returns
+    /// * Result<Vec<EsClient>, anyhow::Error>
+    fn create_node_clients(
+        config: &ElasticServerConfig,
+        elastic_auth: &ElasticAuth,
+    ) -> Result<Vec<EsClient>, anyhow::Error> {
         let mut es_clients: Vec<EsClient> = Vec::new();
 
-        for url in es_url_vec {
-            let parse_url: String = format!("http://{}:{}@{}", es_id, es_pw, url);
+        for url in config.elastic_host().clone() {
+            /* 스킴이 없는 주소는 TLS 설정 여부에 따라 http/https 를 붙인다. */
+            let parse_url: String = if url.contains("://") {
+                url.clone()
+            } else {
+                format!("{}://{}", config.default_scheme(), url)
+            };
 
             let es_url: Url = Url::parse(&parse_url)?;
             let conn_pool: SingleNodeConnectionPool = SingleNodeConnectionPool::new(es_url);
-            let transport: EsTransport = TransportBuilder::new(conn_pool)
-                .timeout(Duration::new(5, 0))
-                .build()?;
+            let transport: EsTransport =
+                Self::build_transport(conn_pool, config.elastic_tls(), elastic_auth)?;
 
             let elastic_conn: Elasticsearch = Elasticsearch::new(transport);
             let es_client: EsClient = EsClient::new(url, elastic_conn);
@@ -106,34 +224,194 @@ impl EsRepositoryPub {
             es_clients.push(es_client);
         }
 
-        Ok(EsRepositoryPub { es_clients })
+        Ok(es_clients)
+    }
+
+    #[doc = "인증/TLS 설정을 적용한 Transport 를 만들어주는 함수"]
+    /// # Arguments
+    /// * `conn_pool` - 노드 커넥션 풀
+    /// * `tls_config` - HTTPS 연결 설정
+    /// * `auth` - 인증 정보
+    ///
+    /// # Returns
+    /// * Result<EsTransport, anyhow::Error>
+    ///
+    /// 클라이언트 인증서를 사용하는 경우 Transport 의 credentials 는 인증서가 차지하므로,
+    /// 그 외의 인증 정보는 Authorization 헤더로 함께 보낸다.
+    fn build_transport(
+        conn_pool: SingleNodeConnectionPool,
+        tls_config: &ElasticTlsConfig,
+        auth: &ElasticAuth,
+    ) -> Result<EsTransport, anyhow::Error> {
+        let mut builder: TransportBuilder =
+            TransportBuilder::new(conn_pool).timeout(Duration::new(5, 0));
+
+        if let Some(ca_cert_path) = tls_config.ca_cert_path() {
+            let ca_pem: Vec<u8> = std::fs::read(ca_cert_path).map_err(|e| {
+                anyhow!("[Error][EsRepositoryPub->build_transport] Failed to read CA bundle '{}': {}", ca_cert_path, e)
+            })?;
+            builder = builder.cert_validation(CertificateValidation::Full(Certificate::from_pem(&ca_pem)?));
+        } else if *tls_config.skip_verify() {
+            builder = builder.cert_validation(CertificateValidation::None);
+        }
+
+        let credentials: Option<Credentials> = match auth {
+            ElasticAuth::None => None,
+            ElasticAuth::Basic { id, pw } => Some(Credentials::Basic(id.clone(), pw.clone())),
+            ElasticAuth::ApiKey { id, key } => Some(Credentials::ApiKey(id.clone(), key.clone())),
+            ElasticAuth::Bearer(token) => Some(Credentials::Bearer(token.clone())),
+            ElasticAuth::EncodedApiKey(encoded_key) => {
+                let mut header_value: HeaderValue =
+                    HeaderValue::from_str(&format!("ApiKey {}", encoded_key))?;
+                header_value.set_sensitive(true);
+                builder = builder.header(AUTHORIZATION, header_value);
+                None
+            }
+        };
+
+        match tls_config.client_cert_path() {
+            Some(client_cert_path) => {
+                let pkcs12: Vec<u8> = std::fs::read(client_cert_path).map_err(|e| {
+                    anyhow!("[Error][EsRepositoryPub->build_transport] Failed to read client certificate '{}': {}", client_cert_path, e)
+                })?;
+                builder = builder.auth(Credentials::Certificate(ClientCertificate::Pkcs12(
+                    pkcs12,
+                    tls_config.client_cert_pw().clone(),
+                )));
+
+                if let Some(credentials) = credentials {
+                    builder = builder.header(AUTHORIZATION, Self::authorization_header(&credentials)?);
+                }
+            }
+            None => {
+                if let Some(credentials) = credentials {
+                    builder = builder.auth(credentials);
+                }
+            }
+        }
+
+        Ok(builder.build()?)
+    }
+
+    #[doc = "인증 정보를 Authorization 헤더 값으로 바꿔주는 함수 (클라이언트 인증서와 함께 쓰는 경우)"]
+    fn authorization_header(credentials: &Credentials) -> Result<HeaderValue, anyhow::Error> {
+        let header: String = match credentials {
+            Credentials::Basic(id, pw) => format!("Basic {}", BASE64_STANDARD.encode(format!("{}:{}", id, pw))),
+            Credentials::ApiKey(id, key) => format!("ApiKey {}", BASE64_STANDARD.encode(format!("{}:{}", id, key))),
+            Credentials::Bearer(token) => format!("Bearer {}", token),
+            Credentials::Certificate(_) => {
+                return Err(anyhow!("[Error][EsRepositoryPub->authorization_header] A client certificate cannot be sent as a header"))
+            }
+        };
+
+        let mut header_value: HeaderValue = HeaderValue::from_str(&header)?;
+        header_value.set_sensitive(true);
+
+        Ok(header_value)
     }
 
     #[doc = "Common logic: common node failure handling and node selection"]
+    /// 조회처럼 여러 번 보내도 되는 요청에 사용한다. 노드가 실패하면(연결 실패, 시간 초과, 429/502/503/504) 다음 노드로 다시 보낸다.
     async fn execute_on_any_node<F, Fut>(&self, operation: F) -> Result<Response, anyhow::Error>
     where
         F: Fn(EsClient) -> Fut + Send + Sync,
         Fut: Future<Output = Result<Response, anyhow::Error>> + Send,
     {
-        let mut last_error: Option<anyhow::Error> = None;
+        self.execute_with_failover(operation, true).await
+    }
+
+    #[doc = "삭제/생성처럼 두 번 실행되면 안 되는 요청을 보내주는 함수"]
+    /// 노드에 연결조차 하지 못한 경우에만 다음 노드로 다시 보낸다. (시간 초과 후에는 다시 보내지 않는다.)
+    async fn execute_write_on_any_node<F, Fut>(&self, operation: F) -> Result<Response, anyhow::Error>
+    where
+        F: Fn(EsClient) -> Fut + Send + Sync,
+        Fut: Future<Output = Result<Response, anyhow::Error>> + Send,
+    {
+        self.execute_with_failover(operation, false).await
+    }
+
+    #[doc = "살아있는 노드를 라운드로빈으로 먼저 시도하고, 노드 탓인 실패만 노드 상태에 반영해주는 함수"]
+    /// # Arguments
+    /// * `operation` - 노드 하나에 보낼 요청
+    /// * `idempotent` - 요청이 전달되었을 수도 있는 실패(시간 초과, 5xx) 후에도 다른 노드로 다시 보내도 되는지
+    ///
+    /// # Returns
+    /// * Result<Response, anyhow::Error> - 다시 보낼 노드가 없으면 마지막 응답(또는 에러)을 그대로 반환한다.
+    async fn execute_with_failover<F, Fut>(
+        &self,
+        operation: F,
+        idempotent: bool,
+    ) -> Result<Response, anyhow::Error>
+    where
+        F: Fn(EsClient) -> Fut + Send + Sync,
+        Fut: Future<Output = Result<Response, anyhow::Error>> + Send,
+    {
+        let mut last_result: Option<Result<Response, anyhow::Error>> = None;
+
+        for (node_idx, es_client) in self.node_pool.select_nodes() {
+            let host: String = es_client.host.clone();
+            self.node_pool.record_request(node_idx);
+
+            let request_start: Instant = Instant::now();
+            let result: Result<Response, anyhow::Error> = operation(es_client).await;
 
-        let mut rng: StdRng = StdRng::from_entropy();
-        let mut shuffled_clients = self.es_clients.clone();
-        shuffled_clients.shuffle(&mut rng);
+            let outcome: &str = match &result {
+                Ok(response) if response.status_code().is_success() => "success",
+                _ => "error",
+            };
+            get_retention_metrics()
+                .es_request_duration
+                .with_label_values(&[&host, outcome])
+                .observe(request_start.elapsed().as_secs_f64());
 
-        for es_client in shuffled_clients {
-            match operation(es_client).await {
-                Ok(response) => return Ok(response),
-                Err(err) => {
-                    last_error = Some(err);
+            let node_failure: Option<NodeFailure> = match &result {
+                Ok(response) => NodeFailure::from_status(response.status_code()),
+                Err(err) => NodeFailure::from_error(err),
+            };
+
+            let node_failure: NodeFailure = match node_failure {
+                Some(node_failure) => node_failure,
+                /* 노드와 상관없는 결과(4xx, 500 등 포함)는 그대로 호출한 쪽에서 처리한다. */
+                None => {
+                    if result.is_ok() {
+                        self.node_pool.mark_alive(node_idx);
+                    }
+
+                    return result;
                 }
+            };
+
+            let failure_message: String = match &result {
+                Ok(response) => format!("Status Code: {}", response.status_code()),
+                Err(err) => describe_node_error(err),
+            };
+
+            error!(
+                "[Error][EsRepositoryPub->execute_with_failover] Elasticsearch node '{}' failed ({:?}): {}",
+                host, node_failure, failure_message
+            );
+
+            if node_failure.is_node_down() {
+                self.node_pool.mark_dead(node_idx, &failure_message);
+            } else {
+                self.node_pool.record_failure(node_idx, &failure_message);
+            }
+
+            if !node_failure.can_retry(idempotent) {
+                return result;
             }
+
+            last_result = Some(result);
         }
 
-        Err(anyhow::anyhow!(
-            "All Elasticsearch nodes failed. Last error: {:?}",
-            last_error
-        ))
+        match last_result {
+            Some(Ok(response)) => Ok(response),
+            Some(Err(err)) => Err(anyhow!(
+                "All Elasticsearch nodes failed. Last error: {:?}",
+                err
+            )),
+            None => Err(anyhow!("[Error][EsRepositoryPub->execute_with_failover] No Elasticsearch node is configured")),
+        }
     }
 }
 
@@ -147,7 +425,7 @@ impl EsRepository for EsRepositoryPub {
     /// * Result<(), anyhow::Error>
     async fn delete_index(&self, index_name: &str) -> Result<(), anyhow::Error> {
         let response = self
-            .execute_on_any_node(|es_client| async move {
+            .execute_write_on_any_node(|es_client| async move {
                 let response = es_client
                     .es_conn
                     .indices()
@@ -167,7 +445,7 @@ impl EsRepository for EsRepositoryPub {
         }
     }
 
-    #[doc = "특정 인덱스 패턴에 속하는 인덱스 전부를 가져와주는 함수."]
+    #[doc = "특정 인덱스 패턴에 속하는 인덱스 전부를 가져와주는 함수. (닫힌 인덱스 포함)"]
     /// # Arguments
     /// * `index_pattern` - 인덱스 패턴 문자열
     ///
@@ -180,6 +458,9 @@ impl EsRepository for EsRepositoryPub {
                     .es_conn
                     .cat()
                     .indices(CatIndicesParts::Index(&[index_pattern]))
+                    .expand_wildcards(&[ExpandWildcards::Open, ExpandWildcards::Closed])
+                    .h(&CAT_INDICES_COLUMNS)
+                    .bytes(Bytes::B)
                     .format("json")
                     .send()
                     .await?;
@@ -196,4 +477,954 @@ impl EsRepository for EsRepositoryPub {
             Err(anyhow!(error_message))
         }
     }
+
+    #[doc = "특정 인덱스 패턴에 속하는 인덱스들의 롤오버 정보를 가져와주는 함수."]
+    /// # Arguments
+    /// * `index_pattern` - 인덱스 패턴 문자열
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - { "metadata": { "indices": { "<index>": { "rollover_info": { "<alias>": { "time": .. } } } } } }
+    async fn get_index_rollover_info(&self, index_pattern: &str) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .cluster()
+                    .state(ClusterStateParts::MetricIndex(&["metadata"], &[index_pattern]))
+                    .filter_path(&["metadata.indices.*.rollover_info"])
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_index_rollover_info()] Failed to get rollover info: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스에 문서 하나를 색인해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 색인할 인덱스 명
+    /// * `document` - 색인할 문서
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn index_document(&self, index_name: &str, document: &Value) -> Result<(), anyhow::Error> {
+        let response = self
+            .execute_write_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .index(IndexParts::Index(index_name))
+                    .body(document)
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let error_message = format!("[Elasticsearch Error][index_document()] Failed to index document: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스 하나를 스냅샷 저장소에 스냅샷으로 남겨주는 함수. 완료를 기다리지 않으므로 get_snapshot 으로 상태를 확인해야 한다."]
+    /// # Arguments
+    /// * `repository` - 스냅샷 저장소 명
+    /// * `snapshot_name` - 스냅샷 명
+    /// * `index_name` - 스냅샷을 남길 인덱스 명
+    /// * `metadata` - 스냅샷에 함께 남길 메타데이터
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn create_snapshot(
+        &self,
+        repository: &str,
+        snapshot_name: &str,
+        index_name: &str,
+        metadata: &Value,
+    ) -> Result<(), anyhow::Error> {
+        let body: Value = json!({
+            "indices": index_name,
+            "ignore_unavailable": false,
+            "include_global_state": false,
+            "metadata": metadata
+        });
+
+        let response = self
+            .execute_write_on_any_node(|es_client| {
+                let body: &Value = &body;
+
+                async move {
+                    let response = es_client
+                        .es_conn
+                        .snapshot()
+                        .create(SnapshotCreateParts::RepositorySnapshot(repository, snapshot_name))
+                        .wait_for_completion(false)
+                        .body(body)
+                        .send()
+                        .await?;
+
+                    Ok(response)
+                }
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let status_code = response.status_code();
+            let response_body: String = response.text().await.unwrap_or_default();
+            let error_message = format!("[Elasticsearch Error][create_snapshot()] Failed to create snapshot: Status Code: {}, {}", status_code, response_body);
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "스냅샷의 상태를 가져와주는 함수."]
+    /// # Arguments
+    /// * `repository` - 스냅샷 저장소 명
+    /// * `snapshot_name` - 스냅샷 명
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - { "snapshots": [ { "snapshot": .., "state": "IN_PROGRESS|SUCCESS|PARTIAL|FAILED", .. } ] }
+    async fn get_snapshot(&self, repository: &str, snapshot_name: &str) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .snapshot()
+                    .get(SnapshotGetParts::RepositorySnapshot(repository, &[snapshot_name]))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_snapshot()] Failed to get snapshot: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "스냅샷에서 인덱스 하나를 다른 이름으로 복원해주는 함수. 복원이 끝날 때까지 기다린다."]
+    /// # Arguments
+    /// * `repository` - 스냅샷 저장소 명
+    /// * `snapshot_name` - 스냅샷 명
+    /// * `index_name` - 스냅샷 안의 인덱스 명
+    /// * `restored_index_name` - 복원될 인덱스 명
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error>
+    async fn restore_snapshot(
+        &self,
+        repository: &str,
+        snapshot_name: &str,
+        index_name: &str,
+        restored_index_name: &str,
+    ) -> Result<Value, anyhow::Error> {
+        let body: Value = json!({
+            "indices": index_name,
+            "include_global_state": false,
+            "include_aliases": false,
+            "rename_pattern": format!("^{}$", regex::escape(index_name)),
+            "rename_replacement": restored_index_name
+        });
+
+        let response = self
+            .execute_write_on_any_node(|es_client| {
+                let body: &Value = &body;
+
+                async move {
+                    let response = es_client
+                        .es_conn
+                        .snapshot()
+                        .restore(SnapshotRestoreParts::RepositorySnapshot(repository, snapshot_name))
+                        .wait_for_completion(true)
+                        .request_timeout(LONG_REQUEST_TIMEOUT)
+                        .body(body)
+                        .send()
+                        .await?;
+
+                    Ok(response)
+                }
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let status_code = response.status_code();
+            let response_body: String = response.text().await.unwrap_or_default();
+            let error_message = format!("[Elasticsearch Error][restore_snapshot()] Failed to restore snapshot: Status Code: {}, {}", status_code, response_body);
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스 패턴에 속하는 인덱스들의 매핑 _meta 를 가져와주는 함수."]
+    /// # Arguments
+    /// * `index_pattern` - 인덱스 패턴 문자열
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - { "<index>": { "mappings": { "_meta": { .. } } } } (_meta 가 없는 인덱스는 빠진다.)
+    async fn get_index_meta(&self, index_pattern: &str) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .get_mapping(IndicesGetMappingParts::Index(&[index_pattern]))
+                    .expand_wildcards(&[ExpandWildcards::Open, ExpandWildcards::Closed])
+                    .filter_path(&["*.mappings._meta"])
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_index_meta()] Failed to get index mapping meta: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스의 매핑 _meta 를 덮어써주는 함수."]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    /// * `meta` - 새로운 _meta 전체
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn put_index_meta(&self, index_name: &str, meta: &Value) -> Result<(), anyhow::Error> {
+        let body: Value = json!({ "_meta": meta });
+
+        let response = self
+            .execute_write_on_any_node(|es_client| {
+                let body: &Value = &body;
+
+                async move {
+                    let response = es_client
+                        .es_conn
+                        .indices()
+                        .put_mapping(IndicesPutMappingParts::Index(&[index_name]))
+                        .body(body)
+                        .send()
+                        .await?;
+
+                    Ok(response)
+                }
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let error_message = format!("[Elasticsearch Error][put_index_meta()] Failed to put index mapping meta: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스의 매핑과 세팅을 가져와주는 함수."]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - { "<index>": { "aliases": .., "mappings": .., "settings": .. } }
+    async fn get_index_definition(&self, index_name: &str) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .get(IndicesGetParts::Index(&[index_name]))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_index_definition()] Failed to get index definition: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스의 문서 수를 가져와주는 함수. (_count)"]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    ///
+    /// # Returns
+    /// * Result<u64, anyhow::Error>
+    async fn count_index(&self, index_name: &str) -> Result<u64, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .count(CountParts::Index(&[index_name]))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+
+            response_body["count"].as_u64().ok_or_else(|| {
+                anyhow!("[Elasticsearch Error][count_index()] Invalid _count response: {}", response_body)
+            })
+        } else {
+            let error_message = format!("[Elasticsearch Error][count_index()] Failed to count documents: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스에 point in time 을 열어주는 함수."]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    /// * `keep_alive` - point in time 유지 시간 (예: "5m")
+    ///
+    /// # Returns
+    /// * Result<String, anyhow::Error> - point in time id
+    async fn open_point_in_time(&self, index_name: &str, keep_alive: &str) -> Result<String, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .open_point_in_time(OpenPointInTimeParts::Index(&[index_name]))
+                    .keep_alive(keep_alive)
+                    .request_timeout(LONG_REQUEST_TIMEOUT)
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+
+            response_body["id"]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!("[Elasticsearch Error][open_point_in_time()] Invalid response: {}", response_body))
+        } else {
+            let error_message = format!("[Elasticsearch Error][open_point_in_time()] Failed to open point in time: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "point in time 안의 문서를 _shard_doc 순서로 한 페이지 가져와주는 함수."]
+    /// # Arguments
+    /// * `pit_id` - point in time id
+    /// * `keep_alive` - point in time 유지 시간 (예: "5m")
+    /// * `search_after` - 이전 페이지 마지막 문서의 sort 값 (첫 페이지는 None)
+    /// * `page_size` - 한 페이지의 문서 수
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - { "pit_id": .., "hits": { "hits": [ { "_id": .., "_source": .., "sort": [..] } ] } }
+    async fn search_after_page(
+        &self,
+        pit_id: &str,
+        keep_alive: &str,
+        search_after: Option<&Value>,
+        page_size: usize,
+    ) -> Result<Value, anyhow::Error> {
+        let mut body: Value = json!({
+            "size": page_size,
+            "pit": { "id": pit_id, "keep_alive": keep_alive },
+            "sort": [ { "_shard_doc": "asc" } ],
+            "track_total_hits": false
+        });
+
+        if let Some(search_after) = search_after {
+            body["search_after"] = search_after.clone();
+        }
+
+        let response = self
+            .execute_on_any_node(|es_client| {
+                let body: &Value = &body;
+
+                async move {
+                    let response = es_client
+                        .es_conn
+                        .search(SearchParts::None)
+                        .body(body)
+                        .request_timeout(LONG_REQUEST_TIMEOUT)
+                        .send()
+                        .await?;
+
+                    Ok(response)
+                }
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][search_after_page()] Failed to search: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "point in time 을 닫아주는 함수."]
+    /// # Arguments
+    /// * `pit_id` - point in time id
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn close_point_in_time(&self, pit_id: &str) -> Result<(), anyhow::Error> {
+        let body: Value = json!({ "id": pit_id });
+
+        let response = self
+            .execute_on_any_node(|es_client| {
+                let body: &Value = &body;
+
+                async move {
+                    let response = es_client
+                        .es_conn
+                        .close_point_in_time()
+                        .body(body)
+                        .request_timeout(LONG_REQUEST_TIMEOUT)
+                        .send()
+                        .await?;
+
+                    Ok(response)
+                }
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let error_message = format!("[Elasticsearch Error][close_point_in_time()] Failed to close point in time: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "매핑과 세팅을 지정해서 인덱스를 새로 만들어주는 함수. (이미 있는 경우 에러)"]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    /// * `body` - { "settings": .., "mappings": .. }
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn create_index(&self, index_name: &str, body: &Value) -> Result<(), anyhow::Error> {
+        let response = self
+            .execute_write_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .create(IndicesCreateParts::Index(index_name))
+                    .body(body)
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let status_code = response.status_code();
+            let response_body: String = response.text().await.unwrap_or_default();
+            let error_message = format!("[Elasticsearch Error][create_index()] Failed to create index: Status Code: {}, {}", status_code, response_body);
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스에 _bulk 로 문서들을 넣어주는 함수."]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    /// * `lines` - _bulk 본문 (action 줄과 문서 줄이 번갈아 들어있음)
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - _bulk 응답 (문서별 결과는 items 에 들어있다.)
+    async fn bulk_index(&self, index_name: &str, lines: &[Value]) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_write_on_any_node(|es_client| async move {
+                let body: Vec<JsonBody<Value>> = lines.iter().cloned().map(JsonBody::from).collect();
+
+                let response = es_client
+                    .es_conn
+                    .bulk(BulkParts::Index(index_name))
+                    .body(body)
+                    .request_timeout(LONG_REQUEST_TIMEOUT)
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][bulk_index()] Failed to bulk index documents: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스를 refresh 해주는 함수."]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn refresh_index(&self, index_name: &str) -> Result<(), anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .refresh(IndicesRefreshParts::Index(&[index_name]))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let error_message = format!("[Elasticsearch Error][refresh_index()] Failed to refresh index: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스를 닫아주는 함수."]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn close_index(&self, index_name: &str) -> Result<(), anyhow::Error> {
+        let response = self
+            .execute_write_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .close(IndicesCloseParts::Index(&[index_name]))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let error_message = format!("[Elasticsearch Error][close_index()] Failed to close index: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스에 쓰기 차단(index.blocks.write)을 걸어주는 함수."]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn add_write_block(&self, index_name: &str) -> Result<(), anyhow::Error> {
+        let response = self
+            .execute_write_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .add_block(IndicesAddBlockParts::IndexBlock(&[index_name], "write"))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let error_message = format!("[Elasticsearch Error][add_write_block()] Failed to add write block: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스 패턴에 속하는 인덱스들의 쓰기 차단 세팅을 가져와주는 함수. (닫힌 인덱스 포함)"]
+    /// # Arguments
+    /// * `index_pattern` - 인덱스 패턴 문자열
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - { "<index>": { "settings": { "index": { "blocks": { "write": "true" } } } } } (차단이 없는 인덱스는 빠진다.)
+    async fn get_index_write_blocks(&self, index_pattern: &str) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .get_settings(IndicesGetSettingsParts::Index(&[index_pattern]))
+                    .expand_wildcards(&[ExpandWildcards::Open, ExpandWildcards::Closed])
+                    .filter_path(&["*.settings.index.blocks.write"])
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_index_write_blocks()] Failed to get index settings: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스 패턴에 속하는 인덱스들의 세팅을 평평한 키(index.number_of_replicas 등)로 가져와주는 함수."]
+    /// # Arguments
+    /// * `index_pattern` - 인덱스 패턴 문자열
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - { "<index>": { "settings": { "index.number_of_replicas": "1", .. } } }
+    async fn get_index_flat_settings(&self, index_pattern: &str) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .get_settings(IndicesGetSettingsParts::Index(&[index_pattern]))
+                    .flat_settings(true)
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_index_flat_settings()] Failed to get index settings: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스 패턴에 속하는 인덱스들의 샤드별 세그먼트 수를 가져와주는 함수. (열린 인덱스만)"]
+    /// # Arguments
+    /// * `index_pattern` - 인덱스 패턴 문자열
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - { "indices": { "<index>": { "shards": { "0": [ { "segments": { "count": 3 } } ] } } } }
+    async fn get_index_segment_counts(&self, index_pattern: &str) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .stats(IndicesStatsParts::IndexMetric(&[index_pattern], &["segments"]))
+                    .level(Level::Shards)
+                    .filter_path(&["indices.*.shards.*.segments.count"])
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_index_segment_counts()] Failed to get segment stats: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스의 세팅을 바꿔주는 함수."]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    /// * `settings` - 바꿀 세팅 (예: { "index.number_of_replicas": 0 })
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn update_index_settings(&self, index_name: &str, settings: &Value) -> Result<(), anyhow::Error> {
+        let response = self
+            .execute_write_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .put_settings(IndicesPutSettingsParts::Index(&[index_name]))
+                    .body(settings)
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let status_code = response.status_code();
+            let response_body: String = response.text().await.unwrap_or_default();
+            let error_message = format!("[Elasticsearch Error][update_index_settings()] Failed to update index settings: Status Code: {}, {}", status_code, response_body);
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스를 forcemerge 해주는 함수. forcemerge 가 끝날 때까지 기다린다."]
+    /// # Arguments
+    /// * `index_name` - 인덱스 명
+    /// * `max_num_segments` - 샤드별 최대 세그먼트 수
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn forcemerge_index(&self, index_name: &str, max_num_segments: u32) -> Result<(), anyhow::Error> {
+        let response = self
+            .execute_write_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .forcemerge(IndicesForcemergeParts::Index(&[index_name]))
+                    .max_num_segments(max_num_segments as i64)
+                    .request_timeout(LONG_REQUEST_TIMEOUT)
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let error_message = format!("[Elasticsearch Error][forcemerge_index()] Failed to forcemerge index: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "데이터 스트림 정보(backing 인덱스 목록, 세대)를 가져와주는 함수."]
+    /// # Arguments
+    /// * `data_stream_pattern` - 데이터 스트림 명 (와일드카드 가능)
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error>
+    async fn get_data_streams(&self, data_stream_pattern: &str) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .get_data_stream(IndicesGetDataStreamParts::Name(&[data_stream_pattern]))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_data_streams()] Failed to get data streams: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스 패턴에 속하는 인덱스들의 alias 정보를 가져와주는 함수. (닫힌 인덱스 포함)"]
+    /// # Arguments
+    /// * `index_pattern` - 인덱스 패턴 문자열
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - { "<index>": { "aliases": { "<alias>": { "is_write_index": true } } } }
+    async fn get_index_aliases(&self, index_pattern: &str) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .get_alias(IndicesGetAliasParts::Index(&[index_pattern]))
+                    .expand_wildcards(&[ExpandWildcards::Open, ExpandWildcards::Closed])
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_index_aliases()] Failed to get index aliases: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "alias 들이 가리키는 인덱스 전체를 가져와주는 함수."]
+    /// # Arguments
+    /// * `alias_names` - alias 명 목록
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - { "<index>": { "aliases": { "<alias>": { .. } } } }
+    async fn get_alias_indices(&self, alias_names: &[&str]) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .get_alias(IndicesGetAliasParts::Name(alias_names))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_alias_indices()] Failed to get alias indices: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "특정 인덱스 패턴에 속하는 인덱스들의 ILM 정책, CCR 팔로워 세팅을 가져와주는 함수. (닫힌 인덱스 포함)"]
+    /// # Arguments
+    /// * `index_pattern` - 인덱스 패턴 문자열
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - { "<index>": { "settings": { "index": { "lifecycle": { "name": .. }, "xpack": { "ccr": { "following_index": .. } } } } } }
+    async fn get_index_protection_settings(&self, index_pattern: &str) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .indices()
+                    .get_settings(IndicesGetSettingsParts::Index(&[index_pattern]))
+                    .expand_wildcards(&[ExpandWildcards::Open, ExpandWildcards::Closed])
+                    .filter_path(&[
+                        "*.settings.index.lifecycle.name",
+                        "*.settings.index.xpack.ccr.following_index",
+                    ])
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_index_protection_settings()] Failed to get index settings: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
+
+    #[doc = "클러스터 시각을 가져와주는 함수 - _cat/health 의 epoch 를 사용하고, 없으면 응답의 Date 헤더를 사용한다."]
+    ///
+    /// # Returns
+    /// * Result<Option<DateTime<Utc>>, anyhow::Error> - 두 값 모두 없거나 해석할 수 없는 경우 None
+    async fn get_cluster_date(&self) -> Result<Option<DateTime<Utc>>, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .cat()
+                    .health()
+                    .h(&["epoch"])
+                    .format("json")
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if !response.status_code().is_success() {
+            let error_message = format!("[Elasticsearch Error][get_cluster_date()] Failed to get cluster health: Status Code: {}", response.status_code());
+            return Err(anyhow!(error_message));
+        }
+
+        let header_date: Option<DateTime<Utc>> = response
+            .headers()
+            .get("date")
+            .and_then(|date| date.to_str().ok())
+            .and_then(|date| DateTime::parse_from_rfc2822(date).ok())
+            .map(|date| date.with_timezone(&Utc));
+
+        let response_body: Value = response.json::<Value>().await?;
+
+        /* epoch 는 초 단위 문자열이다. (예: [{"epoch":"1735689600"}]) */
+        let epoch_date: Option<DateTime<Utc>> = response_body
+            .get(0)
+            .and_then(|health| health.get("epoch"))
+            .and_then(|epoch| match epoch {
+                Value::String(epoch) => epoch.parse::<i64>().ok(),
+                _ => epoch.as_i64(),
+            })
+            .and_then(|epoch| DateTime::from_timestamp(epoch, 0));
+
+        Ok(epoch_date.or(header_date))
+    }
+
+    #[doc = "노드별 디스크 사용량을 가져와주는 함수."]
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error>
+    async fn get_disk_allocation(&self) -> Result<Value, anyhow::Error> {
+        let response = self
+            .execute_on_any_node(|es_client| async move {
+                let response = es_client
+                    .es_conn
+                    .cat()
+                    .allocation(CatAllocationParts::None)
+                    .h(&["node", "disk.used", "disk.total"])
+                    .bytes(Bytes::B)
+                    .format("json")
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            let response_body = response.json::<Value>().await?;
+            Ok(response_body)
+        } else {
+            let error_message = format!("[Elasticsearch Error][get_disk_allocation()] Failed to get disk allocation: Status Code: {}", response.status_code());
+            Err(anyhow!(error_message))
+        }
+    }
 }
@@ -0,0 +1,32 @@
+use crate::common::*;
+
+use crate::configs::hold_config::*;
+
+use crate::model::legal_hold::*;
+
+use crate::utils_module::io_utils::*;
+
+#[doc = "리갈 홀드 파일을 읽어주는 함수 - 운영자가 수정할 수 있으므로 실행할 때마다 새로 읽는다."]
+/// # Returns
+/// * Result<LegalHoldList, anyhow::Error> - 파일이 설정되지 않은 경우 빈 목록
+///
+/// 홀드를 확인하지 못한 채로 삭제하면 안 되므로, 설정된 파일을 읽지 못하면 에러를 반환한다.
+pub fn read_legal_holds() -> Result<LegalHoldList, anyhow::Error> {
+    let holds_file_path: &str = match get_hold_config().holds_file_path() {
+        Some(holds_file_path) => holds_file_path,
+        None => return Ok(LegalHoldList::default()),
+    };
+
+    let legal_holds: LegalHoldList =
+        read_toml_from_file::<LegalHoldList>(holds_file_path).map_err(|e| {
+            anyhow!(
+                "[Error][read_legal_holds] There was a problem reading '{}': {:?}",
+                holds_file_path,
+                e
+            )
+        })?;
+
+    legal_holds.validate()?;
+
+    Ok(legal_holds)
+}
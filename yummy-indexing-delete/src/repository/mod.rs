@@ -1 +1,4 @@
+pub mod audit_repository;
+pub mod es_node_pool;
 pub mod es_repository;
+pub mod hold_repository;
@@ -1,110 +1,3429 @@
 use crate::common::*;
 
+use crate::repository::audit_repository::*;
 use crate::repository::es_repository::*;
+use crate::repository::hold_repository::*;
 
+use crate::utils_module::archive_utils::*;
+use crate::utils_module::io_utils::*;
+use crate::utils_module::size_utils::*;
 use crate::utils_module::time_utils::*;
 
+use crate::metrics::retention_metrics::*;
+
+use crate::model::archive_manifest::*;
+use crate::model::audit_entry::*;
+use crate::model::cat_index::*;
+use crate::model::data_stream::*;
+use crate::model::disk_allocation::*;
+use crate::model::emergency_config::*;
+use crate::model::index_date_format::*;
+use crate::model::index_decision::*;
+use crate::model::index_phase::*;
+use crate::model::index_protection::*;
+use crate::model::legal_hold::*;
+use crate::model::retention_meta::*;
 use crate::model::target_index::*;
 
 #[async_trait]
 pub trait IndexClearService {
-    async fn delete_index_from_rule(&self, target_index: &TargetIndex)
-        -> Result<(), anyhow::Error>;
-    fn parsed_data_index(&self, index_name: &str) -> Result<NaiveDate, anyhow::Error>;
+    async fn run_index_plan(
+        &self,
+        target_index: &TargetIndex,
+        index_plan: &[IndexDecision],
+        run_id: &str,
+    ) -> Result<(), anyhow::Error>;
+    async fn build_index_plan(
+        &self,
+        target_index: &TargetIndex,
+    ) -> Result<Vec<IndexDecision>, anyhow::Error>;
+    async fn apply_index_plan(
+        &self,
+        target_index: &TargetIndex,
+        planned_decisions: &[IndexDecision],
+        run_id: &str,
+    ) -> Result<(), anyhow::Error>;
+    async fn delete_planned_indexes(
+        &self,
+        index_plan: &[IndexDecision],
+        run_id: &str,
+    ) -> Result<(), anyhow::Error>;
+    async fn build_emergency_plan(
+        &self,
+        target_indexes: &[TargetIndex],
+        emergency_config: &EmergencyConfig,
+    ) -> Result<EmergencyPlan, anyhow::Error>;
+    async fn restore_index(
+        &self,
+        index_name: &str,
+        repositories: &[String],
+        snapshot_name: Option<&str>,
+        restored_index_name: &str,
+        ttl_days: Option<u32>,
+    ) -> Result<String, anyhow::Error>;
+    async fn import_archive(
+        &self,
+        archive_dir: &Path,
+        manifest: &ArchiveManifest,
+        imported_index_name: &str,
+        ttl_days: Option<u32>,
+        bulk_size: usize,
+        bulk_concurrency: usize,
+    ) -> Result<u64, anyhow::Error>;
+    fn decide_index(
+        &self,
+        target_index: &TargetIndex,
+        date_format: &IndexDateFormat,
+        cat_index: &CatIndex,
+        rollover_time: Option<NaiveDateTime>,
+        cur_utc_datetime: NaiveDateTime,
+    ) -> IndexDecision;
+    fn apply_size_retention(
+        &self,
+        target_index: &TargetIndex,
+        rule_plan: &mut [IndexDecision],
+    ) -> Result<(), anyhow::Error>;
+    fn apply_count_retention(&self, target_index: &TargetIndex, rule_plan: &mut [IndexDecision]);
+    fn select_emergency_indexes(
+        &self,
+        disk_usage: &ClusterDiskUsage,
+        emergency_config: &EmergencyConfig,
+        rule_plans: Vec<(&TargetIndex, Vec<IndexDecision>)>,
+        cur_utc_time: NaiveDateTime,
+    ) -> EmergencyPlan;
+    fn apply_soft_delete(
+        &self,
+        target_index: &TargetIndex,
+        rule_plan: &mut [IndexDecision],
+        soft_deleted_times: &HashMap<String, DateTime<Utc>>,
+        cur_utc_datetime: NaiveDateTime,
+    );
+    fn resolve_index_period(
+        &self,
+        target_index: &TargetIndex,
+        date_format: &IndexDateFormat,
+        cat_index: &CatIndex,
+        rollover_time: Option<NaiveDateTime>,
+    ) -> Result<IndexPeriod, anyhow::Error>;
+    fn parsed_data_index(
+        &self,
+        date_format: &IndexDateFormat,
+        index_name: &str,
+    ) -> Result<IndexPeriod, anyhow::Error>;
+}
+
+/* 로컬 시계와 클러스터 시계의 허용 오차 - 넘으면 보존기한을 판단하지 않는다. */
+const MAX_CLOCK_SKEW: chrono::Duration = chrono::Duration::minutes(5);
+
+/* 인덱스 날짜가 현재 시각보다 이 이상 미래인 경우 잘못된 날짜로 본다. (타임존 차이로 하루 앞선 인덱스는 허용) */
+const FUTURE_INDEX_DATE_TOLERANCE: chrono::Duration = chrono::Duration::days(1);
+
+/* 삭제 전 스냅샷의 완료를 기다리는 최대 시간과 상태 확인 주기 */
+const SNAPSHOT_WAIT_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+const SNAPSHOT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/* 아카이브 시 point in time 유지 시간과 한 번에 가져올 문서 수 */
+const ARCHIVE_PIT_KEEP_ALIVE: &str = "5m";
+const ARCHIVE_PAGE_SIZE: usize = 1000;
+
+/* 아카이브를 가져올 때 _bulk 재시도 횟수와 첫 재시도 대기 시간 (재시도마다 2배씩 늘어난다.) */
+const IMPORT_BULK_MAX_RETRIES: u32 = 5;
+const IMPORT_BULK_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/* 아카이브를 가져올 때 빼야 하는 세팅 - 클러스터가 관리하는 값이거나, 원래 인덱스의 상태(ILM, 쓰기 차단, 노드 배치 등)를 그대로 가져오면 안 되는 값 */
+const IMPORT_EXCLUDED_INDEX_SETTINGS: [&str; 9] = [
+    "uuid",
+    "creation_date",
+    "provided_name",
+    "version",
+    "history_uuid",
+    "resize",
+    "lifecycle",
+    "blocks",
+    "routing",
+];
+
+#[doc = "로컬 시각과 클러스터 시각의 차이가 허용 오차 안에 있는지 확인해주는 함수"]
+/// # Arguments
+/// * `cur_utc_datetime` - 로컬 시계의 현재 UTC 시각
+/// * `cluster_time` - 클러스터의 현재 UTC 시각 (None 인 경우 알 수 없음)
+///
+/// # Returns
+/// * Result<(), anyhow::Error> - 로컬 시계가 앞서거나 늦은 경우 모두 에러, 클러스터 시각을 알 수 없는 경우에도 에러
+fn check_clock_skew(
+    cur_utc_datetime: NaiveDateTime,
+    cluster_time: Option<NaiveDateTime>,
+) -> Result<(), anyhow::Error> {
+    /* 시각을 비교할 수 없으면 로컬 시계가 앞선 경우(전부 삭제)를 막을 수 없으므로 판단하지 않는다. */
+    let cluster_time: NaiveDateTime = cluster_time.ok_or_else(|| {
+        anyhow!("[Error][check_clock_skew] Could not get the cluster clock (no epoch in _cat/health and no Date header). Refusing to judge index ages.")
+    })?;
+
+    let skew: chrono::Duration = cur_utc_datetime - cluster_time;
+
+    if skew.abs() > MAX_CLOCK_SKEW {
+        return Err(anyhow!(
+            "[Error][check_clock_skew] Local clock ({}) is {} the cluster clock ({}) by {} seconds (tolerance: {} seconds). Refusing to judge index ages.",
+            cur_utc_datetime,
+            if skew > chrono::Duration::zero() { "ahead of" } else { "behind" },
+            cluster_time,
+            skew.num_seconds().abs(),
+            MAX_CLOCK_SKEW.num_seconds()
+        ));
+    }
+
+    Ok(())
 }
 
 #[derive(Debug, Getters, Clone, new)]
 pub struct IndexClearServicePub;
 
-#[async_trait]
-impl IndexClearService for IndexClearServicePub {
-    #[doc = "규칙에 의거하여 인덱스를 지워주는 함수"]
+impl IndexClearServicePub {
+    #[doc = "규칙의 인덱스 패턴에 속하는 인덱스 목록을 가져와주는 함수"]
     /// # Arguments
+    /// * `es_conn` - Elasticsearch 커넥션
     /// * `target_index` - 대상이 되는 인덱스 정보
+    /// * `data_streams` - 규칙의 데이터 스트림 목록 (data_stream = true 인 경우)
     ///
     /// # Returns
-    /// * Result<(), anyhow::Error>
-    async fn delete_index_from_rule(
+    /// * Result<Vec<CatIndex>, anyhow::Error>
+    async fn get_rule_indexes(
         &self,
+        es_conn: &ElasticConnGuard,
         target_index: &TargetIndex,
+        data_streams: &[DataStream],
+    ) -> Result<Vec<CatIndex>, anyhow::Error> {
+        let res: Value = es_conn
+            .get_index_belong_pattern(target_index.index_name())
+            .await?;
+
+        let mut cat_indexes: Vec<CatIndex> = serde_json::from_value(res).map_err(|e| {
+            anyhow!(
+                "[Error][IndexClearService->get_rule_indexes] Failed to parse _cat/indices response: {:?}",
+                e
+            )
+        })?;
+
+        /* 데이터 스트림 규칙은 패턴에 같이 걸린 일반 인덱스를 빼고 backing 인덱스만 남긴다. */
+        if *target_index.data_stream() {
+            let backing_indexes: HashSet<&str> = data_streams
+                .iter()
+                .flat_map(|data_stream| data_stream.indices())
+                .map(|backing_index| backing_index.index_name().as_str())
+                .collect();
+
+            cat_indexes.retain(|cat_index| backing_indexes.contains(cat_index.index().as_str()));
+        }
+
+        Ok(cat_indexes)
+    }
+
+    #[doc = "로컬 시계가 클러스터 시계와 허용 오차 이상 차이나는지 확인해주는 함수"]
+    /// # Arguments
+    /// * `es_conn` - Elasticsearch 커넥션
+    /// * `cur_utc_datetime` - 로컬 시계의 현재 UTC 시각
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error> - 차이가 허용 오차를 넘거나 클러스터 시각을 알 수 없으면 에러
+    async fn check_cluster_clock(
+        &self,
+        es_conn: &ElasticConnGuard,
+        cur_utc_datetime: NaiveDateTime,
     ) -> Result<(), anyhow::Error> {
+        let cluster_time: Option<NaiveDateTime> = es_conn
+            .get_cluster_date()
+            .await?
+            .map(|cluster_date| cluster_date.naive_utc());
 
-        /* Elasitcsearch 커넥션 */
-        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+        check_clock_skew(cur_utc_datetime, cluster_time)
+    }
 
-        let res: Value = es_conn
-            .get_index_belong_pattern(target_index.index_name())
+    #[doc = "규칙에 해당하는 데이터 스트림 목록을 가져와주는 함수 (data_stream = true 인 경우에만 조회)"]
+    /// # Arguments
+    /// * `es_conn` - Elasticsearch 커넥션
+    /// * `target_index` - 대상이 되는 인덱스 정보
+    ///
+    /// # Returns
+    /// * Result<Vec<DataStream>, anyhow::Error>
+    async fn get_rule_data_streams(
+        &self,
+        es_conn: &ElasticConnGuard,
+        target_index: &TargetIndex,
+    ) -> Result<Vec<DataStream>, anyhow::Error> {
+        if !*target_index.data_stream() {
+            return Ok(Vec::new());
+        }
+
+        let res: Value = es_conn.get_data_streams(target_index.index_name()).await?;
+
+        let data_stream_list: DataStreamList = serde_json::from_value(res).map_err(|e| {
+            anyhow!(
+                "[Error][IndexClearService->get_rule_data_streams] Failed to parse _data_stream response: {:?}",
+                e
+            )
+        })?;
+
+        Ok(data_stream_list.data_streams)
+    }
+
+    #[doc = "데이터 스트림의 현재 write 인덱스에 대한 판단을 만들어주는 함수 (나이와 관계없이 항상 보존)"]
+    /// # Arguments
+    /// * `target_index` - 인덱스가 속한 규칙
+    /// * `cat_index` - 판단 대상 인덱스 정보
+    /// * `data_stream_name` - 인덱스가 write 인덱스인 데이터 스트림 명
+    /// * `cur_utc_datetime` - 기준이 되는 현재 UTC 시각
+    ///
+    /// # Returns
+    /// * IndexDecision
+    ///
+    /// write 인덱스는 삭제할 수 없으므로 날짜를 넣지 않아서 용량/개수 보존 정책에서도 제외되게 한다.
+    fn decide_data_stream_write_index(
+        &self,
+        target_index: &TargetIndex,
+        cat_index: &CatIndex,
+        data_stream_name: &str,
+        cur_utc_datetime: NaiveDateTime,
+    ) -> IndexDecision {
+        let perserve_days_ago: NaiveDate = (cur_utc_datetime
+            - chrono::Duration::days(target_index.duration_days as i64))
+        .date();
+
+        IndexDecision::new(
+            cat_index.index(),
+            target_index.index_name(),
+            perserve_days_ago,
+            cat_index.store_size_bytes(),
+            cat_index.docs_count_num(),
+        )
+        .with_action(
+            IndexAction::Keep,
+            format!("current write index of data stream '{}'", data_stream_name),
+        )
+    }
+
+    #[doc = "규칙에 속하는 인덱스 전체에 대해서 삭제 여부를 판단해주는 함수 (계획 작성과 계획 적용 시 동일하게 사용)"]
+    /// # Arguments
+    /// * `es_conn` - Elasticsearch 커넥션
+    /// * `target_index` - 대상이 되는 인덱스 정보
+    ///
+    /// # Returns
+    /// * Result<Vec<IndexDecision>, anyhow::Error>
+    async fn compute_rule_plan(
+        &self,
+        es_conn: &ElasticConnGuard,
+        target_index: &TargetIndex,
+    ) -> Result<Vec<IndexDecision>, anyhow::Error> {
+        let data_streams: Vec<DataStream> = self.get_rule_data_streams(es_conn, target_index).await?;
+        let cat_indexes: Vec<CatIndex> = self
+            .get_rule_indexes(es_conn, target_index, &data_streams)
             .await?;
-        
-        let cur_utc_time: NaiveDate = get_current_utc_naivedate();
 
-        if let Some(index_list) = res.as_array() {
-            for index in index_list {
-                let index_name = index["index"].as_str()
-                    .ok_or_else(|| anyhow!("[Error][IndexClearService->delete_index_from_rule] index['index'] variable not found."))?;
+        let cur_utc_time: NaiveDateTime = get_currnet_utc_naivedatetime();
 
-                let parsed_date: NaiveDate = match self.parsed_data_index(index_name) {
-                    Ok(parsed_date) => parsed_date,
-                    Err(e) => {
-                        error!("{:?}", e);
-                        continue;
+        /* 시계가 틀리면 모든 인덱스의 나이가 틀리므로 아무것도 판단하지 않는다. */
+        self.check_cluster_clock(es_conn, cur_utc_time).await?;
+
+        let rollover_times: HashMap<String, NaiveDateTime> =
+            self.get_rule_rollover_times(es_conn, target_index).await?;
+        let retention_metas: HashMap<String, IndexRetentionMeta> = parse_index_retention_metas(
+            &es_conn.get_index_meta(target_index.index_name()).await?,
+        );
+
+        /* write 인덱스명 -> 데이터 스트림 명 */
+        let write_indexes: HashMap<&str, &str> = data_streams
+            .iter()
+            .filter_map(|data_stream| {
+                data_stream
+                    .write_index()
+                    .map(|write_index| (write_index, data_stream.name().as_str()))
+            })
+            .collect();
+
+        let date_format: IndexDateFormat = target_index.index_date_format()?;
+
+        let mut rule_plan: Vec<IndexDecision> = cat_indexes
+            .iter()
+            .map(|cat_index| {
+                if let Some(data_stream_name) = write_indexes.get(cat_index.index().as_str()) {
+                    return self.decide_data_stream_write_index(
+                        target_index,
+                        cat_index,
+                        data_stream_name,
+                        cur_utc_time,
+                    );
+                }
+
+                /* 복원된 인덱스는 원래 날짜가 아닌 복원 정보(ttl_days)로 판단한다. */
+                let restored_meta: Option<&RestoredMeta> = retention_metas
+                    .get(cat_index.index())
+                    .and_then(|retention_meta| retention_meta.restored().as_ref());
+
+                match restored_meta {
+                    Some(restored_meta) => self.decide_restored_index(
+                        target_index,
+                        cat_index,
+                        restored_meta,
+                        cur_utc_time,
+                    ),
+                    None => self.decide_index(
+                        target_index,
+                        &date_format,
+                        cat_index,
+                        rollover_times.get(cat_index.index()).copied(),
+                        cur_utc_time,
+                    ),
+                }
+            })
+            .collect();
+
+        /* 나이 -> 용량 상한 -> 개수 순서로 판단한다. (min_keep 이 항상 마지막에 우선 적용됨) */
+        self.apply_size_retention(target_index, &mut rule_plan)?;
+        self.apply_count_retention(target_index, &mut rule_plan);
+
+        /* 삭제로 판단된 인덱스를 soft delete 단계에 맞춰서 보정한다. */
+        let soft_deleted_times: HashMap<String, DateTime<Utc>> = self
+            .get_soft_deleted_times(es_conn, target_index, &cat_indexes, &retention_metas)
+            .await?;
+        self.apply_soft_delete(target_index, &mut rule_plan, &soft_deleted_times, cur_utc_time);
+
+        /* 마지막으로 보호 대상 인덱스는 어떤 정책으로 판단되었든 제거하지 않는다. */
+        let mut index_protections: HashMap<String, Vec<IndexProtection>> = self
+            .get_index_protections(
+                es_conn,
+                target_index,
+                &cat_indexes,
+                &retention_metas,
+                cur_utc_time,
+            )
+            .await?;
+
+        for decision in rule_plan.iter_mut() {
+            if let Some(protections) = index_protections.remove(decision.index_name()) {
+                decision.protect(protections);
+            }
+        }
+
+        Ok(rule_plan)
+    }
+
+    #[doc = "규칙의 인덱스 중에서 삭제하면 안 되는 인덱스와 그 사유를 가져와주는 함수"]
+    /// # Arguments
+    /// * `es_conn` - Elasticsearch 커넥션
+    /// * `target_index` - 대상이 되는 인덱스 정보
+    /// * `cat_indexes` - 규칙의 인덱스 목록
+    /// * `retention_metas` - 규칙의 인덱스별 보존 메타데이터
+    /// * `cur_utc_datetime` - 기준이 되는 현재 UTC 시각 (리갈 홀드 만료 판단)
+    ///
+    /// # Returns
+    /// * Result<HashMap<String, Vec<IndexProtection>>, anyhow::Error> - 인덱스명 -> 보호 사유 목록
+    ///
+    /// alias 의 write 인덱스, ILM 정책이 관리하는 인덱스, CCR 팔로워 인덱스, 보호 라벨이 붙은 인덱스, 리갈 홀드가 걸린 인덱스를 보호한다.
+    async fn get_index_protections(
+        &self,
+        es_conn: &ElasticConnGuard,
+        target_index: &TargetIndex,
+        cat_indexes: &[CatIndex],
+        retention_metas: &HashMap<String, IndexRetentionMeta>,
+        cur_utc_datetime: NaiveDateTime,
+    ) -> Result<HashMap<String, Vec<IndexProtection>>, anyhow::Error> {
+        let index_aliases: Value = es_conn.get_index_aliases(target_index.index_name()).await?;
+
+        /* is_write_index 가 없는 alias 는 alias 가 가리키는 전체 인덱스 수를 따로 확인한다. */
+        let implicit_alias_names: Vec<String> = self.get_implicit_alias_names(&index_aliases);
+        let alias_indices: Value = if implicit_alias_names.is_empty() {
+            Value::Null
+        } else {
+            let alias_names: Vec<&str> = implicit_alias_names.iter().map(String::as_str).collect();
+            es_conn.get_alias_indices(&alias_names).await?
+        };
+
+        let protection_settings: Value = es_conn
+            .get_index_protection_settings(target_index.index_name())
+            .await?;
+
+        let mut index_protections: HashMap<String, Vec<IndexProtection>> = self
+            .classify_cluster_protections(&index_aliases, &alias_indices, &protection_settings);
+
+        let meta_protections: HashMap<String, Vec<IndexProtection>> = self.classify_meta_protections(
+            target_index,
+            cat_indexes,
+            retention_metas,
+            &read_legal_holds()?,
+            cur_utc_datetime.date(),
+        );
+
+        for (index_name, protections) in meta_protections {
+            index_protections
+                .entry(index_name)
+                .or_default()
+                .extend(protections);
+        }
+
+        Ok(index_protections)
+    }
+
+    #[doc = "alias 응답에서 is_write_index 가 지정되지 않은 alias 명을 뽑아주는 함수"]
+    /// # Arguments
+    /// * `index_aliases` - 규칙의 인덱스별 alias (`get_index_aliases` 의 응답)
+    ///
+    /// # Returns
+    /// * Vec<String> - 중복 없이 정렬된 alias 명 목록
+    fn get_implicit_alias_names(&self, index_aliases: &Value) -> Vec<String> {
+        let mut alias_names: Vec<String> = index_aliases
+            .as_object()
+            .into_iter()
+            .flat_map(|indices| indices.values())
+            .filter_map(|index_aliases| index_aliases["aliases"].as_object())
+            .flat_map(|aliases| aliases.iter())
+            .filter(|(_, alias_info)| alias_info["is_write_index"].as_bool().is_none())
+            .map(|(alias_name, _)| alias_name.clone())
+            .collect();
+
+        alias_names.sort();
+        alias_names.dedup();
+
+        alias_names
+    }
+
+    #[doc = "alias 와 인덱스 세팅 응답으로 write alias, ILM, CCR 팔로워 보호 사유를 분류해주는 함수"]
+    /// # Arguments
+    /// * `index_aliases` - 규칙의 인덱스별 alias (`get_index_aliases` 의 응답)
+    /// * `alias_indices` - is_write_index 가 없는 alias 가 가리키는 인덱스 (`get_alias_indices` 의 응답, 없으면 Null)
+    /// * `protection_settings` - 규칙의 인덱스별 보호 관련 세팅 (`get_index_protection_settings` 의 응답)
+    ///
+    /// # Returns
+    /// * HashMap<String, Vec<IndexProtection>> - 인덱스명 -> 보호 사유 목록
+    fn classify_cluster_protections(
+        &self,
+        index_aliases: &Value,
+        alias_indices: &Value,
+        protection_settings: &Value,
+    ) -> HashMap<String, Vec<IndexProtection>> {
+        let mut index_protections: HashMap<String, Vec<IndexProtection>> = HashMap::new();
+
+        /* is_write_index 가 지정되지 않은 alias -> alias 가 붙은 인덱스 목록 */
+        let mut implicit_aliases: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        if let Some(indices) = index_aliases.as_object() {
+            for (index_name, index_aliases) in indices {
+                let aliases = match index_aliases["aliases"].as_object() {
+                    Some(aliases) => aliases,
+                    None => continue,
+                };
+
+                for (alias_name, alias_info) in aliases {
+                    match alias_info["is_write_index"].as_bool() {
+                        Some(true) => index_protections
+                            .entry(index_name.clone())
+                            .or_default()
+                            .push(IndexProtection::WriteAlias(alias_name.clone())),
+                        Some(false) => {}
+                        None => implicit_aliases
+                            .entry(alias_name.clone())
+                            .or_default()
+                            .push(index_name.clone()),
                     }
+                }
+            }
+        }
+
+        /* is_write_index 가 없는 alias 는 인덱스 하나만 가리키는 경우 그 인덱스가 write 인덱스이다. */
+        let mut alias_index_cnt: HashMap<&str, usize> = HashMap::new();
+
+        if let Some(indices) = alias_indices.as_object() {
+            for index_aliases in indices.values() {
+                if let Some(aliases) = index_aliases["aliases"].as_object() {
+                    for alias_name in aliases.keys() {
+                        *alias_index_cnt.entry(alias_name.as_str()).or_default() += 1;
+                    }
+                }
+            }
+        }
+
+        for (alias_name, index_names) in &implicit_aliases {
+            if alias_index_cnt.get(alias_name.as_str()).copied() == Some(1) {
+                for index_name in index_names {
+                    index_protections
+                        .entry(index_name.clone())
+                        .or_default()
+                        .push(IndexProtection::WriteAlias(alias_name.clone()));
+                }
+            }
+        }
+
+        if let Some(indices) = protection_settings.as_object() {
+            for (index_name, index_settings) in indices {
+                if let Some(policy) = index_settings
+                    .pointer("/settings/index/lifecycle/name")
+                    .and_then(Value::as_str)
+                    .filter(|policy| !policy.is_empty())
+                {
+                    index_protections
+                        .entry(index_name.clone())
+                        .or_default()
+                        .push(IndexProtection::IlmManaged(policy.to_string()));
+                }
+
+                let is_ccr_follower: bool = match index_settings.pointer("/settings/index/xpack/ccr/following_index") {
+                    Some(Value::String(following)) => following == "true",
+                    Some(Value::Bool(following)) => *following,
+                    _ => false,
                 };
-                
-                /* 보존기한 데드라인 일자. */
-                let perserve_days_ago: NaiveDate =
-                    cur_utc_time - chrono::Duration::days(target_index.duration_days as i64);
-
-                if parsed_date <= perserve_days_ago {
-                    /* 인덱스 삭제 */
-                    match es_conn.delete_index(index_name).await {
-                        Ok(_) => {
-                            info!("{} has been successfully deleted.", index_name);
-                        }
-                        Err(e) => {
-                            error!("[Error][IndexClearService->delete_index_from_rule] {:?}", e);
-                            continue;
-                        }
+
+                if is_ccr_follower {
+                    index_protections
+                        .entry(index_name.clone())
+                        .or_default()
+                        .push(IndexProtection::CcrFollower);
+                }
+            }
+        }
+
+        index_protections
+    }
+
+    #[doc = "인덱스 _meta 와 리갈 홀드 파일로 보호 라벨, 리갈 홀드 보호 사유를 분류해주는 함수"]
+    /// # Arguments
+    /// * `target_index` - 대상이 되는 인덱스 정보
+    /// * `cat_indexes` - 규칙의 인덱스 목록
+    /// * `retention_metas` - 규칙의 인덱스별 보존 메타데이터
+    /// * `legal_holds` - 리갈 홀드 파일의 홀드 목록
+    /// * `today` - 기준 일자 (리갈 홀드 만료 판단)
+    ///
+    /// # Returns
+    /// * HashMap<String, Vec<IndexProtection>> - 인덱스명 -> 보호 사유 목록
+    fn classify_meta_protections(
+        &self,
+        target_index: &TargetIndex,
+        cat_indexes: &[CatIndex],
+        retention_metas: &HashMap<String, IndexRetentionMeta>,
+        legal_holds: &LegalHoldList,
+        today: NaiveDate,
+    ) -> HashMap<String, Vec<IndexProtection>> {
+        let mut index_protections: HashMap<String, Vec<IndexProtection>> = HashMap::new();
+
+        if !target_index.protect_labels().is_empty() {
+            for (index_name, retention_meta) in retention_metas {
+                for label in retention_meta.labels() {
+                    if target_index.protect_labels().contains(label) {
+                        index_protections
+                            .entry(index_name.clone())
+                            .or_default()
+                            .push(IndexProtection::Label(label.clone()));
                     }
-                    info!("{}", index_name);
                 }
             }
         }
 
-        Ok(())
+        /* 리갈 홀드는 파일의 홀드와 인덱스 _meta 의 홀드를 모두 적용한다. (만료된 홀드는 무시) */
+        for cat_index in cat_indexes {
+            let index_name: &str = cat_index.index();
+
+            let meta_hold: Option<&LegalHold> = retention_metas
+                .get(index_name)
+                .and_then(|retention_meta| retention_meta.legal_hold().as_ref());
+
+            let active_holds: Vec<&LegalHold> = legal_holds
+                .hold()
+                .iter()
+                .filter(|entry| entry.is_match_index(index_name))
+                .map(|entry| entry.hold())
+                .chain(meta_hold)
+                .filter(|legal_hold| legal_hold.is_active(today))
+                .collect();
+
+            for legal_hold in active_holds {
+                index_protections
+                    .entry(index_name.to_string())
+                    .or_default()
+                    .push(IndexProtection::LegalHold(legal_hold.clone()));
+            }
+        }
+
+        index_protections
     }
 
-    #[doc = "인덱스에 존재하는 날짜 양식을 날짜 포멧으로 뽑아주는 함수"]
+    #[doc = "규칙의 인덱스 중에서 soft delete 된 상태인 인덱스와 soft delete 된 시각을 가져와주는 함수"]
     /// # Arguments
-    /// * `index_name` - 대상이 되는 인덱스 정보
+    /// * `es_conn` - Elasticsearch 커넥션
+    /// * `target_index` - 대상이 되는 인덱스 정보
+    /// * `cat_indexes` - 규칙의 인덱스 목록
+    /// * `retention_metas` - 규칙의 인덱스별 보존 메타데이터
     ///
     /// # Returns
-    /// * Result<NaiveDate, anyhow::Error>  
-    fn parsed_data_index(&self, index_name: &str) -> Result<NaiveDate, anyhow::Error> {
-        let regex: Regex = Regex::new(r"(\d{4}[-_]?\d{2}[-_]?\d{2})")?;
+    /// * Result<HashMap<String, DateTime<Utc>>, anyhow::Error> - 인덱스명 -> soft delete 된 시각
+    ///
+    /// 표시가 남아있더라도 다시 열렸거나 쓰기 차단이 풀린 인덱스는 soft delete 가 취소된 것으로 본다.
+    async fn get_soft_deleted_times(
+        &self,
+        es_conn: &ElasticConnGuard,
+        target_index: &TargetIndex,
+        cat_indexes: &[CatIndex],
+        retention_metas: &HashMap<String, IndexRetentionMeta>,
+    ) -> Result<HashMap<String, DateTime<Utc>>, anyhow::Error> {
+        let mut soft_deleted_times: HashMap<String, DateTime<Utc>> = HashMap::new();
 
-        /* 날짜 포멧 검증 및 수정 */
-        let date_format: String = match regex
-            .find(index_name)
-            .map(|mat| mat.as_str().replace("_", "-"))
-        {
-            Some(date_format) => date_format,
-            None => {
-                return Err(anyhow!("[Error][IndexClearService->delete_index_from_rule] Error parsing variable 'date_format' into regular expression. : {}", index_name));
+        let has_write_block_mode: bool = retention_metas.values().any(|retention_meta| {
+            retention_meta
+                .soft_deleted()
+                .as_ref()
+                .is_some_and(|soft_deleted| *soft_deleted.mode() == SoftDeleteMode::WriteBlock)
+        });
+
+        /* 쓰기 차단 방식으로 soft delete 된 인덱스가 있는 경우에만 세팅을 조회한다. */
+        let mut write_blocked_indexes: HashSet<String> = HashSet::new();
+
+        if has_write_block_mode {
+            let res: Value = es_conn
+                .get_index_write_blocks(target_index.index_name())
+                .await?;
+
+            if let Some(indices) = res.as_object() {
+                for (index_name, index_settings) in indices {
+                    let is_blocked: bool = match index_settings.pointer("/settings/index/blocks/write") {
+                        Some(Value::String(blocked)) => blocked == "true",
+                        Some(Value::Bool(blocked)) => *blocked,
+                        _ => false,
+                    };
+
+                    if is_blocked {
+                        write_blocked_indexes.insert(index_name.clone());
+                    }
+                }
             }
-        };
+        }
 
-        /* 현재 인덱스의 생성일자 파악 */
-        let parsed_date: NaiveDate = match NaiveDate::parse_from_str(&date_format, "%Y-%m-%d") {
-            Ok(parsed_date) => parsed_date,
-            Err(_e) => match NaiveDate::parse_from_str(&date_format, "%Y%m%d") {
-                Ok(parsed_date) => parsed_date,
+        let closed_indexes: HashSet<&str> = cat_indexes
+            .iter()
+            .filter(|cat_index| cat_index.status().as_deref() == Some("close"))
+            .map(|cat_index| cat_index.index().as_str())
+            .collect();
+
+        for (index_name, retention_meta) in retention_metas {
+            let soft_deleted: &SoftDeletedMeta = match retention_meta.soft_deleted() {
+                Some(soft_deleted) => soft_deleted,
+                None => continue,
+            };
+
+            let is_in_effect: bool = match soft_deleted.mode() {
+                SoftDeleteMode::Close => closed_indexes.contains(index_name.as_str()),
+                SoftDeleteMode::WriteBlock => write_blocked_indexes.contains(index_name),
+            };
+
+            if is_in_effect {
+                soft_deleted_times.insert(index_name.clone(), *soft_deleted.soft_deleted_at());
+            }
+        }
+
+        Ok(soft_deleted_times)
+    }
+
+    #[doc = "규칙의 인덱스들이 롤오버된 시각을 가져와주는 함수 (age_source = rollover_date 인 경우에만 조회)"]
+    /// # Arguments
+    /// * `es_conn` - Elasticsearch 커넥션
+    /// * `target_index` - 대상이 되는 인덱스 정보
+    ///
+    /// # Returns
+    /// * Result<HashMap<String, NaiveDateTime>, anyhow::Error> - 인덱스명 -> 롤오버 시각
+    async fn get_rule_rollover_times(
+        &self,
+        es_conn: &ElasticConnGuard,
+        target_index: &TargetIndex,
+    ) -> Result<HashMap<String, NaiveDateTime>, anyhow::Error> {
+        let mut rollover_times: HashMap<String, NaiveDateTime> = HashMap::new();
+
+        if *target_index.age_source() != AgeSource::RolloverDate {
+            return Ok(rollover_times);
+        }
+
+        let res: Value = es_conn
+            .get_index_rollover_info(target_index.index_name())
+            .await?;
+
+        if let Some(indices) = res["metadata"]["indices"].as_object() {
+            for (index_name, index_metadata) in indices {
+                /* alias 가 여러 개인 경우 가장 최근에 롤오버된 시각을 사용한다. */
+                let rollover_time: Option<NaiveDateTime> = index_metadata["rollover_info"]
+                    .as_object()
+                    .and_then(|rollover_info| {
+                        rollover_info
+                            .values()
+                            .filter_map(|info| info["time"].as_i64())
+                            .max()
+                    })
+                    .and_then(DateTime::from_timestamp_millis)
+                    .map(|datetime| datetime.naive_utc());
+
+                if let Some(rollover_time) = rollover_time {
+                    rollover_times.insert(index_name.to_string(), rollover_time);
+                }
+            }
+        }
+
+        Ok(rollover_times)
+    }
+
+    #[doc = "스냅샷에서 복원된 인덱스의 삭제 여부를 판단해주는 함수"]
+    /// # Arguments
+    /// * `target_index` - 인덱스가 속한 규칙
+    /// * `cat_index` - 복원된 인덱스 정보
+    /// * `restored_meta` - 복원 정보
+    /// * `cur_utc_datetime` - 현재 시각 (UTC)
+    ///
+    /// # Returns
+    /// * IndexDecision - 날짜가 없는 결정이므로 개수/용량/긴급 정리 대상에서 제외된다.
+    fn decide_restored_index(
+        &self,
+        target_index: &TargetIndex,
+        cat_index: &CatIndex,
+        restored_meta: &RestoredMeta,
+        cur_utc_datetime: NaiveDateTime,
+    ) -> IndexDecision {
+        let perserve_days_ago: NaiveDate = (cur_utc_datetime
+            - chrono::Duration::days(target_index.duration_days as i64))
+        .date();
+
+        let decision: IndexDecision = IndexDecision::new(
+            cat_index.index(),
+            target_index.index_name(),
+            perserve_days_ago,
+            cat_index.store_size_bytes(),
+            cat_index.docs_count_num(),
+        );
+
+        match restored_meta.expires_at() {
+            Some(expires_at) if expires_at.naive_utc() <= cur_utc_datetime => decision.with_action(
+                IndexAction::Delete,
+                format!(
+                    "restored index from {} expired at {} (ttl_days = {})",
+                    restored_meta.source(),
+                    expires_at.format("%Y-%m-%d %H:%M:%S"),
+                    restored_meta.ttl_days().unwrap_or(0)
+                ),
+            ),
+            Some(expires_at) => decision.with_action(
+                IndexAction::Keep,
+                format!(
+                    "restored index from {} is kept until {}",
+                    restored_meta.source(),
+                    expires_at.format("%Y-%m-%d %H:%M:%S")
+                ),
+            ),
+            None => decision.with_action(
+                IndexAction::Keep,
+                format!(
+                    "restored index from {} is ignored by retention rules",
+                    restored_meta.source()
+                ),
+            ),
+        }
+    }
+
+    #[doc = "인덱스가 들어있는 가장 최근의 성공한 스냅샷을 찾아주는 함수"]
+    /// # Arguments
+    /// * `es_conn` - Elasticsearch 커넥션
+    /// * `index_name` - 찾을 인덱스 명
+    /// * `repositories` - 찾아볼 스냅샷 저장소 목록
+    /// * `snapshot_name` - 지정한 스냅샷 명 (None 인 경우 전체 스냅샷에서 찾는다.)
+    ///
+    /// # Returns
+    /// * Result<Option<(String, String)>, anyhow::Error> - (저장소 명, 스냅샷 명)
+    async fn find_index_snapshot(
+        &self,
+        es_conn: &ElasticConnGuard,
+        index_name: &str,
+        repositories: &[String],
+        snapshot_name: Option<&str>,
+    ) -> Result<Option<(String, String)>, anyhow::Error> {
+        /* (종료 시각, 저장소 명, 스냅샷 명) */
+        let mut latest_snapshot: Option<(i64, String, String)> = None;
+
+        for repository in repositories {
+            let res: Value = match es_conn
+                .get_snapshot(repository, snapshot_name.unwrap_or("*"))
+                .await
+            {
+                Ok(res) => res,
                 Err(e) => {
-                    return Err(anyhow!("[Parsing Error][delete_cluster_index()] An error occurred while converting 'parsed_date' data. // date_format: {:?}, {:?}", date_format, e));
+                    error!(
+                        "[Error][IndexClearService->find_index_snapshot] Failed to list snapshots in '{}': {:?}",
+                        repository, e
+                    );
+                    continue;
                 }
-            },
-        };
+            };
+
+            let snapshots: &[Value] = res["snapshots"]
+                .as_array()
+                .map(|snapshots| snapshots.as_slice())
+                .unwrap_or_default();
+
+            for snapshot in snapshots {
+                let has_index: bool = snapshot["indices"]
+                    .as_array()
+                    .is_some_and(|indices| indices.iter().any(|index| index == index_name));
+
+                if !has_index || snapshot["state"] != "SUCCESS" {
+                    continue;
+                }
+
+                let end_time: i64 = snapshot["end_time_in_millis"].as_i64().unwrap_or(0);
+                let name: &str = snapshot["snapshot"].as_str().unwrap_or_default();
+
+                if latest_snapshot
+                    .as_ref()
+                    .is_none_or(|(latest_end_time, _, _)| end_time > *latest_end_time)
+                {
+                    latest_snapshot = Some((end_time, repository.clone(), name.to_string()));
+                }
+            }
+        }
+
+        Ok(latest_snapshot.map(|(_, repository, name)| (repository, name)))
+    }
+
+    #[doc = "보존(KEEP)되는 인덱스 중 나이가 규칙의 단계(phases)에 도달한 인덱스에 단계를 적용해주는 함수"]
+    /// # Arguments
+    /// * `target_index` - 대상이 되는 인덱스 정보
+    /// * `index_plan` - 규칙의 삭제 계획
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error> - 단계 적용에 실패한 인덱스가 있다면 나머지를 모두 시도한 뒤 에러를 반환한다.
+    ///
+    /// 현재 세팅과 세그먼트 수를 먼저 확인하므로, 이미 적용된 동작은 다시 하지 않는다.
+    async fn apply_index_phases(
+        &self,
+        target_index: &TargetIndex,
+        index_plan: &[IndexDecision],
+    ) -> Result<(), anyhow::Error> {
+        if target_index.phases().is_empty() {
+            return Ok(());
+        }
+
+        let cur_utc_time: NaiveDateTime = get_currnet_utc_naivedatetime();
+
+        /* soft delete 된 인덱스와 날짜를 알 수 없는 인덱스는 제외한다. */
+        let phase_targets: Vec<(&IndexDecision, PhaseTarget)> = index_plan
+            .iter()
+            .filter(|decision| {
+                decision.action == IndexAction::Keep && decision.soft_deleted_at.is_none()
+            })
+            .filter_map(|decision| {
+                decision
+                    .index_time
+                    .and_then(|index_time| {
+                        PhaseTarget::from_phases(target_index.phases(), index_time, cur_utc_time)
+                    })
+                    .map(|phase_target| (decision, phase_target))
+            })
+            .collect();
+
+        if phase_targets.is_empty() {
+            return Ok(());
+        }
+
+        /* Elasitcsearch 커넥션 */
+        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+
+        let index_settings: Value = es_conn
+            .get_index_flat_settings(target_index.index_name())
+            .await?;
+
+        let segment_counts: Value = if phase_targets
+            .iter()
+            .any(|(_, phase_target)| phase_target.forcemerge_segments.is_some())
+        {
+            es_conn
+                .get_index_segment_counts(target_index.index_name())
+                .await?
+        } else {
+            Value::Null
+        };
+
+        let mut failed_indexes: Vec<&str> = Vec::new();
+
+        for (decision, phase_target) in &phase_targets {
+            let index_name: &str = decision.index_name();
+            let settings: &Value = &index_settings[index_name]["settings"];
+
+            /* 계획 이후에 삭제된 인덱스 */
+            if settings.is_null() {
+                continue;
+            }
+
+            if let Err(e) = self
+                .apply_phase_target(
+                    &es_conn,
+                    target_index,
+                    index_name,
+                    phase_target,
+                    settings,
+                    &segment_counts["indices"][index_name],
+                )
+                .await
+            {
+                error!("[Error][IndexClearService->apply_index_phases] {:?}", e);
+                failed_indexes.push(index_name);
+            }
+        }
+
+        if !failed_indexes.is_empty() {
+            return Err(anyhow!(
+                "[Error][IndexClearService->apply_index_phases] Failed to apply phases to {} indices: [{}]",
+                failed_indexes.len(),
+                failed_indexes.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[doc = "단계가 요구하는 세팅 중에서 인덱스의 현재 세팅과 다른 부분만 골라주는 함수"]
+    /// # Arguments
+    /// * `phase_target` - 인덱스가 가져야 할 상태
+    /// * `settings` - 인덱스의 현재 세팅 (flat_settings)
+    ///
+    /// # Returns
+    /// * serde_json::Map<String, Value> - 바꿔야 할 세팅 (이미 적용된 경우 비어있음)
+    fn diff_phase_settings(
+        &self,
+        phase_target: &PhaseTarget,
+        settings: &Value,
+    ) -> serde_json::Map<String, Value> {
+        let mut settings_update: serde_json::Map<String, Value> = serde_json::Map::new();
+
+        if let Some(replicas) = phase_target.replicas() {
+            if settings["index.number_of_replicas"].as_str() != Some(replicas.to_string().as_str()) {
+                settings_update.insert(String::from("index.number_of_replicas"), json!(replicas));
+            }
+        }
+
+        for (attribute, value) in phase_target.require_attributes() {
+            let setting_key: String = format!("index.routing.allocation.require.{}", attribute);
+
+            if settings[&setting_key].as_str() != Some(value.as_str()) {
+                settings_update.insert(setting_key, json!(value));
+            }
+        }
+
+        settings_update
+    }
+
+    #[doc = "샤드 복제본 중 가장 많은 세그먼트 수를 구해주는 함수"]
+    /// # Arguments
+    /// * `segment_counts` - 인덱스의 샤드별 세그먼트 수 (닫힌 인덱스는 Null)
+    ///
+    /// # Returns
+    /// * Option<u64> - 세그먼트 정보가 없으면 None
+    fn max_segment_count(&self, segment_counts: &Value) -> Option<u64> {
+        segment_counts["shards"].as_object().map(|shards| {
+            shards
+                .values()
+                .filter_map(|shard_copies| shard_copies.as_array())
+                .flatten()
+                .filter_map(|shard_copy| shard_copy["segments"]["count"].as_u64())
+                .max()
+                .unwrap_or(0)
+        })
+    }
+
+    #[doc = "인덱스 하나의 현재 상태를 단계가 요구하는 상태와 비교해서, 다른 부분만 적용해주는 함수"]
+    /// # Arguments
+    /// * `es_conn` - Elasticsearch 커넥션
+    /// * `target_index` - 대상이 되는 인덱스 정보
+    /// * `index_name` - 인덱스 명
+    /// * `phase_target` - 인덱스가 가져야 할 상태
+    /// * `settings` - 인덱스의 현재 세팅 (flat_settings)
+    /// * `segment_counts` - 인덱스의 샤드별 세그먼트 수 (닫힌 인덱스는 Null)
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn apply_phase_target(
+        &self,
+        es_conn: &ElasticConnGuard,
+        target_index: &TargetIndex,
+        index_name: &str,
+        phase_target: &PhaseTarget,
+        settings: &Value,
+        segment_counts: &Value,
+    ) -> Result<(), anyhow::Error> {
+        let metrics: &RetentionMetrics = get_retention_metrics();
+        let rule_name: &str = target_index.index_name();
+
+        /* 레플리카 수와 노드 속성은 세팅 한 번으로 바꿔준다. */
+        let settings_update: serde_json::Map<String, Value> =
+            self.diff_phase_settings(phase_target, settings);
+
+        if !settings_update.is_empty() {
+            let settings_update: Value = Value::Object(settings_update);
+
+            es_conn
+                .update_index_settings(index_name, &settings_update)
+                .await?;
+
+            metrics
+                .phase_actions
+                .with_label_values(&[rule_name, "settings"])
+                .inc();
+
+            info!(
+                "[IndexClearService->apply_phase_target] {} (phase after {} days) settings updated: {}",
+                index_name,
+                phase_target.after_days(),
+                settings_update
+            );
+        }
+
+        if let Some(forcemerge_segments) = phase_target.forcemerge_segments() {
+            let max_segment_count: Option<u64> = self.max_segment_count(segment_counts);
+
+            if max_segment_count.is_some_and(|count| count > *forcemerge_segments as u64) {
+                es_conn
+                    .forcemerge_index(index_name, *forcemerge_segments)
+                    .await?;
+
+                metrics
+                    .phase_actions
+                    .with_label_values(&[rule_name, "forcemerge"])
+                    .inc();
+
+                info!(
+                    "[IndexClearService->apply_phase_target] {} (phase after {} days) force merged to {} segments per shard. (was {})",
+                    index_name,
+                    phase_target.after_days(),
+                    forcemerge_segments,
+                    max_segment_count.unwrap_or_default()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[doc = "인덱스에 soft delete 표시를 남기고, 인덱스를 닫거나 쓰기를 막아주는 함수"]
+    /// # Arguments
+    /// * `decision` - soft delete 할 인덱스 결정
+    /// * `run_id` - 실행 ID
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    ///
+    /// 닫힌 인덱스는 매핑을 바꿀 수 없으므로 표시를 먼저 남긴다.
+    /// 표시만 남고 닫기에 실패한 경우에는 soft delete 된 것으로 보지 않으므로 다음 실행에서 다시 시도된다.
+    async fn soft_delete_index(
+        &self,
+        decision: &IndexDecision,
+        run_id: &str,
+    ) -> Result<(), anyhow::Error> {
+        let index_name: &str = decision.index_name();
+        let mode: SoftDeleteMode = decision.soft_delete_mode().unwrap_or_default();
+
+        /* 기존 _meta 는 유지하고 soft delete 표시만 추가해준다. */
+        let res: Value = get_elastic_guard_conn()
+            .await?
+            .get_index_meta(index_name)
+            .await?;
+        let mut meta: Value = res
+            .pointer(&format!("/{}/mappings/_meta", index_name))
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+
+        let soft_deleted_meta: SoftDeletedMeta =
+            SoftDeletedMeta::new(Utc::now(), mode, run_id.to_string());
+
+        meta[RETENTION_META_KEY]["soft_deleted"] = serde_json::to_value(&soft_deleted_meta)?;
+
+        get_elastic_guard_conn()
+            .await?
+            .put_index_meta(index_name, &meta)
+            .await?;
+
+        /* Elasitcsearch 커넥션 */
+        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+
+        match mode {
+            SoftDeleteMode::Close => es_conn.close_index(index_name).await,
+            SoftDeleteMode::WriteBlock => es_conn.add_write_block(index_name).await,
+        }
+    }
+
+    #[doc = "인덱스의 모든 문서를 point in time + search_after 로 읽어서 압축된 NDJSON 아카이브로 내보내주는 함수"]
+    /// # Arguments
+    /// * `decision` - 삭제할 인덱스 결정
+    /// * `archive_target` - 아카이브 위치와 압축 방식
+    /// * `run_id` - 실행 ID
+    ///
+    /// # Returns
+    /// * Result<String, anyhow::Error> - 아카이브 디렉토리, 아카이브 문서 수가 _count 와 다르면 에러
+    ///
+    /// `<archive_dir>/<인덱스 명>/` 에 데이터 파일, 매핑/세팅 파일, 매니페스트를 남긴다.
+    /// 매니페스트는 검증이 끝난 뒤 마지막에 쓰므로, 매니페스트가 없는 아카이브는 완성되지 않은 것이다.
+    async fn archive_index(
+        &self,
+        decision: &IndexDecision,
+        archive_target: &ArchiveTarget,
+        run_id: &str,
+    ) -> Result<String, anyhow::Error> {
+        let index_name: &str = decision.index_name();
+        let index_dir: PathBuf = archive_target.index_dir(index_name);
+
+        std::fs::create_dir_all(&index_dir).map_err(|e| {
+            anyhow!(
+                "[Error][IndexClearService->archive_index] Failed to create '{}': {:?}",
+                index_dir.display(),
+                e
+            )
+        })?;
+
+        /* 이전 시도의 매니페스트는 지워서, 이번 아카이브가 끝나기 전까지 미완성으로 보이게 한다. */
+        let manifest_path: PathBuf = index_dir.join(ARCHIVE_MANIFEST_FILE);
+
+        if manifest_path.exists() {
+            std::fs::remove_file(&manifest_path)?;
+        }
+
+        /* 매핑과 세팅 */
+        let definition: Value = get_elastic_guard_conn()
+            .await?
+            .get_index_definition(index_name)
+            .await?;
+        let definition_file: String = format!("{}.definition.json", index_name);
+
+        write_json_to_file(
+            &index_dir.join(&definition_file).to_string_lossy(),
+            &definition[index_name],
+        )?;
+
+        /* 문서 */
+        let data_file: String = format!(
+            "{}.ndjson.{}",
+            index_name,
+            archive_target.compression().extension()
+        );
+        let data_path: PathBuf = index_dir.join(&data_file);
+
+        let mut pit_id: String = get_elastic_guard_conn()
+            .await?
+            .open_point_in_time(index_name, ARCHIVE_PIT_KEEP_ALIVE)
+            .await?;
+
+        let export_result: Result<u64, anyhow::Error> = async {
+            let mut writer: ArchiveWriter =
+                ArchiveWriter::create(&data_path, *archive_target.compression())?;
+            let mut search_after: Option<Value> = None;
+            let mut doc_count: u64 = 0;
+
+            loop {
+                let mut res: Value = get_elastic_guard_conn()
+                    .await?
+                    .search_after_page(
+                        &pit_id,
+                        ARCHIVE_PIT_KEEP_ALIVE,
+                        search_after.as_ref(),
+                        ARCHIVE_PAGE_SIZE,
+                    )
+                    .await?;
+
+                if let Some(new_pit_id) = res["pit_id"].as_str() {
+                    pit_id = new_pit_id.to_string();
+                }
+
+                let hits: Vec<Value> = match res["hits"]["hits"].take() {
+                    Value::Array(hits) => hits,
+                    _ => Vec::new(),
+                };
+
+                if hits.is_empty() {
+                    break;
+                }
+
+                for hit in &hits {
+                    let line: Vec<u8> = serde_json::to_vec(&json!({
+                        "_id": hit["_id"],
+                        "_source": hit["_source"]
+                    }))?;
+
+                    writer.write_line(&line)?;
+                    doc_count += 1;
+                }
+
+                search_after = hits.last().map(|hit| hit["sort"].clone());
+            }
+
+            writer.finish()?;
+
+            Ok(doc_count)
+        }
+        .await;
+
+        let close_result: Result<(), anyhow::Error> = async {
+            get_elastic_guard_conn()
+                .await?
+                .close_point_in_time(&pit_id)
+                .await
+        }
+        .await;
+
+        if let Err(e) = close_result {
+            error!(
+                "[Error][IndexClearService->archive_index] Failed to close point in time of {}: {:?}",
+                index_name, e
+            );
+        }
+
+        let doc_count: u64 = export_result?;
+        let expected_doc_count: u64 = get_elastic_guard_conn()
+            .await?
+            .count_index(index_name)
+            .await?;
+
+        if doc_count != expected_doc_count {
+            return Err(anyhow!(
+                "[Error][IndexClearService->archive_index] Archived {} documents of {}, but _count is {}",
+                doc_count,
+                index_name,
+                expected_doc_count
+            ));
+        }
+
+        let manifest: ArchiveManifest = ArchiveManifest {
+            index_name: index_name.to_string(),
+            rule: decision.rule().clone(),
+            run_id: run_id.to_string(),
+            created_at: Utc::now(),
+            parsed_date: *decision.parsed_date(),
+            compression: *archive_target.compression(),
+            data_file,
+            definition_file,
+            doc_count,
+            sha256: sha256_file(&data_path)?,
+        };
+
+        write_json_to_file(&manifest_path.to_string_lossy(), &manifest)?;
+
+        info!(
+            "[IndexClearService->archive_index] Archived {} documents of {} into '{}'",
+            doc_count,
+            index_name,
+            index_dir.display()
+        );
+
+        Ok(index_dir.display().to_string())
+    }
+
+    #[doc = "아카이브의 매핑/세팅으로 새 인덱스를 만들 때 사용할 요청 본문을 만들어주는 함수"]
+    /// # Arguments
+    /// * `definition` - 아카이브의 매핑과 세팅 ({ "aliases": .., "mappings": .., "settings": .. })
+    /// * `restored_meta` - 매핑 _meta 에 남길 복원 정보
+    ///
+    /// # Returns
+    /// * Value - { "settings": .., "mappings": .. } (별칭은 원래 인덱스와 충돌할 수 있으므로 가져오지 않는다.)
+    fn build_import_index_body(&self, definition: &Value, restored_meta: &RestoredMeta) -> Value {
+        let mut index_settings: Value = definition["settings"]["index"].clone();
+
+        if let Some(index_settings) = index_settings.as_object_mut() {
+            for excluded_setting in IMPORT_EXCLUDED_INDEX_SETTINGS {
+                index_settings.remove(excluded_setting);
+            }
+        }
+
+        let mut mappings: Value = match &definition["mappings"] {
+            Value::Object(mappings) => Value::Object(mappings.clone()),
+            _ => json!({}),
+        };
+
+        /* 원래 인덱스의 보존기한 정보는 버리고 복원 정보만 남긴다. */
+        mappings["_meta"][RETENTION_META_KEY] = json!({ "restored": restored_meta });
+
+        json!({
+            "settings": { "index": index_settings },
+            "mappings": mappings
+        })
+    }
+
+    #[doc = "아카이브 문서 묶음을 _bulk 로 넣어주는 함수. 요청이 실패하거나 429/503 으로 거절된 문서는 재시도한다."]
+    /// # Arguments
+    /// * `index_name` - 문서를 넣을 인덱스 명
+    /// * `docs` - 아카이브의 문서 ({"_id": .., "_source": ..}) 목록
+    ///
+    /// # Returns
+    /// * Result<u64, anyhow::Error> - 들어간 문서 수
+    ///
+    /// 문서의 _id 를 그대로 사용하므로 같은 문서를 다시 보내도 중복되지 않는다.
+    async fn import_bulk_batch(&self, index_name: &str, docs: Vec<Value>) -> Result<u64, anyhow::Error> {
+        let mut pending_docs: Vec<Value> = docs;
+        let mut imported_count: u64 = 0;
+        let mut retry_delay: Duration = IMPORT_BULK_RETRY_DELAY;
+
+        for attempt in 0..=IMPORT_BULK_MAX_RETRIES {
+            if attempt > 0 {
+                tokio::time::sleep(retry_delay).await;
+                retry_delay *= 2;
+            }
+
+            let lines: Vec<Value> = pending_docs
+                .iter()
+                .flat_map(|doc| {
+                    let action: Value = match doc.get("_id") {
+                        Some(id) if !id.is_null() => json!({ "index": { "_id": id } }),
+                        _ => json!({ "index": {} }),
+                    };
+
+                    [action, doc["_source"].clone()]
+                })
+                .collect();
+
+            let bulk_result: Result<Value, anyhow::Error> = async {
+                let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+                es_conn.bulk_index(index_name, &lines).await
+            }
+            .await;
+
+            let res: Value = match bulk_result {
+                Ok(res) => res,
+                Err(e) => {
+                    error!(
+                        "[Error][IndexClearService->import_bulk_batch] _bulk into {} failed (attempt {}/{}): {:?}",
+                        index_name,
+                        attempt + 1,
+                        IMPORT_BULK_MAX_RETRIES + 1,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            let items: &Vec<Value> = res["items"]
+                .as_array()
+                .filter(|items| items.len() == pending_docs.len())
+                .ok_or_else(|| {
+                    anyhow!(
+                        "[Error][IndexClearService->import_bulk_batch] Invalid _bulk response for {}",
+                        index_name
+                    )
+                })?;
+
+            let mut rejected_docs: Vec<Value> = Vec::new();
+
+            for (doc, item) in pending_docs.into_iter().zip(items) {
+                let status: u64 = item["index"]["status"].as_u64().unwrap_or_default();
+
+                if (200..300).contains(&status) {
+                    imported_count += 1;
+                } else if matches!(status, 429 | 503) {
+                    rejected_docs.push(doc);
+                } else {
+                    return Err(anyhow!(
+                        "[Error][IndexClearService->import_bulk_batch] Failed to import document {} into {}: {}",
+                        doc["_id"],
+                        index_name,
+                        item["index"]["error"]
+                    ));
+                }
+            }
+
+            if rejected_docs.is_empty() {
+                return Ok(imported_count);
+            }
+
+            error!(
+                "[Error][IndexClearService->import_bulk_batch] {} documents were rejected by {} (attempt {}/{})",
+                rejected_docs.len(),
+                index_name,
+                attempt + 1,
+                IMPORT_BULK_MAX_RETRIES + 1
+            );
+
+            pending_docs = rejected_docs;
+        }
+
+        Err(anyhow!(
+            "[Error][IndexClearService->import_bulk_batch] {} documents could not be imported into {} after {} retries",
+            pending_docs.len(),
+            index_name,
+            IMPORT_BULK_MAX_RETRIES
+        ))
+    }
+
+    #[doc = "인덱스를 스냅샷 저장소에 스냅샷으로 남기고, 스냅샷이 성공할 때까지 기다려주는 함수"]
+    /// # Arguments
+    /// * `decision` - 삭제할 인덱스 결정
+    /// * `repository` - 스냅샷 저장소 명
+    /// * `run_id` - 실행 ID (스냅샷 명과 메타데이터에 사용)
+    ///
+    /// # Returns
+    /// * Result<String, anyhow::Error> - "<저장소>/<스냅샷>", 스냅샷이 SUCCESS 가 아니면 에러
+    async fn snapshot_index(
+        &self,
+        decision: &IndexDecision,
+        repository: &str,
+        run_id: &str,
+    ) -> Result<String, anyhow::Error> {
+        let index_name: &str = decision.index_name();
+        let snapshot_name: String = format!("{}-{}", index_name, run_id).to_lowercase();
+
+        let metadata: Value = json!({
+            "taken_by": "yummy-indexing-delete",
+            "run_id": run_id,
+            "rule": decision.rule(),
+            "index": index_name,
+            "parsed_date": decision.parsed_date(),
+        });
+
+        get_elastic_guard_conn()
+            .await?
+            .create_snapshot(repository, &snapshot_name, index_name, &metadata)
+            .await?;
+
+        info!(
+            "[IndexClearService->snapshot_index] Snapshot '{}/{}' of {} started.",
+            repository, snapshot_name, index_name
+        );
+
+        let wait_start: Instant = Instant::now();
+
+        loop {
+            /* 기다리는 동안에는 커넥션을 들고 있지 않는다. */
+            let res: Value = get_elastic_guard_conn()
+                .await?
+                .get_snapshot(repository, &snapshot_name)
+                .await?;
+            let snapshot: &Value = &res["snapshots"][0];
+
+            /* 상태를 알 수 없는 스냅샷은 성공한 것으로도, 진행중인 것으로도 보지 않는다. */
+            let state: &str = snapshot["state"].as_str().ok_or_else(|| {
+                anyhow!(
+                    "[Error][IndexClearService->snapshot_index] Snapshot '{}/{}' of {} has no state: {}",
+                    repository,
+                    snapshot_name,
+                    index_name,
+                    res
+                )
+            })?;
+
+            match state {
+                "SUCCESS" => {
+                    info!(
+                        "[IndexClearService->snapshot_index] Snapshot '{}/{}' of {} succeeded.",
+                        repository, snapshot_name, index_name
+                    );
+                    return Ok(format!("{}/{}", repository, snapshot_name));
+                }
+                "IN_PROGRESS" => {}
+                "FAILED" | "PARTIAL" | "INCOMPATIBLE" => {
+                    return Err(anyhow!(
+                        "[Error][IndexClearService->snapshot_index] Snapshot '{}/{}' of {} finished with state {}: {}",
+                        repository,
+                        snapshot_name,
+                        index_name,
+                        state,
+                        snapshot["failures"]
+                    ));
+                }
+                state => {
+                    return Err(anyhow!(
+                        "[Error][IndexClearService->snapshot_index] Snapshot '{}/{}' of {} has an unknown state {}",
+                        repository,
+                        snapshot_name,
+                        index_name,
+                        state
+                    ));
+                }
+            }
+
+            if wait_start.elapsed() >= SNAPSHOT_WAIT_TIMEOUT {
+                return Err(anyhow!(
+                    "[Error][IndexClearService->snapshot_index] Timed out after {:?} waiting for snapshot '{}/{}' of {}",
+                    SNAPSHOT_WAIT_TIMEOUT,
+                    repository,
+                    snapshot_name,
+                    index_name
+                ));
+            }
+
+            tokio::time::sleep(SNAPSHOT_POLL_INTERVAL).await;
+        }
+    }
+}
+
+#[async_trait]
+impl IndexClearService for IndexClearServicePub {
+    #[doc = "이미 세운 규칙의 계획대로 인덱스를 삭제하고 단계(phase)를 적용해주는 함수 - 안전 상한은 호출하는 쪽에서 확인한다."]
+    /// # Arguments
+    /// * `target_index` - 대상이 되는 인덱스 정보
+    /// * `index_plan` - 규칙의 전체 계획 (`build_index_plan` 의 결과)
+    /// * `run_id` - 감사 로그에 남길 실행 ID
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn run_index_plan(
+        &self,
+        target_index: &TargetIndex,
+        index_plan: &[IndexDecision],
+        run_id: &str,
+    ) -> Result<(), anyhow::Error> {
+        let run_start: Instant = Instant::now();
+        let rule_name: &str = target_index.index_name();
+        let metrics: &RetentionMetrics = get_retention_metrics();
+
+        let result: Result<(), anyhow::Error> = async {
+            metrics
+                .indices_matched
+                .with_label_values(&[rule_name])
+                .inc_by(index_plan.len() as u64);
+
+            for decision in index_plan {
+                /* 리갈 홀드가 걸린 인덱스는 삭제 대상이 아니더라도 매 실행마다 남긴다. */
+                for protection in decision.protections() {
+                    if let IndexProtection::LegalHold(legal_hold) = protection {
+                        info!(
+                            "[IndexClearService->run_index_plan] {} is under {}",
+                            decision.index_name(),
+                            legal_hold
+                        );
+                    }
+                }
+
+                if decision.action != IndexAction::Skip {
+                    continue;
+                }
+
+                metrics.indices_skipped.with_label_values(&[rule_name]).inc();
+
+                if decision.protections().is_empty() {
+                    error!(
+                        "[Error][IndexClearService->run_index_plan] {}",
+                        decision.reason()
+                    );
+                } else {
+                    info!(
+                        "[IndexClearService->run_index_plan] {} is not deleted: {}",
+                        decision.index_name(),
+                        decision.reason()
+                    );
+                }
+            }
+
+            /* 삭제에 실패하더라도 남은 인덱스의 단계는 적용한다. */
+            let delete_result: Result<(), anyhow::Error> =
+                self.delete_planned_indexes(index_plan, run_id).await;
+            let phase_result: Result<(), anyhow::Error> =
+                self.apply_index_phases(target_index, index_plan).await;
+
+            delete_result.and(phase_result)
+        }
+        .await;
+
+        metrics
+            .run_duration
+            .with_label_values(&[rule_name])
+            .observe(run_start.elapsed().as_secs_f64());
+
+        if result.is_ok() {
+            metrics
+                .last_success_timestamp
+                .with_label_values(&[rule_name])
+                .set(Utc::now().timestamp() as f64);
+        }
+
+        result
+    }
+
+    #[doc = "계획에서 삭제(DELETE)로 판단된 인덱스들을 삭제하고, 삭제/건너뜀 결과를 감사 로그에 남겨주는 함수"]
+    /// # Arguments
+    /// * `index_plan` - 삭제 계획
+    /// * `run_id` - 감사 로그에 남길 실행 ID
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error> - 삭제 또는 감사 로그 기록에 실패한 인덱스가 있다면 나머지를 모두 시도한 뒤 에러를 반환한다.
+    async fn delete_planned_indexes(
+        &self,
+        index_plan: &[IndexDecision],
+        run_id: &str,
+    ) -> Result<(), anyhow::Error> {
+        /* 아카이브/스냅샷은 오래 걸리므로 커넥션은 요청마다 빌려서 다른 규칙의 작업을 막지 않는다. */
+        let metrics: &RetentionMetrics = get_retention_metrics();
+
+        let mut failed_indexes: Vec<&str> = Vec::new();
+        let mut unaudited_indexes: Vec<&str> = Vec::new();
+
+        for decision in index_plan {
+            let rule_name: &str = decision.rule();
+
+            let audit_entry: AuditEntry = match decision.action {
+                IndexAction::Keep => continue,
+                IndexAction::Skip => {
+                    AuditEntry::from_decision(run_id, decision, AuditOutcome::Skipped, None)
+                }
+                /* 인덱스 삭제 - 아카이브/스냅샷이 설정된 경우 모두 성공해야만 삭제(soft delete)한다. */
+                IndexAction::Delete | IndexAction::SoftDelete => {
+                    let mut snapshot: Option<String> = None;
+                    let mut archive: Option<String> = None;
+
+                    let delete_result: Result<(), anyhow::Error> = async {
+                        if let Some(archive_target) = decision.archive_target() {
+                            archive = Some(
+                                self.archive_index(decision, archive_target, run_id)
+                                    .await?,
+                            );
+                        }
+
+                        if let Some(repository) = decision.snapshot_repository() {
+                            snapshot = Some(
+                                self.snapshot_index(decision, repository, run_id)
+                                    .await?,
+                            );
+                        }
+
+                        match decision.action {
+                            IndexAction::SoftDelete => {
+                                self.soft_delete_index(decision, run_id).await
+                            }
+                            _ => {
+                                get_elastic_guard_conn()
+                                    .await?
+                                    .delete_index(decision.index_name())
+                                    .await
+                            }
+                        }
+                    }
+                    .await;
+
+                    match delete_result {
+                        Ok(_) if decision.action == IndexAction::SoftDelete => {
+                            metrics
+                                .indices_soft_deleted
+                                .with_label_values(&[rule_name])
+                                .inc();
+
+                            info!(
+                                "{} has been soft-deleted. ({:?})",
+                                decision.index_name(),
+                                decision.soft_delete_mode().unwrap_or_default()
+                            );
+                            AuditEntry::from_decision(
+                                run_id,
+                                decision,
+                                AuditOutcome::SoftDeleted,
+                                None,
+                            )
+                            .with_backup(snapshot, archive)
+                        }
+                        Ok(_) => {
+                            metrics.indices_deleted.with_label_values(&[rule_name]).inc();
+                            metrics
+                                .bytes_freed
+                                .with_label_values(&[rule_name])
+                                .inc_by(decision.store_size.unwrap_or(0));
+                            metrics
+                                .last_delete_timestamp
+                                .with_label_values(&[rule_name])
+                                .set(Utc::now().timestamp() as f64);
+
+                            info!("{} has been successfully deleted.", decision.index_name());
+                            AuditEntry::from_decision(run_id, decision, AuditOutcome::Deleted, None)
+                                .with_backup(snapshot, archive)
+                        }
+                        Err(e) => {
+                            metrics
+                                .indices_delete_failed
+                                .with_label_values(&[rule_name])
+                                .inc();
+
+                            error!("[Error][IndexClearService->delete_planned_indexes] {:?}", e);
+                            failed_indexes.push(decision.index_name());
+                            AuditEntry::from_decision(
+                                run_id,
+                                decision,
+                                AuditOutcome::Failed,
+                                Some(format!("{:#}", e)),
+                            )
+                            .with_backup(snapshot, archive)
+                        }
+                    }
+                }
+            };
+
+            if let Err(e) = write_audit_entry(&audit_entry).await {
+                error!("[Error][IndexClearService->delete_planned_indexes] Failed to write audit entry for {}: {:?}", decision.index_name(), e);
+                unaudited_indexes.push(decision.index_name());
+            }
+        }
+
+        if !failed_indexes.is_empty() || !unaudited_indexes.is_empty() {
+            return Err(anyhow!(
+                "[Error][IndexClearService->delete_planned_indexes] Failed to delete {} indices: [{}], failed to audit {} indices: [{}]",
+                failed_indexes.len(),
+                failed_indexes.join(", "),
+                unaudited_indexes.len(),
+                unaudited_indexes.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[doc = "디스크 사용률이 상한을 넘은 경우, 목표 사용률 아래로 내려갈 때까지 전체 규칙에서 삭제할 인덱스를 골라주는 함수"]
+    /// # Arguments
+    /// * `target_indexes` - 인덱스 보존 규칙 리스트
+    /// * `emergency_config` - 긴급 정리 설정
+    ///
+    /// # Returns
+    /// * Result<EmergencyPlan, anyhow::Error>
+    async fn build_emergency_plan(
+        &self,
+        target_indexes: &[TargetIndex],
+        emergency_config: &EmergencyConfig,
+    ) -> Result<EmergencyPlan, anyhow::Error> {
+        /* Elasitcsearch 커넥션 */
+        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+
+        let res: Value = es_conn.get_disk_allocation().await?;
+        let cat_allocations: Vec<CatAllocation> = serde_json::from_value(res).map_err(|e| {
+            anyhow!(
+                "[Error][IndexClearService->build_emergency_plan] Failed to parse _cat/allocation response: {:?}",
+                e
+            )
+        })?;
+
+        let disk_usage: ClusterDiskUsage = ClusterDiskUsage::new(cat_allocations);
+        let used_percent: f64 = disk_usage.max_used_percent();
+
+        for node in disk_usage.nodes() {
+            info!(
+                "[IndexClearService->build_emergency_plan] node '{}' disk used: {} / {}",
+                node.node(),
+                format_byte_size(*node.used()),
+                format_byte_size(*node.total())
+            );
+        }
+
+        info!(
+            "[IndexClearService->build_emergency_plan] Max node disk usage: {:.1}% (high: {}%, target: {}%)",
+            used_percent,
+            emergency_config.high_disk_percent(),
+            emergency_config.target_disk_percent()
+        );
+
+        if used_percent <= *emergency_config.high_disk_percent() {
+            return Ok(EmergencyPlan::new(used_percent, used_percent, vec![], vec![]));
+        }
+
+        let cur_utc_time: NaiveDateTime = get_currnet_utc_naivedatetime();
+        let mut rule_plans: Vec<(&TargetIndex, Vec<IndexDecision>)> = Vec::new();
+
+        for target_index in target_indexes {
+            let rule_plan: Vec<IndexDecision> =
+                self.compute_rule_plan(&es_conn, target_index).await?;
+            rule_plans.push((target_index, rule_plan));
+        }
+
+        let emergency_plan: EmergencyPlan =
+            self.select_emergency_indexes(&disk_usage, emergency_config, rule_plans, cur_utc_time);
+
+        if emergency_plan.projected_percent >= *emergency_config.target_disk_percent() {
+            error!(
+                "[Error][IndexClearService->build_emergency_plan] Disk usage is expected to stay at {:.1}% even after deleting every eligible index.",
+                emergency_plan.projected_percent
+            );
+        }
+
+        Ok(emergency_plan)
+    }
+
+    #[doc = "스냅샷에서 삭제된 인덱스를 다른 이름으로 복원하고, 보존기한 규칙이 복원 정보로 판단하도록 _meta 에 표시해주는 함수"]
+    /// # Arguments
+    /// * `index_name` - 복원할 (삭제된) 인덱스 명
+    /// * `repositories` - 찾아볼 스냅샷 저장소 목록
+    /// * `snapshot_name` - 지정한 스냅샷 명 (None 인 경우 인덱스가 들어있는 가장 최근 스냅샷)
+    /// * `restored_index_name` - 복원될 인덱스 명
+    /// * `ttl_days` - 복원 후 보존할 일수 (None 인 경우 보존기한 규칙이 삭제하지 않는다.)
+    ///
+    /// # Returns
+    /// * Result<String, anyhow::Error> - 복원에 사용한 스냅샷 ("<저장소>/<스냅샷>")
+    async fn restore_index(
+        &self,
+        index_name: &str,
+        repositories: &[String],
+        snapshot_name: Option<&str>,
+        restored_index_name: &str,
+        ttl_days: Option<u32>,
+    ) -> Result<String, anyhow::Error> {
+        /* Elasitcsearch 커넥션 */
+        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+
+        let (repository, snapshot) = self
+            .find_index_snapshot(&es_conn, index_name, repositories, snapshot_name)
+            .await?
+            .ok_or_else(|| {
+                anyhow!(
+                    "[Error][IndexClearService->restore_index] No successful snapshot containing '{}' was found in [{}]",
+                    index_name,
+                    repositories.join(", ")
+                )
+            })?;
+
+        let source: String = format!("{}/{}", repository, snapshot);
+
+        info!(
+            "[IndexClearService->restore_index] Restoring {} from {} as {}",
+            index_name, source, restored_index_name
+        );
+
+        es_conn
+            .restore_snapshot(&repository, &snapshot, index_name, restored_index_name)
+            .await?;
+
+        /* 기존 _meta 는 유지하고 복원 정보만 추가해준다. */
+        let res: Value = es_conn.get_index_meta(restored_index_name).await?;
+        let mut meta: Value = res
+            .pointer(&format!("/{}/mappings/_meta", restored_index_name))
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+
+        let restored_meta: RestoredMeta =
+            RestoredMeta::new(Utc::now(), index_name.to_string(), source.clone(), ttl_days);
+
+        meta[RETENTION_META_KEY]["restored"] = serde_json::to_value(&restored_meta)?;
+
+        es_conn
+            .put_index_meta(restored_index_name, &meta)
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "[Error][IndexClearService->restore_index] {} was restored, but tagging it failed. Retention rules may treat it as a normal index: {:?}",
+                    restored_index_name,
+                    e
+                )
+            })?;
+
+        Ok(source)
+    }
+
+    #[doc = "아카이브(NDJSON)를 원래 매핑/세팅의 새 인덱스로 가져오고, 문서 수가 매니페스트와 같은지 확인해주는 함수"]
+    /// # Arguments
+    /// * `archive_dir` - 인덱스 하나의 아카이브 디렉토리
+    /// * `manifest` - 아카이브의 매니페스트
+    /// * `imported_index_name` - 새로 만들 인덱스 명 (이미 있는 경우 에러)
+    /// * `ttl_days` - 가져온 후 보존할 일수 (None 인 경우 보존기한 규칙이 삭제하지 않는다.)
+    /// * `bulk_size` - 한 번의 _bulk 요청에 넣을 문서 수
+    /// * `bulk_concurrency` - 동시에 보낼 _bulk 요청 수
+    ///
+    /// # Returns
+    /// * Result<u64, anyhow::Error> - 가져온 문서 수
+    async fn import_archive(
+        &self,
+        archive_dir: &Path,
+        manifest: &ArchiveManifest,
+        imported_index_name: &str,
+        ttl_days: Option<u32>,
+        bulk_size: usize,
+        bulk_concurrency: usize,
+    ) -> Result<u64, anyhow::Error> {
+        /* 체크섬이 다르면 아카이브가 손상된 것이므로 인덱스를 만들기 전에 멈춘다. */
+        let data_path: PathBuf = archive_dir.join(manifest.data_file());
+        let sha256: String = sha256_file(&data_path)?;
+
+        if &sha256 != manifest.sha256() {
+            return Err(anyhow!(
+                "[Error][IndexClearService->import_archive] Checksum mismatch for '{}': expected {}, got {}",
+                data_path.display(),
+                manifest.sha256(),
+                sha256
+            ));
+        }
+
+        let definition: Value = read_json_from_file(
+            &archive_dir.join(manifest.definition_file()).to_string_lossy(),
+        )?;
+
+        /* 복원된 인덱스와 동일하게, 원래 날짜가 아닌 복원 정보(ttl_days)로 보존기한을 판단하도록 표시한다. */
+        let restored_meta: RestoredMeta = RestoredMeta::new(
+            Utc::now(),
+            manifest.index_name().clone(),
+            format!("archive:{}", archive_dir.display()),
+            ttl_days,
+        );
+        let index_body: Value = self.build_import_index_body(&definition, &restored_meta);
+
+        {
+            let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+            es_conn.create_index(imported_index_name, &index_body).await?;
+        }
+
+        info!(
+            "[IndexClearService->import_archive] Importing {} documents from '{}' into {}",
+            manifest.doc_count(),
+            archive_dir.display(),
+            imported_index_name
+        );
+
+        /* 동시에 보내는 _bulk 요청 수를 제한해서, 파일 전체가 메모리에 올라오지 않도록 한다. */
+        let bulk_semaphore: Arc<Semaphore> = Arc::new(Semaphore::new(bulk_concurrency));
+        let mut handles: Vec<JoinHandle<Result<u64, anyhow::Error>>> = Vec::new();
+        let mut lines = open_archive_reader(&data_path, *manifest.compression())?.lines();
+        let mut reached_end: bool = false;
+        let mut imported_count: u64 = 0;
+
+        while !reached_end {
+            let mut batch: Vec<Value> = Vec::with_capacity(bulk_size);
+
+            while batch.len() < bulk_size {
+                let line: String = match lines.next() {
+                    Some(line) => line?,
+                    None => {
+                        reached_end = true;
+                        break;
+                    }
+                };
+
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                batch.push(serde_json::from_str(&line).map_err(|e| {
+                    anyhow!(
+                        "[Error][IndexClearService->import_archive] Invalid line in '{}': {:?}",
+                        data_path.display(),
+                        e
+                    )
+                })?);
+            }
+
+            if batch.is_empty() {
+                break;
+            }
+
+            let permit: OwnedSemaphorePermit = bulk_semaphore.clone().acquire_owned().await?;
+
+            /* 먼저 끝난 요청이 실패했다면 나머지 문서는 읽지 않고 멈춘다. */
+            let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut handles)
+                .into_iter()
+                .partition(|handle| handle.is_finished());
+            handles = running;
+
+            for handle in finished {
+                match handle.await? {
+                    Ok(count) => imported_count += count,
+                    Err(e) => {
+                        handles.iter().for_each(|handle| handle.abort());
+                        return Err(e);
+                    }
+                }
+            }
+
+            let service: IndexClearServicePub = self.clone();
+            let index_name: String = imported_index_name.to_string();
+
+            handles.push(tokio::spawn(async move {
+                let _permit: OwnedSemaphorePermit = permit;
+                service.import_bulk_batch(&index_name, batch).await
+            }));
+        }
+
+        for handle in handles {
+            imported_count += handle.await??;
+        }
+
+        {
+            let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+            es_conn.refresh_index(imported_index_name).await?;
+
+            let index_doc_count: u64 = es_conn.count_index(imported_index_name).await?;
+
+            if imported_count != *manifest.doc_count() || index_doc_count != *manifest.doc_count() {
+                return Err(anyhow!(
+                    "[Error][IndexClearService->import_archive] Document count mismatch for {}: manifest {}, imported {}, _count {}",
+                    imported_index_name,
+                    manifest.doc_count(),
+                    imported_count,
+                    index_doc_count
+                ));
+            }
+        }
+
+        Ok(imported_count)
+    }
+
+    #[doc = "규칙에 해당하는 인덱스 각각에 대해서 삭제 여부를 판단한 계획을 만들어주는 함수 (실제 삭제는 하지 않음)"]
+    /// # Arguments
+    /// * `target_index` - 대상이 되는 인덱스 정보
+    ///
+    /// # Returns
+    /// * Result<Vec<IndexDecision>, anyhow::Error>
+    async fn build_index_plan(
+        &self,
+        target_index: &TargetIndex,
+    ) -> Result<Vec<IndexDecision>, anyhow::Error> {
+        /* Elasitcsearch 커넥션 */
+        let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+
+        let mut index_plan: Vec<IndexDecision> =
+            self.compute_rule_plan(&es_conn, target_index).await?;
+
+        /* 출력 시 보기 편하도록 인덱스 이름순으로 정렬 */
+        index_plan.sort_by(|a, b| a.index_name.cmp(&b.index_name));
+
+        Ok(index_plan)
+    }
+
+    #[doc = "검토된 삭제 계획에 포함된 인덱스만 삭제해주는 함수. 삭제 직전에 인덱스가 여전히 존재하고 규칙에 부합하는지 다시 확인한다."]
+    /// # Arguments
+    /// * `target_index` - 계획 작성 시점의 규칙 정보
+    /// * `planned_decisions` - 해당 규칙으로 계획된 삭제 대상 인덱스 목록
+    /// * `run_id` - 감사 로그에 남길 실행 ID
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error> - 삭제에 실패했거나 규칙에 더 이상 부합하지 않는 인덱스가 있으면 에러 (이미 없어진 인덱스는 제외)
+    async fn apply_index_plan(
+        &self,
+        target_index: &TargetIndex,
+        planned_decisions: &[IndexDecision],
+        run_id: &str,
+    ) -> Result<(), anyhow::Error> {
+        /* 현재 시점으로 규칙 전체를 다시 판단한다. - 존재 여부와 규칙 부합 여부를 동시에 확인할 수 있다. */
+        let current_plan: Vec<IndexDecision> = {
+            /* Elasitcsearch 커넥션 - 삭제 전에 반환해준다. */
+            let es_conn: ElasticConnGuard = get_elastic_guard_conn().await?;
+            self.compute_rule_plan(&es_conn, target_index).await?
+        };
+
+        let current_decisions: HashMap<&str, &IndexDecision> = current_plan
+            .iter()
+            .map(|decision| (decision.index_name().as_str(), decision))
+            .collect();
+
+        let mut confirmed_decisions: Vec<IndexDecision> = Vec::new();
+        let mut drifted_indexes: Vec<&str> = Vec::new();
+
+        for planned in planned_decisions {
+            if !planned.action.is_removal() {
+                continue;
+            }
+
+            let decision: &IndexDecision =
+                match current_decisions.get(planned.index_name().as_str()) {
+                    Some(decision) => decision,
+                    None => {
+                        info!(
+                            "[IndexClearService->apply_index_plan] {} no longer exists (or no longer matches '{}'). skipped.",
+                            planned.index_name(),
+                            target_index.index_name()
+                        );
+                        continue;
+                    }
+                };
+
+            if decision.action != planned.action {
+                error!(
+                    "[Error][IndexClearService->apply_index_plan] {} no longer matches its rule. skipped. ({}: {})",
+                    planned.index_name(),
+                    decision.action(),
+                    decision.reason()
+                );
+                drifted_indexes.push(planned.index_name());
+                continue;
+            }
+
+            confirmed_decisions.push((*decision).clone());
+        }
+
+        self.delete_planned_indexes(&confirmed_decisions, run_id).await?;
+
+        /* 규칙에 더 이상 부합하지 않아 건너뛴 인덱스는 계획이 적용되지 않은 것이다. */
+        if !drifted_indexes.is_empty() {
+            return Err(anyhow!(
+                "[Error][IndexClearService->apply_index_plan] {} planned indices no longer match '{}' and were not deleted: [{}]",
+                drifted_indexes.len(),
+                target_index.index_name(),
+                drifted_indexes.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[doc = "인덱스 하나에 대해서 보존기한 규칙에 따른 삭제 여부를 판단해주는 함수"]
+    /// # Arguments
+    /// * `target_index` - 인덱스가 속한 규칙 정보
+    /// * `date_format` - 규칙의 인덱스 이름 날짜 양식
+    /// * `cat_index` - 판단 대상 인덱스 정보
+    /// * `rollover_time` - 인덱스가 롤오버된 시각 (age_source = rollover_date 인 경우)
+    /// * `cur_utc_datetime` - 기준이 되는 현재 UTC 시각
+    ///
+    /// # Returns
+    /// * IndexDecision
+    fn decide_index(
+        &self,
+        target_index: &TargetIndex,
+        date_format: &IndexDateFormat,
+        cat_index: &CatIndex,
+        rollover_time: Option<NaiveDateTime>,
+        cur_utc_datetime: NaiveDateTime,
+    ) -> IndexDecision {
+        let index_name: &str = cat_index.index();
+
+        /* 보존기한 데드라인 시각. */
+        let perserve_time_ago: NaiveDateTime =
+            cur_utc_datetime - chrono::Duration::days(target_index.duration_days as i64);
+        let perserve_days_ago: NaiveDate = perserve_time_ago.date();
+
+        let decision: IndexDecision = IndexDecision::new(
+            index_name,
+            target_index.index_name(),
+            perserve_days_ago,
+            cat_index.store_size_bytes(),
+            cat_index.docs_count_num(),
+        )
+        .with_snapshot_repository(target_index.snapshot_target_repository())
+        .with_archive_target(target_index.archive_target());
+
+        /* 아직 롤오버되지 않은 인덱스는 현재 쓰기중인 인덱스이므로 나이와 관계없이 보존한다. */
+        if *target_index.age_source() == AgeSource::RolloverDate && rollover_time.is_none() {
+            return decision.with_action(
+                IndexAction::Keep,
+                String::from("index has not been rolled over yet (current write index)"),
+            );
+        }
+
+        let index_period: IndexPeriod =
+            match self.resolve_index_period(target_index, date_format, cat_index, rollover_time) {
+                Ok(index_period) => index_period,
+                Err(e) => {
+                    return decision.with_action(IndexAction::Skip, e.to_string());
+                }
+            };
+
+        /* 미래 날짜의 인덱스는 이름이나 시계가 잘못된 것이므로 판단하지 않는다. (날짜가 없으므로 개수/용량 정책에서도 제외) */
+        if *index_period.start() > cur_utc_datetime + FUTURE_INDEX_DATE_TOLERANCE {
+            return decision.with_action(
+                IndexAction::Skip,
+                format!(
+                    "index date {} is in the future (current UTC time {}) - check the index name or the clock",
+                    index_period.start(),
+                    cur_utc_datetime.format("%Y-%m-%d %H:%M:%S")
+                ),
+            );
+        }
+
+        /*
+            기간 단위에 맞춰서 비교한다.
+            - hourly : 인덱스 시각 <= 데드라인 시각 (시 단위 절삭)
+            - daily/weekly/monthly : 인덱스 기간의 마지막 일자 <= 데드라인 일자
+              (월 단위 인덱스는 그 달의 데이터 전체가 보존기한을 넘겨야 삭제된다.)
+        */
+        let is_expired: bool = match index_period.granularity() {
+            DateGranularity::Hourly => {
+                let perserve_hour_ago: NaiveDateTime = perserve_time_ago
+                    .with_minute(0)
+                    .and_then(|time| time.with_second(0))
+                    .and_then(|time| time.with_nanosecond(0))
+                    .unwrap_or(perserve_time_ago);
+
+                *index_period.start() <= perserve_hour_ago
+            }
+            DateGranularity::Timestamp => *index_period.start() <= perserve_time_ago,
+            _ => *index_period.last_date() <= perserve_days_ago,
+        };
+
+        let period_desc: String = match (index_period.granularity(), target_index.age_source()) {
+            (DateGranularity::Timestamp, AgeSource::RolloverDate) => {
+                format!("rollover date {}", index_period.period_end_label())
+            }
+            (DateGranularity::Timestamp, _) => {
+                format!("creation date {}", index_period.period_end_label())
+            }
+            (_, AgeSource::Name | AgeSource::NameThenCreation) if *target_index.data_stream() => {
+                format!("backing index generation date {}", index_period.period_end_label())
+            }
+            (granularity, _) => format!(
+                "{:?} index period ending {}",
+                granularity,
+                index_period.period_end_label()
+            ),
+        };
+
+        let (action, reason) = if is_expired {
+            (
+                IndexAction::Delete,
+                format!(
+                    "{} is on or before the cutoff date ({} days retention)",
+                    period_desc,
+                    target_index.duration_days()
+                ),
+            )
+        } else {
+            (
+                IndexAction::Keep,
+                format!(
+                    "{} is within the retention period ({} days)",
+                    period_desc,
+                    target_index.duration_days()
+                ),
+            )
+        };
+
+        decision
+            .with_index_time(*index_period.start())
+            .with_action(action, reason)
+    }
+
+    #[doc = "규칙의 max_total_size 에 따라 전체 용량이 상한 아래로 내려갈 때까지 오래된 인덱스부터 삭제 대상으로 바꿔주는 함수"]
+    /// # Arguments
+    /// * `target_index` - 인덱스가 속한 규칙 정보
+    /// * `rule_plan` - 나이로 판단한 규칙 전체의 결과
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    ///
+    /// 날짜를 알 수 없는 인덱스는 삭제할 수 없으므로 전체 용량에도 포함하지 않는다.
+    fn apply_size_retention(
+        &self,
+        target_index: &TargetIndex,
+        rule_plan: &mut [IndexDecision],
+    ) -> Result<(), anyhow::Error> {
+        let max_total_size: u64 = match target_index.max_total_size_bytes()? {
+            Some(max_total_size) => max_total_size,
+            None => return Ok(()),
+        };
+
+        /* 이미 삭제 대상인 인덱스를 제외한 남은 용량 */
+        let mut remain_size: u64 = rule_plan
+            .iter()
+            .filter(|decision| decision.action != IndexAction::Delete && decision.index_time.is_some())
+            .filter_map(|decision| decision.store_size)
+            .sum();
+
+        /* 오래된 인덱스가 앞에 오도록 정렬 */
+        let mut dated_decisions: Vec<&mut IndexDecision> = rule_plan
+            .iter_mut()
+            .filter(|decision| decision.index_time.is_some())
+            .collect();
+        dated_decisions.sort_by_key(|decision| decision.index_time);
+
+        for decision in dated_decisions {
+            if remain_size <= max_total_size {
+                break;
+            }
+
+            if decision.action != IndexAction::Keep {
+                continue;
+            }
+
+            let store_size: u64 = decision.store_size.unwrap_or(0);
+            remain_size = remain_size.saturating_sub(store_size);
+
+            decision.action = IndexAction::Delete;
+            decision.reason = format!(
+                "exceeds max_total_size = {} (oldest first, {} freed)",
+                target_index.max_total_size().as_deref().unwrap_or_default(),
+                format_byte_size(store_size)
+            );
+        }
+
+        if remain_size > max_total_size {
+            error!(
+                "[Error][IndexClearService->apply_size_retention] '{}' is still over max_total_size after deleting every eligible index. ({} remain)",
+                target_index.index_name(),
+                format_byte_size(remain_size)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[doc = "규칙의 min_keep / max_keep 에 따라 나이로 판단한 결과를 보정해주는 함수"]
+    /// # Arguments
+    /// * `target_index` - 인덱스가 속한 규칙 정보
+    /// * `rule_plan` - 나이로 판단한 규칙 전체의 결과
+    ///
+    /// 날짜를 알 수 없는 인덱스(SKIP, 롤오버 전 write 인덱스)는 개수에 포함하지 않는다.
+    fn apply_count_retention(&self, target_index: &TargetIndex, rule_plan: &mut [IndexDecision]) {
+        if target_index.min_keep().is_none() && target_index.max_keep().is_none() {
+            return;
+        }
+
+        /* 최신 인덱스가 앞에 오도록 정렬 */
+        let mut dated_decisions: Vec<&mut IndexDecision> = rule_plan
+            .iter_mut()
+            .filter(|decision| decision.index_time.is_some())
+            .collect();
+        dated_decisions.sort_by_key(|decision| std::cmp::Reverse(decision.index_time));
+
+        for (newer_cnt, decision) in dated_decisions.into_iter().enumerate() {
+            if let Some(min_keep) = *target_index.min_keep() {
+                if newer_cnt < min_keep && decision.action == IndexAction::Delete {
+                    decision.action = IndexAction::Keep;
+                    decision.reason = format!(
+                        "kept by min_keep = {} (one of the newest {} indices), although {}",
+                        min_keep, min_keep, decision.reason
+                    );
+                    continue;
+                }
+            }
+
+            if let Some(max_keep) = *target_index.max_keep() {
+                if newer_cnt >= max_keep && decision.action == IndexAction::Keep {
+                    decision.action = IndexAction::Delete;
+                    decision.reason = format!(
+                        "exceeds max_keep = {} ({} newer indices exist)",
+                        max_keep, newer_cnt
+                    );
+                }
+            }
+        }
+    }
+
+    #[doc = "규칙별 인덱스 결정 중에서 디스크 사용률이 목표 아래로 내려갈 때까지 긴급 삭제할 인덱스를 골라주는 함수"]
+    /// # Arguments
+    /// * `disk_usage` - 클러스터 디스크 사용량
+    /// * `emergency_config` - 긴급 정리 설정
+    /// * `rule_plans` - (인덱스 보존 규칙, 규칙에 해당하는 인덱스 결정) 리스트
+    /// * `cur_utc_time` - 현재 UTC 시각
+    ///
+    /// # Returns
+    /// * EmergencyPlan
+    ///
+    /// 삭제 순서 : 원래 보존기한이 지난 인덱스 -> priority 가 낮은 규칙 -> 오래된 인덱스.
+    /// never_below_days 보다 최근 인덱스, min_keep 에 해당하는 최신 인덱스, 날짜를 알 수 없는 인덱스는 삭제하지 않는다.
+    fn select_emergency_indexes(
+        &self,
+        disk_usage: &ClusterDiskUsage,
+        emergency_config: &EmergencyConfig,
+        rule_plans: Vec<(&TargetIndex, Vec<IndexDecision>)>,
+        cur_utc_time: NaiveDateTime,
+    ) -> EmergencyPlan {
+        let used_percent: f64 = disk_usage.max_used_percent();
+
+        /* (규칙 중요도, 원래 보존기한 경과 여부, 인덱스 결정) */
+        let mut candidates: Vec<(u32, bool, IndexDecision)> = Vec::new();
+        /* 규칙에 해당하는 전체 인덱스 - 삭제 대상으로 고르지 않은 인덱스는 남겨진다. */
+        let mut rule_decisions: Vec<IndexDecision> = Vec::new();
+
+        for (target_index, mut rule_plan) in rule_plans {
+            rule_decisions.extend(rule_plan.iter().cloned());
+
+            /* 최신 인덱스가 앞에 오도록 정렬 (보호 대상 인덱스는 긴급 정리에서도 삭제하지 않는다.) */
+            rule_plan.retain(|decision| {
+                decision.index_time.is_some() && decision.protections.is_empty()
+            });
+            rule_plan.sort_by_key(|decision| std::cmp::Reverse(decision.index_time));
+
+            let floor_time: Option<NaiveDateTime> = target_index
+                .never_below_days()
+                .map(|days| cur_utc_time - chrono::Duration::days(days as i64));
+
+            for decision in rule_plan
+                .into_iter()
+                .skip(target_index.min_keep().unwrap_or(0))
+            {
+                let is_under_floor: bool = match (floor_time, decision.index_time) {
+                    (Some(floor_time), Some(index_time)) => index_time > floor_time,
+                    _ => false,
+                };
+
+                if is_under_floor {
+                    continue;
+                }
+
+                /* soft delete 된 인덱스는 유예 기간 중이라도 원래 보존기한이 지난 인덱스다. */
+                let is_expired: bool =
+                    decision.action.is_removal() || decision.soft_deleted_at.is_some();
+                candidates.push((*target_index.priority(), is_expired, decision));
+            }
+        }
+
+        candidates.sort_by(|(a_priority, a_expired, a), (b_priority, b_expired, b)| {
+            b_expired
+                .cmp(a_expired)
+                .then(a_priority.cmp(b_priority))
+                .then(a.index_time.cmp(&b.index_time))
+        });
+
+        let mut freed_bytes: u64 = 0;
+        let mut emergency_decisions: Vec<IndexDecision> = Vec::new();
+
+        for (priority, _, decision) in candidates {
+            if disk_usage.projected_max_used_percent(freed_bytes)
+                < *emergency_config.target_disk_percent()
+            {
+                break;
+            }
+
+            freed_bytes += decision.store_size.unwrap_or(0);
+
+            let reason: String = if decision.action == IndexAction::Delete {
+                decision.reason.clone()
+            } else {
+                format!(
+                    "emergency cleanup: disk usage {:.1}% > {}% (rule priority {})",
+                    used_percent,
+                    emergency_config.high_disk_percent(),
+                    priority
+                )
+            };
+
+            emergency_decisions.push(decision.with_action(IndexAction::Delete, reason));
+        }
+
+        let projected_percent: f64 = disk_usage.projected_max_used_percent(freed_bytes);
+
+        let emergency_indexes: HashSet<&str> = emergency_decisions
+            .iter()
+            .map(|decision| decision.index_name().as_str())
+            .collect();
+
+        let kept_decisions: Vec<IndexDecision> = rule_decisions
+            .into_iter()
+            .filter(|decision| !emergency_indexes.contains(decision.index_name().as_str()))
+            .map(|decision| {
+                decision.with_action(
+                    IndexAction::Keep,
+                    String::from("not needed for emergency cleanup"),
+                )
+            })
+            .collect();
+
+        EmergencyPlan::new(
+            used_percent,
+            projected_percent,
+            emergency_decisions,
+            kept_decisions,
+        )
+    }
+
+    #[doc = "규칙의 soft_delete_days 에 따라 삭제(DELETE)로 판단된 결과를 soft delete 단계에 맞춰 보정해주는 함수"]
+    /// # Arguments
+    /// * `target_index` - 인덱스가 속한 규칙 정보
+    /// * `rule_plan` - 규칙 전체의 결과
+    /// * `soft_deleted_times` - soft delete 된 상태인 인덱스 -> soft delete 된 시각
+    /// * `cur_utc_datetime` - 기준이 되는 현재 UTC 시각
+    ///
+    /// - 아직 soft delete 되지 않은 인덱스 : DELETE -> SOFT_DELETE
+    /// - soft delete 후 soft_delete_days 가 지나지 않은 인덱스 : DELETE -> KEEP
+    /// - soft delete 후 soft_delete_days 가 지난 인덱스 : DELETE
+    ///
+    /// 스냅샷/아카이브는 soft delete 할 때 남기므로 (닫힌 인덱스는 읽을 수 없음), 이미 soft delete 된 인덱스는 다시 남기지 않는다.
+    fn apply_soft_delete(
+        &self,
+        target_index: &TargetIndex,
+        rule_plan: &mut [IndexDecision],
+        soft_deleted_times: &HashMap<String, DateTime<Utc>>,
+        cur_utc_datetime: NaiveDateTime,
+    ) {
+        for decision in rule_plan.iter_mut() {
+            let soft_deleted_at: Option<DateTime<Utc>> =
+                soft_deleted_times.get(decision.index_name()).copied();
+
+            if let Some(soft_deleted_at) = soft_deleted_at {
+                decision.soft_deleted_at = Some(soft_deleted_at);
+                decision.snapshot_repository = None;
+                decision.archive_target = None;
+            }
+
+            let soft_delete_days: u32 = match target_index.soft_delete_days() {
+                Some(soft_delete_days) => *soft_delete_days,
+                None => continue,
+            };
+
+            match (decision.action, soft_deleted_at) {
+                (IndexAction::Delete, None) => {
+                    decision.action = IndexAction::SoftDelete;
+                    decision.soft_delete_mode = Some(*target_index.soft_delete_mode());
+                    decision.reason = format!(
+                        "{} (soft delete first, deleted {} days later)",
+                        decision.reason, soft_delete_days
+                    );
+                }
+                (IndexAction::Delete, Some(soft_deleted_at)) => {
+                    let delete_after: DateTime<Utc> =
+                        soft_deleted_at + chrono::Duration::days(soft_delete_days as i64);
+
+                    if cur_utc_datetime.and_utc() >= delete_after {
+                        decision.reason = format!(
+                            "{} (soft-deleted at {}, {} days grace period has passed)",
+                            decision.reason,
+                            soft_deleted_at.format("%Y-%m-%d %H:%M:%S"),
+                            soft_delete_days
+                        );
+                    } else {
+                        decision.action = IndexAction::Keep;
+                        decision.reason = format!(
+                            "soft-deleted at {}, will be deleted after {}",
+                            soft_deleted_at.format("%Y-%m-%d %H:%M:%S"),
+                            delete_after.format("%Y-%m-%d %H:%M:%S")
+                        );
+                    }
+                }
+                (IndexAction::Keep, Some(soft_deleted_at)) => {
+                    decision.reason = format!(
+                        "{} (soft-deleted at {}, no longer expired: reopen it or remove the write block to restore)",
+                        decision.reason,
+                        soft_deleted_at.format("%Y-%m-%d %H:%M:%S")
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[doc = "규칙의 age_source 에 따라 인덱스의 나이를 판단할 기간을 구해주는 함수"]
+    /// # Arguments
+    /// * `target_index` - 인덱스가 속한 규칙 정보
+    /// * `date_format` - 규칙의 인덱스 이름 날짜 양식
+    /// * `cat_index` - 대상 인덱스 정보
+    /// * `rollover_time` - 인덱스가 롤오버된 시각
+    ///
+    /// # Returns
+    /// * Result<IndexPeriod, anyhow::Error>
+    fn resolve_index_period(
+        &self,
+        target_index: &TargetIndex,
+        date_format: &IndexDateFormat,
+        cat_index: &CatIndex,
+        rollover_time: Option<NaiveDateTime>,
+    ) -> Result<IndexPeriod, anyhow::Error> {
+        let creation_period = || -> Result<IndexPeriod, anyhow::Error> {
+            cat_index
+                .creation_datetime()
+                .map(|created_at| {
+                    IndexPeriod::new(DateGranularity::Timestamp, created_at, created_at.date())
+                })
+                .ok_or_else(|| anyhow!("[Error][IndexClearService->resolve_index_period] 'creation.date' not found : {}", cat_index.index()))
+        };
+
+        match target_index.age_source() {
+            AgeSource::Name => self.parsed_data_index(date_format, cat_index.index()),
+            AgeSource::CreationDate => creation_period(),
+            AgeSource::NameThenCreation => self
+                .parsed_data_index(date_format, cat_index.index())
+                .or_else(|_| creation_period()),
+            AgeSource::RolloverDate => rollover_time
+                .map(|rolled_at| IndexPeriod::new(DateGranularity::Timestamp, rolled_at, rolled_at.date()))
+                .ok_or_else(|| anyhow!("[Error][IndexClearService->resolve_index_period] rollover time not found : {}", cat_index.index())),
+        }
+    }
+
+    #[doc = "인덱스에 존재하는 날짜 양식을 날짜 포멧으로 뽑아주는 함수"]
+    /// # Arguments
+    /// * `date_format` - 규칙의 인덱스 이름 날짜 양식
+    /// * `index_name` - 대상이 되는 인덱스 정보
+    ///
+    /// # Returns
+    /// * Result<IndexPeriod, anyhow::Error>
+    fn parsed_data_index(
+        &self,
+        date_format: &IndexDateFormat,
+        index_name: &str,
+    ) -> Result<IndexPeriod, anyhow::Error> {
+        date_format.parse_index_period(index_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    fn utc(datetime: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn rule(rule_toml: &str) -> TargetIndex {
+        toml::from_str(rule_toml).unwrap()
+    }
+
+    fn dated_decision(index_date: &str, store_size: u64, action: IndexAction) -> IndexDecision {
+        let mut decision: IndexDecision = IndexDecision::new(
+            &format!("logs-{}", index_date),
+            "logs-*",
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            Some(store_size),
+            None,
+        )
+        .with_index_time(utc(&format!("{} 00:00:00", index_date)));
+        decision.action = action;
+        decision
+    }
+
+    fn actions(rule_plan: &[IndexDecision]) -> Vec<IndexAction> {
+        rule_plan.iter().map(|decision| decision.action).collect()
+    }
+
+    #[test]
+    fn clock_skew_within_tolerance_is_accepted() {
+        let cluster_time: NaiveDateTime = utc("2025-01-10 00:00:00");
+
+        assert!(check_clock_skew(utc("2025-01-10 00:00:00"), Some(cluster_time)).is_ok());
+        assert!(check_clock_skew(utc("2025-01-10 00:05:00"), Some(cluster_time)).is_ok());
+        assert!(check_clock_skew(utc("2025-01-09 23:55:00"), Some(cluster_time)).is_ok());
+    }
+
+    #[test]
+    fn local_clock_ahead_is_rejected() {
+        let err: anyhow::Error =
+            check_clock_skew(utc("2026-01-10 00:00:00"), Some(utc("2025-01-10 00:00:00")))
+                .unwrap_err();
+
+        assert!(err.to_string().contains("ahead of"));
+        assert!(check_clock_skew(utc("2025-01-10 00:05:01"), Some(utc("2025-01-10 00:00:00"))).is_err());
+    }
+
+    #[test]
+    fn local_clock_behind_is_rejected() {
+        let err: anyhow::Error =
+            check_clock_skew(utc("2025-01-09 00:00:00"), Some(utc("2025-01-10 00:00:00")))
+                .unwrap_err();
+
+        assert!(err.to_string().contains("behind"));
+    }
+
+    #[test]
+    fn unknown_cluster_clock_fails_closed() {
+        let err: anyhow::Error = check_clock_skew(utc("2025-01-10 00:00:00"), None).unwrap_err();
+
+        assert!(err.to_string().contains("Could not get the cluster clock"));
+    }
+
+    #[test]
+    fn import_body_drops_cluster_specific_settings() {
+        let definition: Value = json!({
+            "settings": {
+                "index": {
+                    "number_of_shards": "3",
+                    "uuid": "abc",
+                    "blocks": { "write": "true" },
+                    "routing": { "allocation": { "require": { "_name": "node-1" } } },
+                    "routing_partition_size": "2"
+                }
+            },
+            "mappings": { "properties": { "message": { "type": "text" } } }
+        });
+        let restored_meta: RestoredMeta = RestoredMeta::new(
+            Utc::now(),
+            String::from("logs-2020.01.01"),
+            String::from("archive"),
+            None,
+        );
+
+        let body: Value = IndexClearServicePub::new().build_import_index_body(&definition, &restored_meta);
+        let index_settings: &Value = &body["settings"]["index"];
+
+        assert_eq!(index_settings["number_of_shards"], "3");
+        assert_eq!(index_settings["routing_partition_size"], "2");
+        assert!(index_settings.get("uuid").is_none());
+        assert!(index_settings.get("blocks").is_none());
+        assert!(index_settings.get("routing").is_none());
+        assert_eq!(body["mappings"]["properties"]["message"]["type"], "text");
+    }
+
+    #[test]
+    fn size_retention_deletes_oldest_until_under_budget() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-*\"\nduration_days = 30\nmax_total_size = \"25gb\"");
+        let mut rule_plan: Vec<IndexDecision> = vec![
+            dated_decision("2025-01-04", 10 * GB, IndexAction::Keep),
+            dated_decision("2025-01-02", 10 * GB, IndexAction::Keep),
+            dated_decision("2025-01-03", 10 * GB, IndexAction::Keep),
+            dated_decision("2025-01-01", 10 * GB, IndexAction::Keep),
+        ];
+
+        IndexClearServicePub::new()
+            .apply_size_retention(&target_index, &mut rule_plan)
+            .unwrap();
+
+        assert_eq!(
+            actions(&rule_plan),
+            vec![IndexAction::Keep, IndexAction::Delete, IndexAction::Keep, IndexAction::Delete]
+        );
+        assert!(rule_plan[3].reason.contains("max_total_size = 25gb"));
+    }
+
+    #[test]
+    fn size_retention_does_not_count_indices_already_deleted() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-*\"\nduration_days = 30\nmax_total_size = \"25gb\"");
+        let mut rule_plan: Vec<IndexDecision> = vec![
+            dated_decision("2025-01-01", 10 * GB, IndexAction::Delete),
+            dated_decision("2025-01-02", 10 * GB, IndexAction::Keep),
+            dated_decision("2025-01-03", 10 * GB, IndexAction::Keep),
+            dated_decision("2025-01-04", 10 * GB, IndexAction::Keep),
+        ];
+
+        IndexClearServicePub::new()
+            .apply_size_retention(&target_index, &mut rule_plan)
+            .unwrap();
+
+        assert_eq!(
+            actions(&rule_plan),
+            vec![IndexAction::Delete, IndexAction::Delete, IndexAction::Keep, IndexAction::Keep]
+        );
+    }
+
+    #[test]
+    fn size_retention_leaves_undated_indices_alone() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-*\"\nduration_days = 30\nmax_total_size = \"5gb\"");
+        let mut undated: IndexDecision = IndexDecision::new(
+            "logs-current",
+            "logs-*",
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            Some(100 * GB),
+            None,
+        );
+        undated.action = IndexAction::Skip;
+        let mut rule_plan: Vec<IndexDecision> = vec![
+            undated,
+            dated_decision("2025-01-01", 2 * GB, IndexAction::Keep),
+            dated_decision("2025-01-02", 2 * GB, IndexAction::Keep),
+        ];
+
+        IndexClearServicePub::new()
+            .apply_size_retention(&target_index, &mut rule_plan)
+            .unwrap();
+
+        assert_eq!(
+            actions(&rule_plan),
+            vec![IndexAction::Skip, IndexAction::Keep, IndexAction::Keep]
+        );
+    }
+
+    #[test]
+    fn size_retention_without_budget_changes_nothing() {
+        let target_index: TargetIndex = rule("index_name = \"logs-*\"\nduration_days = 30");
+        let mut rule_plan: Vec<IndexDecision> = vec![
+            dated_decision("2025-01-01", 100 * GB, IndexAction::Keep),
+            dated_decision("2025-01-02", 100 * GB, IndexAction::Keep),
+        ];
+
+        IndexClearServicePub::new()
+            .apply_size_retention(&target_index, &mut rule_plan)
+            .unwrap();
+
+        assert_eq!(actions(&rule_plan), vec![IndexAction::Keep, IndexAction::Keep]);
+    }
+
+    #[test]
+    fn size_retention_rejects_invalid_budget() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-*\"\nduration_days = 30\nmax_total_size = \"lots\"");
+        let mut rule_plan: Vec<IndexDecision> =
+            vec![dated_decision("2025-01-01", GB, IndexAction::Keep)];
+
+        assert!(IndexClearServicePub::new()
+            .apply_size_retention(&target_index, &mut rule_plan)
+            .is_err());
+    }
+
+    #[test]
+    fn min_keep_restores_newest_expired_indices() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-*\"\nduration_days = 1\nmin_keep = 2");
+        let mut rule_plan: Vec<IndexDecision> = vec![
+            dated_decision("2025-01-01", GB, IndexAction::Delete),
+            dated_decision("2025-01-03", GB, IndexAction::Delete),
+            dated_decision("2025-01-02", GB, IndexAction::Delete),
+        ];
+
+        IndexClearServicePub::new().apply_count_retention(&target_index, &mut rule_plan);
+
+        assert_eq!(
+            actions(&rule_plan),
+            vec![IndexAction::Delete, IndexAction::Keep, IndexAction::Keep]
+        );
+        assert!(rule_plan[1].reason.starts_with("kept by min_keep = 2"));
+    }
+
+    #[test]
+    fn max_keep_deletes_oldest_beyond_limit() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-*\"\nduration_days = 30\nmax_keep = 2");
+        let mut rule_plan: Vec<IndexDecision> = vec![
+            dated_decision("2025-01-02", GB, IndexAction::Keep),
+            dated_decision("2025-01-04", GB, IndexAction::Keep),
+            dated_decision("2025-01-01", GB, IndexAction::Keep),
+            dated_decision("2025-01-03", GB, IndexAction::Keep),
+        ];
+
+        IndexClearServicePub::new().apply_count_retention(&target_index, &mut rule_plan);
+
+        assert_eq!(
+            actions(&rule_plan),
+            vec![IndexAction::Delete, IndexAction::Keep, IndexAction::Delete, IndexAction::Keep]
+        );
+        assert_eq!(rule_plan[2].reason, "exceeds max_keep = 2 (3 newer indices exist)");
+    }
+
+    #[test]
+    fn count_retention_ignores_undated_indices() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-*\"\nduration_days = 30\nmax_keep = 1");
+        let mut rule_plan: Vec<IndexDecision> = vec![
+            IndexDecision::new(
+                "logs-current",
+                "logs-*",
+                NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                None,
+                None,
+            ),
+            dated_decision("2025-01-01", GB, IndexAction::Keep),
+            dated_decision("2025-01-02", GB, IndexAction::Keep),
+        ];
+
+        IndexClearServicePub::new().apply_count_retention(&target_index, &mut rule_plan);
+
+        assert_eq!(
+            actions(&rule_plan),
+            vec![IndexAction::Keep, IndexAction::Delete, IndexAction::Keep]
+        );
+    }
+
+    #[test]
+    fn min_keep_wins_over_max_keep() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-*\"\nduration_days = 30\nmin_keep = 2\nmax_keep = 1");
+        let mut rule_plan: Vec<IndexDecision> = vec![
+            dated_decision("2025-01-01", GB, IndexAction::Keep),
+            dated_decision("2025-01-02", GB, IndexAction::Delete),
+            dated_decision("2025-01-03", GB, IndexAction::Keep),
+        ];
+
+        IndexClearServicePub::new().apply_count_retention(&target_index, &mut rule_plan);
+
+        assert_eq!(
+            actions(&rule_plan),
+            vec![IndexAction::Delete, IndexAction::Keep, IndexAction::Keep]
+        );
+    }
+
+    #[test]
+    fn soft_delete_replaces_first_delete() {
+        let target_index: TargetIndex = rule(concat!(
+            "index_name = \"logs-*\"\nduration_days = 1\n",
+            "soft_delete_days = 7\nsoft_delete_mode = \"write_block\"",
+        ));
+        let mut rule_plan: Vec<IndexDecision> =
+            vec![dated_decision("2025-01-01", GB, IndexAction::Delete)];
+
+        IndexClearServicePub::new().apply_soft_delete(
+            &target_index,
+            &mut rule_plan,
+            &HashMap::new(),
+            utc("2025-01-10 00:00:00"),
+        );
+
+        assert_eq!(rule_plan[0].action, IndexAction::SoftDelete);
+        assert_eq!(rule_plan[0].soft_delete_mode, Some(SoftDeleteMode::WriteBlock));
+    }
+
+    #[test]
+    fn soft_deleted_index_is_deleted_only_after_grace_period() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-*\"\nduration_days = 1\nsoft_delete_days = 7");
+        let mut rule_plan: Vec<IndexDecision> = vec![
+            dated_decision("2025-01-01", GB, IndexAction::Delete)
+                .with_snapshot_repository(Some("backup")),
+            dated_decision("2025-01-02", GB, IndexAction::Delete),
+        ];
+        let soft_deleted_times: HashMap<String, DateTime<Utc>> = HashMap::from([
+            (String::from("logs-2025-01-01"), utc("2025-01-03 00:00:00").and_utc()),
+            (String::from("logs-2025-01-02"), utc("2025-01-03 00:00:01").and_utc()),
+        ]);
+
+        IndexClearServicePub::new().apply_soft_delete(
+            &target_index,
+            &mut rule_plan,
+            &soft_deleted_times,
+            utc("2025-01-10 00:00:00"),
+        );
+
+        assert_eq!(actions(&rule_plan), vec![IndexAction::Delete, IndexAction::Keep]);
+        assert!(rule_plan[0].reason.contains("grace period has passed"));
+        /* soft delete 할 때 이미 남겼으므로 다시 스냅샷을 남기지 않는다. */
+        assert!(rule_plan[0].snapshot_repository.is_none());
+        assert!(rule_plan[1].reason.contains("will be deleted after 2025-01-10 00:00:01"));
+    }
+
+    #[test]
+    fn soft_deleted_index_that_is_no_longer_expired_stays_kept() {
+        let target_index: TargetIndex =
+            rule("index_name = \"logs-*\"\nduration_days = 30\nsoft_delete_days = 7");
+        let mut rule_plan: Vec<IndexDecision> =
+            vec![dated_decision("2025-01-01", GB, IndexAction::Keep)];
+        let soft_deleted_times: HashMap<String, DateTime<Utc>> = HashMap::from([(
+            String::from("logs-2025-01-01"),
+            utc("2025-01-03 00:00:00").and_utc(),
+        )]);
+
+        IndexClearServicePub::new().apply_soft_delete(
+            &target_index,
+            &mut rule_plan,
+            &soft_deleted_times,
+            utc("2025-01-20 00:00:00"),
+        );
+
+        assert_eq!(rule_plan[0].action, IndexAction::Keep);
+        assert!(rule_plan[0].soft_deleted_at.is_some());
+        assert!(rule_plan[0].reason.contains("no longer expired"));
+    }
+
+    #[test]
+    fn rule_without_soft_delete_deletes_directly() {
+        let target_index: TargetIndex = rule("index_name = \"logs-*\"\nduration_days = 1");
+        let mut rule_plan: Vec<IndexDecision> =
+            vec![dated_decision("2025-01-01", GB, IndexAction::Delete)];
+
+        IndexClearServicePub::new().apply_soft_delete(
+            &target_index,
+            &mut rule_plan,
+            &HashMap::new(),
+            utc("2025-01-10 00:00:00"),
+        );
+
+        assert_eq!(rule_plan[0].action, IndexAction::Delete);
+        assert!(rule_plan[0].soft_delete_mode.is_none());
+    }
+
+    fn cat_index(index_name: &str) -> CatIndex {
+        serde_json::from_value(json!({ "index": index_name })).unwrap()
+    }
+
+    fn protections_of(
+        index_protections: &HashMap<String, Vec<IndexProtection>>,
+        index_name: &str,
+    ) -> Vec<IndexProtection> {
+        index_protections.get(index_name).cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn write_alias_protects_write_and_sole_indices() {
+        let service: IndexClearServicePub = IndexClearServicePub::new();
+        let index_aliases: Value = json!({
+            "logs-1": { "aliases": { "logs-write": { "is_write_index": true }, "logs-read": {} } },
+            "logs-2": { "aliases": { "logs-read": {}, "logs-old": { "is_write_index": false } } },
+            "logs-3": { "aliases": { "logs-single": {} } },
+            "logs-4": { "aliases": {} }
+        });
+
+        assert_eq!(
+            service.get_implicit_alias_names(&index_aliases),
+            vec![String::from("logs-read"), String::from("logs-single")]
+        );
+
+        /* is_write_index 가 없는 alias 는 인덱스 하나만 가리킬 때만 write alias 이다. */
+        let alias_indices: Value = json!({
+            "logs-1": { "aliases": { "logs-read": {} } },
+            "logs-2": { "aliases": { "logs-read": {} } },
+            "logs-3": { "aliases": { "logs-single": {} } }
+        });
+        let index_protections: HashMap<String, Vec<IndexProtection>> =
+            service.classify_cluster_protections(&index_aliases, &alias_indices, &Value::Null);
+
+        assert_eq!(
+            protections_of(&index_protections, "logs-1"),
+            vec![IndexProtection::WriteAlias(String::from("logs-write"))]
+        );
+        assert!(protections_of(&index_protections, "logs-2").is_empty());
+        assert_eq!(
+            protections_of(&index_protections, "logs-3"),
+            vec![IndexProtection::WriteAlias(String::from("logs-single"))]
+        );
+        assert!(protections_of(&index_protections, "logs-4").is_empty());
+
+        /* 규칙 밖의 인덱스도 같은 alias 를 가지고 있다면 유일한 인덱스가 아니다. */
+        let alias_indices: Value = json!({
+            "logs-3": { "aliases": { "logs-single": {} } },
+            "other-1": { "aliases": { "logs-single": {} } }
+        });
+        let index_protections: HashMap<String, Vec<IndexProtection>> =
+            service.classify_cluster_protections(&index_aliases, &alias_indices, &Value::Null);
+
+        assert!(protections_of(&index_protections, "logs-3").is_empty());
+    }
+
+    #[test]
+    fn ilm_and_ccr_settings_protect_indices() {
+        let protection_settings: Value = json!({
+            "logs-1": { "settings": { "index": { "lifecycle": { "name": "hot-warm" } } } },
+            "logs-2": { "settings": { "index": {
+                "xpack": { "ccr": { "following_index": "true" } }
+            } } },
+            "logs-3": { "settings": { "index": {
+                "lifecycle": { "name": "" },
+                "xpack": { "ccr": { "following_index": "false" } }
+            } } },
+            "logs-4": { "settings": { "index": {
+                "lifecycle": { "name": "hot-warm" },
+                "xpack": { "ccr": { "following_index": true } }
+            } } }
+        });
+
+        let index_protections: HashMap<String, Vec<IndexProtection>> = IndexClearServicePub::new()
+            .classify_cluster_protections(&Value::Null, &Value::Null, &protection_settings);
+
+        assert_eq!(
+            protections_of(&index_protections, "logs-1"),
+            vec![IndexProtection::IlmManaged(String::from("hot-warm"))]
+        );
+        assert_eq!(
+            protections_of(&index_protections, "logs-2"),
+            vec![IndexProtection::CcrFollower]
+        );
+        assert!(protections_of(&index_protections, "logs-3").is_empty());
+        assert_eq!(
+            protections_of(&index_protections, "logs-4"),
+            vec![
+                IndexProtection::IlmManaged(String::from("hot-warm")),
+                IndexProtection::CcrFollower
+            ]
+        );
+    }
+
+    #[test]
+    fn labels_and_legal_holds_protect_indices() {
+        let target_index: TargetIndex = rule(concat!(
+            "index_name = \"logs-*\"\nduration_days = 30\n",
+            "protect_labels = [\"keep-forever\"]",
+        ));
+        let meta_res: Value = json!({
+            "logs-1": { "mappings": { "_meta": { "yummy_retention": {
+                "labels": ["keep-forever", "team-a"]
+            } } } },
+            "logs-2": { "mappings": { "_meta": { "yummy_retention": { "labels": ["team-a"] } } } },
+            "logs-3": { "mappings": { "_meta": { "yummy_retention": {
+                "legal_hold": { "reason": "CASE-1", "owner": "legal", "expires": "2025-01-31" }
+            } } } },
+            "logs-4": { "mappings": { "_meta": { "yummy_retention": {
+                "legal_hold": { "reason": "CASE-2", "owner": "legal", "expires": "2025-01-09" }
+            } } } }
+        });
+        let retention_metas: HashMap<String, IndexRetentionMeta> =
+            parse_index_retention_metas(&meta_res);
+        let legal_holds: LegalHoldList = toml::from_str(
+            "[[hold]]\nindex = \"logs-5, logs-6\"\nreason = \"CASE-3\"\nowner = \"legal\"",
+        )
+        .unwrap();
+        let cat_indexes: Vec<CatIndex> = (1..=6)
+            .map(|idx| cat_index(&format!("logs-{}", idx)))
+            .collect();
+
+        let index_protections: HashMap<String, Vec<IndexProtection>> = IndexClearServicePub::new()
+            .classify_meta_protections(
+                &target_index,
+                &cat_indexes,
+                &retention_metas,
+                &legal_holds,
+                NaiveDate::from_ymd_opt(2025, 1, 10).unwrap(),
+            );
+
+        assert_eq!(
+            protections_of(&index_protections, "logs-1"),
+            vec![IndexProtection::Label(String::from("keep-forever"))]
+        );
+        assert!(protections_of(&index_protections, "logs-2").is_empty());
+        assert!(matches!(
+            protections_of(&index_protections, "logs-3").as_slice(),
+            [IndexProtection::LegalHold(legal_hold)] if legal_hold.reason() == "CASE-1"
+        ));
+        /* 만료된 홀드는 보호하지 않는다. */
+        assert!(protections_of(&index_protections, "logs-4").is_empty());
+
+        for index_name in ["logs-5", "logs-6"] {
+            assert!(matches!(
+                protections_of(&index_protections, index_name).as_slice(),
+                [IndexProtection::LegalHold(legal_hold)] if legal_hold.reason() == "CASE-3"
+            ));
+        }
+    }
+
+    #[test]
+    fn protected_index_is_skipped_whatever_policy_removed_it() {
+        let target_index: TargetIndex = rule(concat!(
+            "index_name = \"logs-*\"\nduration_days = 30\n",
+            "max_total_size = \"25gb\"\nmax_keep = 1",
+        ));
+        let service: IndexClearServicePub = IndexClearServicePub::new();
+        let mut rule_plan: Vec<IndexDecision> = vec![
+            dated_decision("2025-01-01", 10 * GB, IndexAction::Delete),
+            dated_decision("2025-01-02", 10 * GB, IndexAction::Keep),
+            dated_decision("2025-01-03", 10 * GB, IndexAction::Keep),
+            dated_decision("2025-01-04", 10 * GB, IndexAction::Keep),
+            dated_decision("2025-01-05", 10 * GB, IndexAction::Keep),
+        ];
+
+        service.apply_size_retention(&target_index, &mut rule_plan).unwrap();
+        service.apply_count_retention(&target_index, &mut rule_plan);
+
+        /* 나이, 용량, 용량, 개수 순서로 삭제되고 가장 최근 인덱스만 남는다. */
+        assert_eq!(
+            actions(&rule_plan),
+            vec![
+                IndexAction::Delete,
+                IndexAction::Delete,
+                IndexAction::Delete,
+                IndexAction::Delete,
+                IndexAction::Keep
+            ]
+        );
+
+        let protections: Vec<IndexProtection> = vec![
+            IndexProtection::WriteAlias(String::from("logs-write")),
+            IndexProtection::IlmManaged(String::from("hot-warm")),
+            IndexProtection::CcrFollower,
+            IndexProtection::Label(String::from("keep-forever")),
+            IndexProtection::Label(String::from("keep-forever")),
+        ];
+
+        for (decision, protection) in rule_plan.iter_mut().zip(protections) {
+            decision.protect(vec![protection]);
+        }
+
+        assert_eq!(
+            actions(&rule_plan),
+            vec![
+                IndexAction::Skip,
+                IndexAction::Skip,
+                IndexAction::Skip,
+                IndexAction::Skip,
+                IndexAction::Keep
+            ]
+        );
+        assert!(rule_plan[0].reason.starts_with("protected (write index of alias 'logs-write')"));
+        assert!(rule_plan[1].reason.contains("max_total_size"));
+        assert!(rule_plan[3].reason.contains("max_keep"));
+        assert_eq!(rule_plan[4].protections().len(), 1);
+    }
+
+    #[test]
+    fn protected_index_is_not_soft_deleted() {
+        let target_index: TargetIndex = rule(concat!(
+            "index_name = \"logs-*\"\nduration_days = 1\n",
+            "soft_delete_days = 7",
+        ));
+        let mut rule_plan: Vec<IndexDecision> =
+            vec![dated_decision("2025-01-01", GB, IndexAction::Delete)];
+
+        IndexClearServicePub::new().apply_soft_delete(
+            &target_index,
+            &mut rule_plan,
+            &HashMap::new(),
+            utc("2025-01-10 00:00:00"),
+        );
+        assert_eq!(rule_plan[0].action, IndexAction::SoftDelete);
+
+        rule_plan[0].protect(vec![IndexProtection::LegalHold(LegalHold {
+            reason: String::from("CASE-1"),
+            owner: String::from("legal"),
+            expires: None,
+        })]);
+
+        assert_eq!(rule_plan[0].action, IndexAction::Skip);
+        assert!(rule_plan[0].soft_delete_mode.is_none());
+        assert!(rule_plan[0].reason.contains("refused to"));
+    }
+
+    fn disk_usage(used: u64, total: u64) -> ClusterDiskUsage {
+        ClusterDiskUsage::new(vec![CatAllocation {
+            node: Some(String::from("node-1")),
+            disk_used: Some(used.to_string()),
+            disk_total: Some(total.to_string()),
+        }])
+    }
+
+    fn rule_decision(index_name: &str, index_date: &str, action: IndexAction) -> IndexDecision {
+        let mut decision: IndexDecision = IndexDecision::new(
+            index_name,
+            "rule",
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            Some(GB),
+            None,
+        )
+        .with_index_time(utc(&format!("{} 00:00:00", index_date)));
+        decision.action = action;
+        decision
+    }
+
+    fn index_names(decisions: &[IndexDecision]) -> Vec<&str> {
+        decisions
+            .iter()
+            .map(|decision| decision.index_name().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn emergency_deletes_expired_then_low_priority_then_oldest() {
+        let logs_rule: TargetIndex =
+            rule("index_name = \"logs-*\"\nduration_days = 3\npriority = 1");
+        let metrics_rule: TargetIndex = rule("index_name = \"metrics-*\"\nduration_days = 30");
+        let rule_plans: Vec<(&TargetIndex, Vec<IndexDecision>)> = vec![
+            (
+                &logs_rule,
+                vec![
+                    rule_decision("logs-new", "2025-01-05", IndexAction::Keep),
+                    rule_decision("logs-old", "2025-01-01", IndexAction::Delete),
+                ],
+            ),
+            (
+                &metrics_rule,
+                vec![
+                    rule_decision("metrics-new", "2025-01-03", IndexAction::Keep),
+                    rule_decision("metrics-old", "2025-01-02", IndexAction::Keep),
+                ],
+            ),
+        ];
+
+        /* 목표 사용률이 낮아서 후보를 모두 삭제한다. */
+        let emergency_plan: EmergencyPlan = IndexClearServicePub::new().select_emergency_indexes(
+            &disk_usage(10 * GB, 10 * GB),
+            &EmergencyConfig::new(90.0, 1.0),
+            rule_plans,
+            utc("2025-01-10 00:00:00"),
+        );
+
+        assert_eq!(
+            index_names(emergency_plan.decisions()),
+            vec!["logs-old", "metrics-old", "metrics-new", "logs-new"]
+        );
+        assert!(emergency_plan
+            .decisions()
+            .iter()
+            .all(|decision| decision.action == IndexAction::Delete));
+        assert!(!emergency_plan.decisions()[0].reason.contains("emergency cleanup"));
+        assert!(emergency_plan.decisions()[1].reason.contains("emergency cleanup"));
+        assert!(emergency_plan.kept_decisions().is_empty());
+    }
+
+    #[test]
+    fn emergency_stops_once_usage_is_below_target() {
+        let target_index: TargetIndex = rule("index_name = \"logs-*\"\nduration_days = 30");
+        let rule_plan: Vec<IndexDecision> = vec![
+            rule_decision("logs-1", "2025-01-01", IndexAction::Keep),
+            rule_decision("logs-2", "2025-01-02", IndexAction::Keep),
+            rule_decision("logs-3", "2025-01-03", IndexAction::Keep),
+            rule_decision("logs-4", "2025-01-04", IndexAction::Keep),
+        ];
+
+        /* 50% 사용중 -> 1GB 씩 지워서 40% 아래가 되려면 3개를 지워야 한다. (35%) */
+        let emergency_plan: EmergencyPlan = IndexClearServicePub::new().select_emergency_indexes(
+            &disk_usage(10 * GB, 20 * GB),
+            &EmergencyConfig::new(45.0, 40.0),
+            vec![(&target_index, rule_plan)],
+            utc("2025-01-10 00:00:00"),
+        );
+
+        assert_eq!(
+            index_names(emergency_plan.decisions()),
+            vec!["logs-1", "logs-2", "logs-3"]
+        );
+        assert_eq!(index_names(emergency_plan.kept_decisions()), vec!["logs-4"]);
+        assert_eq!(emergency_plan.kept_decisions()[0].action, IndexAction::Keep);
+        assert_eq!(*emergency_plan.used_percent(), 50.0);
+        assert_eq!(*emergency_plan.projected_percent(), 35.0);
+    }
+
+    #[test]
+    fn emergency_never_deletes_floor_min_keep_protected_or_undated_indices() {
+        let target_index: TargetIndex = rule(concat!(
+            "index_name = \"logs-*\"\nduration_days = 30\n",
+            "min_keep = 1\nnever_below_days = 5",
+        ));
+        let mut protected: IndexDecision =
+            rule_decision("logs-protected", "2025-01-02", IndexAction::Keep);
+        protected.protect(vec![IndexProtection::CcrFollower]);
+        let undated: IndexDecision = IndexDecision::new(
+            "logs-undated",
+            "rule",
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            Some(GB),
+            None,
+        );
+        let rule_plan: Vec<IndexDecision> = vec![
+            rule_decision("logs-newest", "2025-01-08", IndexAction::Keep),
+            rule_decision("logs-recent", "2025-01-07", IndexAction::Keep),
+            rule_decision("logs-old", "2025-01-03", IndexAction::Keep),
+            protected,
+            rule_decision("logs-oldest", "2025-01-01", IndexAction::Keep),
+            undated,
+        ];
+
+        let emergency_plan: EmergencyPlan = IndexClearServicePub::new().select_emergency_indexes(
+            &disk_usage(10 * GB, 10 * GB),
+            &EmergencyConfig::new(90.0, 1.0),
+            vec![(&target_index, rule_plan)],
+            utc("2025-01-10 00:00:00"),
+        );
+
+        /* logs-newest 는 min_keep, logs-recent 는 never_below_days 로 남는다. */
+        assert_eq!(index_names(emergency_plan.decisions()), vec!["logs-oldest", "logs-old"]);
+        assert_eq!(
+            index_names(emergency_plan.kept_decisions()),
+            vec!["logs-newest", "logs-recent", "logs-protected", "logs-undated"]
+        );
+        assert_eq!(*emergency_plan.projected_percent(), 80.0);
+    }
+
+    fn phase_target(phase_toml: &str) -> PhaseTarget {
+        let phase: IndexPhase = toml::from_str(phase_toml).unwrap();
+        PhaseTarget::from_phases(
+            &[phase],
+            utc("2025-01-01 00:00:00"),
+            utc("2025-01-10 00:00:00"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn phase_settings_are_updated_only_where_they_differ() {
+        let phase_target: PhaseTarget = phase_target(concat!(
+            "after_days = 7\nreplicas = 0\n",
+            "require_attributes = { data = \"warm\", zone = \"a\" }",
+        ));
+        let settings: Value = json!({
+            "index.number_of_replicas": "1",
+            "index.routing.allocation.require.zone": "a",
+        });
+
+        let settings_update: serde_json::Map<String, Value> =
+            IndexClearServicePub::new().diff_phase_settings(&phase_target, &settings);
+
+        assert_eq!(
+            Value::Object(settings_update),
+            json!({
+                "index.number_of_replicas": 0,
+                "index.routing.allocation.require.data": "warm",
+            })
+        );
+    }
+
+    #[test]
+    fn phase_already_applied_needs_no_update() {
+        let phase_target: PhaseTarget = phase_target(concat!(
+            "after_days = 7\nreplicas = 0\n",
+            "require_attributes = { data = \"warm\" }",
+        ));
+        let settings: Value = json!({
+            "index.number_of_replicas": "0",
+            "index.routing.allocation.require.data": "warm",
+        });
+
+        assert!(IndexClearServicePub::new()
+            .diff_phase_settings(&phase_target, &settings)
+            .is_empty());
+    }
+
+    #[test]
+    fn max_segment_count_looks_at_every_shard_copy() {
+        let segment_counts: Value = json!({
+            "shards": {
+                "0": [{ "segments": { "count": 3 } }, { "segments": { "count": 5 } }],
+                "1": [{ "segments": { "count": 1 } }],
+            }
+        });
+        let service: IndexClearServicePub = IndexClearServicePub::new();
 
-        Ok(parsed_date)
+        assert_eq!(service.max_segment_count(&segment_counts), Some(5));
+        /* 닫힌 인덱스는 세그먼트 정보가 없으므로 forcemerge 하지 않는다. */
+        assert_eq!(service.max_segment_count(&Value::Null), None);
     }
 }
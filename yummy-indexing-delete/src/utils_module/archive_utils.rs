@@ -0,0 +1,120 @@
+use crate::common::*;
+
+use crate::model::archive_manifest::*;
+
+#[doc = "압축된 NDJSON 아카이브 파일에 한 줄씩 써주는 writer"]
+pub enum ArchiveWriter {
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zstd(ZstdEncoder<'static, BufWriter<File>>),
+}
+
+impl ArchiveWriter {
+    #[doc = "아카이브 파일을 새로 만들어주는 함수 (이미 있다면 덮어쓴다.)"]
+    /// # Arguments
+    /// * `file_path` - 아카이브 파일 경로
+    /// * `compression` - 압축 방식
+    ///
+    /// # Returns
+    /// * Result<ArchiveWriter, anyhow::Error>
+    pub fn create(file_path: &Path, compression: ArchiveCompression) -> Result<Self, anyhow::Error> {
+        let file: BufWriter<File> = BufWriter::new(File::create(file_path)?);
+
+        let writer: ArchiveWriter = match compression {
+            ArchiveCompression::Gzip => {
+                ArchiveWriter::Gzip(GzEncoder::new(file, flate2::Compression::default()))
+            }
+            ArchiveCompression::Zstd => ArchiveWriter::Zstd(ZstdEncoder::new(file, 0)?),
+        };
+
+        Ok(writer)
+    }
+
+    #[doc = "한 줄을 써주는 함수"]
+    pub fn write_line(&mut self, line: &[u8]) -> Result<(), anyhow::Error> {
+        let writer: &mut dyn Write = match self {
+            ArchiveWriter::Gzip(encoder) => encoder,
+            ArchiveWriter::Zstd(encoder) => encoder,
+        };
+
+        writer.write_all(line)?;
+        writer.write_all(b"\n")?;
+
+        Ok(())
+    }
+
+    #[doc = "압축을 마무리하고 디스크에 반영해주는 함수"]
+    pub fn finish(self) -> Result<(), anyhow::Error> {
+        let file: BufWriter<File> = match self {
+            ArchiveWriter::Gzip(encoder) => encoder.finish()?,
+            ArchiveWriter::Zstd(encoder) => encoder.finish()?,
+        };
+
+        let file: File = file.into_inner().map_err(|e| anyhow!("{:?}", e.error()))?;
+        file.sync_all()?;
+
+        Ok(())
+    }
+}
+
+#[doc = "파일의 SHA-256 을 16진수 문자열로 반환해주는 함수"]
+pub fn sha256_file(file_path: &Path) -> Result<String, anyhow::Error> {
+    let mut reader: BufReader<File> = BufReader::new(File::open(file_path)?);
+    let mut hasher: Sha256 = Sha256::new();
+
+    std::io::copy(&mut reader, &mut hasher)?;
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+#[doc = "압축된 NDJSON 아카이브 파일을 한 줄씩 읽을 수 있도록 열어주는 함수"]
+/// # Arguments
+/// * `file_path` - 아카이브 파일 경로
+/// * `compression` - 압축 방식
+///
+/// # Returns
+/// * Result<Box<dyn BufRead + Send>, anyhow::Error>
+pub fn open_archive_reader(
+    file_path: &Path,
+    compression: ArchiveCompression,
+) -> Result<Box<dyn BufRead + Send>, anyhow::Error> {
+    let file: File = File::open(file_path)?;
+
+    let reader: Box<dyn BufRead + Send> = match compression {
+        ArchiveCompression::Gzip => Box::new(BufReader::new(GzDecoder::new(file))),
+        ArchiveCompression::Zstd => Box::new(BufReader::new(ZstdDecoder::new(file)?)),
+    };
+
+    Ok(reader)
+}
+
+#[doc = "아카이브 디렉토리의 매니페스트를 읽어주는 함수 (매니페스트가 없는 아카이브는 완성되지 않은 것이므로 에러)"]
+/// # Arguments
+/// * `index_dir` - 인덱스 하나의 아카이브 디렉토리
+///
+/// # Returns
+/// * Result<ArchiveManifest, anyhow::Error>
+pub fn read_archive_manifest(index_dir: &Path) -> Result<ArchiveManifest, anyhow::Error> {
+    let manifest_path: PathBuf = index_dir.join(ARCHIVE_MANIFEST_FILE);
+
+    if !manifest_path.exists() {
+        return Err(anyhow!(
+            "[Error][read_archive_manifest] '{}' not found. The archive is missing or incomplete.",
+            manifest_path.display()
+        ));
+    }
+
+    let file: File = File::open(&manifest_path)?;
+    let manifest: ArchiveManifest = from_reader(BufReader::new(file)).map_err(|e| {
+        anyhow!(
+            "[Error][read_archive_manifest] Failed to parse '{}': {:?}",
+            manifest_path.display(),
+            e
+        )
+    })?;
+
+    Ok(manifest)
+}
@@ -57,3 +57,23 @@ pub fn convert_json_from_struct<T: Serialize>(input_struct: &T) -> Result<Value,
         )
     })
 }
+
+#[doc = "객체를 Json 형태로 변환하여 파일에 써주는 함수."]
+/// # Arguments
+/// * `file_path` - 저장할 파일 경로
+/// * `input_struct` - json 으로 변환할 구조체
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub fn write_json_to_file<T: Serialize>(file_path: &str, input_struct: &T) -> Result<(), anyhow::Error> {
+    let file: File = File::create(file_path)?;
+    serde_json::to_writer_pretty(file, input_struct).map_err(|err| {
+        anyhow!(
+            "[Error][write_json_to_file()] Failed to write JSON to '{}': {}",
+            file_path,
+            err
+        )
+    })?;
+
+    Ok(())
+}
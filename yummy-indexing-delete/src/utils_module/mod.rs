@@ -1,3 +1,10 @@
+/* 공용 유틸 함수 모음이므로 당장 사용하지 않는 함수가 있어도 경고하지 않는다. */
+#![allow(dead_code)]
+
+pub mod archive_utils;
 pub mod io_utils;
 pub mod logger_utils;
+pub mod secret_utils;
+pub mod signal_utils;
+pub mod size_utils;
 pub mod time_utils;
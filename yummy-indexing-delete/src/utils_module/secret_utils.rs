@@ -0,0 +1,152 @@
+use crate::common::*;
+
+/* 암호화된 비밀 파일의 머리말 - 형식: "yummy-secrets:v1:<salt>:<nonce>:<암호문>" (각 값은 base64) */
+const SECRETS_FILE_HEADER: &str = "yummy-secrets:v1";
+
+/* 패스프레이즈로부터 키를 만들 때의 PBKDF2 반복 횟수 */
+const SECRETS_KDF_ROUNDS: u32 = 200_000;
+
+const SECRETS_SALT_LEN: usize = 16;
+const SECRETS_NONCE_LEN: usize = 12;
+
+#[doc = "패스프레이즈와 salt 로 AES-256 키를 만들어주는 함수"]
+fn derive_secrets_key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    let mut key: [u8; 32] = [0u8; 32];
+    pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, SECRETS_KDF_ROUNDS, &mut key);
+    key
+}
+
+#[doc = "평문을 패스프레이즈로 암호화해서 비밀 파일 내용으로 만들어주는 함수 (AES-256-GCM)"]
+/// # Arguments
+/// * `plaintext` - 암호화할 내용
+/// * `passphrase` - 암호화에 사용할 패스프레이즈
+///
+/// # Returns
+/// * Result<String, anyhow::Error> - 비밀 파일에 쓸 한 줄
+pub fn encrypt_secrets(plaintext: &str, passphrase: &str) -> Result<String, anyhow::Error> {
+    let mut rng: StdRng = StdRng::from_entropy();
+    let mut salt: [u8; SECRETS_SALT_LEN] = [0u8; SECRETS_SALT_LEN];
+    let mut nonce: [u8; SECRETS_NONCE_LEN] = [0u8; SECRETS_NONCE_LEN];
+    rng.fill(&mut salt);
+    rng.fill(&mut nonce);
+
+    let cipher: Aes256Gcm = Aes256Gcm::new_from_slice(&derive_secrets_key(passphrase, &salt))
+        .map_err(|e| anyhow!("[Error][encrypt_secrets] {}", e))?;
+    let ciphertext: Vec<u8> = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+        .map_err(|e| anyhow!("[Error][encrypt_secrets] Failed to encrypt secrets: {}", e))?;
+
+    Ok(format!(
+        "{}:{}:{}:{}",
+        SECRETS_FILE_HEADER,
+        BASE64_STANDARD.encode(salt),
+        BASE64_STANDARD.encode(nonce),
+        BASE64_STANDARD.encode(ciphertext)
+    ))
+}
+
+#[doc = "encrypt_secrets 로 만든 비밀 파일 내용을 복호화해주는 함수"]
+/// # Arguments
+/// * `content` - 비밀 파일 내용
+/// * `passphrase` - 암호화에 사용한 패스프레이즈
+///
+/// # Returns
+/// * Result<String, anyhow::Error> - 패스프레이즈가 틀리거나 파일이 변조된 경우 에러
+pub fn decrypt_secrets(content: &str, passphrase: &str) -> Result<String, anyhow::Error> {
+    let body: &str = content
+        .trim()
+        .strip_prefix(SECRETS_FILE_HEADER)
+        .and_then(|body| body.strip_prefix(':'))
+        .ok_or_else(|| anyhow!("[Error][decrypt_secrets] Not a secrets file (expected '{}:...')", SECRETS_FILE_HEADER))?;
+
+    let parts: Vec<Vec<u8>> = body
+        .split(':')
+        .map(|part| BASE64_STANDARD.decode(part))
+        .collect::<Result<Vec<Vec<u8>>, _>>()
+        .map_err(|e| anyhow!("[Error][decrypt_secrets] Malformed secrets file: {}", e))?;
+
+    let (salt, nonce, ciphertext) = match parts.as_slice() {
+        [salt, nonce, ciphertext] if nonce.len() == SECRETS_NONCE_LEN => (salt, nonce, ciphertext),
+        _ => return Err(anyhow!("[Error][decrypt_secrets] Malformed secrets file")),
+    };
+
+    let cipher: Aes256Gcm = Aes256Gcm::new_from_slice(&derive_secrets_key(passphrase, salt))
+        .map_err(|e| anyhow!("[Error][decrypt_secrets] {}", e))?;
+    let plaintext: Vec<u8> = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("[Error][decrypt_secrets] Failed to decrypt secrets (wrong passphrase or corrupted file)"))?;
+
+    Ok(String::from_utf8(plaintext)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAINTEXT: &str = "ES_ID=elastic\nES_PW=changeme\n";
+
+    /* 키 유도(PBKDF2)가 느리므로 테스트끼리 암호화한 내용을 같이 쓴다. */
+    fn encrypted_content() -> &'static str {
+        static CONTENT: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+        CONTENT.get_or_init(|| encrypt_secrets(PLAINTEXT, "passphrase").unwrap())
+    }
+
+    #[test]
+    fn encrypted_secrets_round_trip() {
+        let content: &str = encrypted_content();
+
+        assert!(content.starts_with("yummy-secrets:v1:"));
+        assert!(!content.contains("changeme"));
+        assert_eq!(decrypt_secrets(content, "passphrase").unwrap(), PLAINTEXT);
+        /* 파일 끝의 줄바꿈은 무시한다. */
+        assert_eq!(decrypt_secrets(&format!("{}\n", content), "passphrase").unwrap(), PLAINTEXT);
+    }
+
+    #[test]
+    fn each_encryption_uses_new_salt_and_nonce() {
+        assert_ne!(encrypt_secrets(PLAINTEXT, "passphrase").unwrap(), encrypted_content());
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let content: &str = encrypted_content();
+
+        assert!(decrypt_secrets(content, "passphrase2").is_err());
+    }
+
+    #[test]
+    fn tampered_ciphertext_is_rejected() {
+        let content: &str = encrypted_content();
+        let (head, ciphertext) = content.rsplit_once(':').unwrap();
+        let mut ciphertext: Vec<u8> = BASE64_STANDARD.decode(ciphertext).unwrap();
+        ciphertext[0] ^= 0x01;
+
+        let tampered: String = format!("{}:{}", head, BASE64_STANDARD.encode(ciphertext));
+
+        assert!(decrypt_secrets(&tampered, "passphrase").is_err());
+    }
+
+    #[test]
+    fn tampered_salt_is_rejected() {
+        let content: &str = encrypted_content();
+        let parts: Vec<&str> = content.split(':').collect();
+        let other_salt: String = BASE64_STANDARD.encode([0u8; SECRETS_SALT_LEN]);
+
+        let tampered: String =
+            format!("{}:{}:{}:{}:{}", parts[0], parts[1], other_salt, parts[3], parts[4]);
+
+        assert!(decrypt_secrets(&tampered, "passphrase").is_err());
+    }
+
+    #[test]
+    fn malformed_content_is_rejected() {
+        let content: &str = encrypted_content();
+        let truncated: &str = content.rsplit_once(':').unwrap().0;
+
+        assert!(decrypt_secrets(PLAINTEXT, "passphrase").is_err());
+        assert!(decrypt_secrets("yummy-secrets:v1", "passphrase").is_err());
+        assert!(decrypt_secrets(truncated, "passphrase").is_err());
+        assert!(decrypt_secrets("yummy-secrets:v1:!!:!!:!!", "passphrase").is_err());
+        assert!(decrypt_secrets("yummy-secrets:v2:AA==:AA==:AA==", "passphrase").is_err());
+    }
+}
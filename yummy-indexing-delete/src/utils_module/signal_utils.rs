@@ -0,0 +1,19 @@
+use crate::common::*;
+
+#[doc = "SIGTERM 또는 SIGINT(Ctrl+C) 가 들어올 때까지 기다려주는 함수"]
+pub async fn wait_shutdown_signal() -> Result<(), anyhow::Error> {
+    let mut sigterm: Signal = signal(SignalKind::terminate())
+        .map_err(|e| anyhow!("[Error][wait_shutdown_signal] Failed to listen SIGTERM: {:?}", e))?;
+
+    tokio::select! {
+        _ = sigterm.recv() => {
+            info!("[wait_shutdown_signal] SIGTERM received.");
+        }
+        result = tokio::signal::ctrl_c() => {
+            result.map_err(|e| anyhow!("[Error][wait_shutdown_signal] Failed to listen SIGINT: {:?}", e))?;
+            info!("[wait_shutdown_signal] SIGINT received.");
+        }
+    }
+
+    Ok(())
+}
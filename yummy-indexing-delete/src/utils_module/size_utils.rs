@@ -0,0 +1,104 @@
+use crate::common::*;
+
+const BYTE_UNITS: [(&str, u64); 6] = [
+    ("pb", 1024 * 1024 * 1024 * 1024 * 1024),
+    ("tb", 1024 * 1024 * 1024 * 1024),
+    ("gb", 1024 * 1024 * 1024),
+    ("mb", 1024 * 1024),
+    ("kb", 1024),
+    ("b", 1),
+];
+
+#[doc = "Elasticsearch 양식의 용량 문자열(예: 200gb, 1.5tb)을 byte 로 바꿔주는 함수"]
+/// # Arguments
+/// * `size_str` - 용량 문자열 (단위: b, kb, mb, gb, tb, pb / 단위가 없으면 byte)
+///
+/// # Returns
+/// * Result<u64, anyhow::Error>
+pub fn parse_byte_size(size_str: &str) -> Result<u64, anyhow::Error> {
+    let size_lower: String = size_str.trim().to_lowercase();
+
+    let (number, unit_bytes) = BYTE_UNITS
+        .iter()
+        .find_map(|(unit, unit_bytes)| {
+            size_lower
+                .strip_suffix(unit)
+                .map(|number| (number.trim(), *unit_bytes))
+        })
+        .unwrap_or((size_lower.as_str(), 1));
+
+    let number: f64 = number.parse::<f64>().map_err(|e| {
+        anyhow!(
+            "[Error][parse_byte_size()] Invalid size '{}': {}",
+            size_str,
+            e
+        )
+    })?;
+
+    if number < 0.0 {
+        return Err(anyhow!(
+            "[Error][parse_byte_size()] Size must not be negative: '{}'",
+            size_str
+        ));
+    }
+
+    Ok((number * unit_bytes as f64) as u64)
+}
+
+#[doc = "byte 를 사람이 읽기 쉬운 용량 문자열로 바꿔주는 함수"]
+pub fn format_byte_size(bytes: u64) -> String {
+    for (unit, unit_bytes) in BYTE_UNITS {
+        if bytes >= unit_bytes && unit_bytes > 1 {
+            return format!("{:.1}{}", bytes as f64 / unit_bytes as f64, unit);
+        }
+    }
+
+    format!("{}b", bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn units_are_binary_multiples() {
+        assert_eq!(parse_byte_size("200gb").unwrap(), 200 * GB);
+        assert_eq!(parse_byte_size("1kb").unwrap(), 1024);
+        assert_eq!(parse_byte_size("3mb").unwrap(), 3 * 1024 * 1024);
+        assert_eq!(parse_byte_size("2tb").unwrap(), 2 * 1024 * GB);
+        assert_eq!(parse_byte_size("1pb").unwrap(), 1024 * 1024 * GB);
+        assert_eq!(parse_byte_size("12b").unwrap(), 12);
+    }
+
+    #[test]
+    fn fractions_case_and_spaces_are_accepted() {
+        assert_eq!(parse_byte_size("1.5tb").unwrap(), 1536 * GB);
+        assert_eq!(parse_byte_size(" 10 GB ").unwrap(), 10 * GB);
+        assert_eq!(parse_byte_size("0.5Kb").unwrap(), 512);
+    }
+
+    #[test]
+    fn size_without_unit_is_bytes() {
+        assert_eq!(parse_byte_size("4096").unwrap(), 4096);
+        assert_eq!(parse_byte_size("0").unwrap(), 0);
+    }
+
+    #[test]
+    fn invalid_sizes_are_rejected() {
+        assert!(parse_byte_size("").is_err());
+        assert!(parse_byte_size("gb").is_err());
+        assert!(parse_byte_size("ten gb").is_err());
+        assert!(parse_byte_size("10 xb").is_err());
+        assert!(parse_byte_size("-1gb").is_err());
+    }
+
+    #[test]
+    fn format_uses_largest_unit() {
+        assert_eq!(format_byte_size(0), "0b");
+        assert_eq!(format_byte_size(1023), "1023b");
+        assert_eq!(format_byte_size(1536), "1.5kb");
+        assert_eq!(format_byte_size(200 * GB), "200.0gb");
+    }
+}